@@ -0,0 +1,93 @@
+//! Derive macros for mysql_cdc.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Implements `FromRow` for a struct.
+/// Named fields are mapped by column name, tuple fields by column ordinal.
+/// Use `#[mysql_cdc(rename = "column")]` when the field name differs from the column name.
+#[proc_macro_derive(FromRow, attributes(mysql_cdc))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "FromRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let body = match fields {
+        Fields::Named(fields) => {
+            let mut values = Vec::new();
+            for (ordinal, field) in fields.named.iter().enumerate() {
+                let ident = field.ident.as_ref().unwrap();
+                let column = match column_name(field)? {
+                    Some(x) => x,
+                    None => ident.to_string().trim_start_matches("r#").to_string(),
+                };
+                values.push(quote! { #ident: get_column(row, table, #column, #ordinal)? });
+            }
+            quote! { Self { #(#values),* } }
+        }
+        Fields::Unnamed(fields) => {
+            let mut values = Vec::new();
+            for (ordinal, field) in fields.unnamed.iter().enumerate() {
+                let column = match column_name(field)? {
+                    Some(x) => x,
+                    None => ordinal.to_string(),
+                };
+                values.push(quote! { get_column(row, table, #column, #ordinal)? });
+            }
+            quote! { Self(#(#values),*) }
+        }
+        Fields::Unit => quote! { Self },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::mysql_cdc::events::row_events::from_row::FromRow for #name #type_generics #where_clause {
+            fn from_row(
+                row: &::mysql_cdc::events::row_events::row_data::RowData,
+                table: &::mysql_cdc::events::table_map_event::TableMapEvent,
+            ) -> ::std::result::Result<Self, ::mysql_cdc::errors::Error> {
+                #[allow(unused_imports)]
+                use ::mysql_cdc::events::row_events::from_row::get_column;
+                Ok(#body)
+            }
+        }
+    })
+}
+
+/// Reads column name from `#[mysql_cdc(rename = "...")]` attribute.
+fn column_name(field: &syn::Field) -> syn::Result<Option<String>> {
+    let mut result = None;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("mysql_cdc") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                let value: LitStr = meta.value()?.parse()?;
+                result = Some(value.value());
+                Ok(())
+            } else {
+                Err(meta.error("unsupported mysql_cdc attribute"))
+            }
+        })?;
+    }
+    Ok(result)
+}
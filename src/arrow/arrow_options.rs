@@ -0,0 +1,18 @@
+/// Settings of record batch accumulator.
+#[derive(Clone, Debug)]
+pub struct ArrowOptions {
+    /// Maximum number of rows in a record batch. Defaults to 8192.
+    pub batch_size: usize,
+
+    /// Defines whether updates add a row with values before the update. Defaults to true.
+    pub include_before_update: bool,
+}
+
+impl Default for ArrowOptions {
+    fn default() -> Self {
+        Self {
+            batch_size: 8192,
+            include_before_update: true,
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use crate::arrow::arrow_options::ArrowOptions;
+use crate::arrow::table_schema::{table_schema, ChangeType, METADATA_COLUMNS};
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::event_header::EventHeader;
+use crate::events::row_events::from_value::FromMySqlValue;
+use crate::events::row_events::json_binary::decode_json;
+use crate::events::row_events::mysql_value::{DateTime, MySqlValue};
+use crate::events::table_map_event::TableMapEvent;
+use arrow_array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Decimal128Array, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, Int8Array, NullArray, RecordBatch, StringArray,
+    TimestampMicrosecondArray, TimestampMillisecondArray, UInt16Array, UInt32Array, UInt64Array,
+    UInt8Array,
+};
+use arrow_schema::{DataType, SchemaRef, TimeUnit};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+/// Record batch of changed rows of a table.
+#[derive(Clone, Debug)]
+pub struct TableBatch {
+    /// Gets database name of the table.
+    pub database: String,
+
+    /// Gets name of the table.
+    pub table: String,
+
+    /// Gets changed rows. See table_schema for the layout.
+    pub batch: RecordBatch,
+}
+
+struct ChangeRow {
+    change_type: ChangeType,
+    binlog_file: Arc<str>,
+    binlog_position: u64,
+    timestamp: u32,
+    cells: Vec<Option<MySqlValue>>,
+}
+
+struct PendingTable {
+    table: TableMapEvent,
+    schema: SchemaRef,
+    rows: Vec<ChangeRow>,
+}
+
+/// Accumulates row events per table into Arrow record batches.
+/// Batches are returned when they reach the batch size or when the table schema changes.
+pub struct BatchAccumulator {
+    pub options: ArrowOptions,
+    binlog_file: Arc<str>,
+    table_map: HashMap<u64, TableMapEvent>,
+    pending: BTreeMap<(String, String), PendingTable>,
+}
+
+impl BatchAccumulator {
+    pub fn new(options: ArrowOptions) -> Self {
+        Self {
+            options,
+            binlog_file: Arc::from(""),
+            table_map: HashMap::new(),
+            pending: BTreeMap::new(),
+        }
+    }
+
+    /// Sets binlog file name of the following events.
+    /// The name is also updated by RotateEvent.
+    pub fn set_binlog_file(&mut self, binlog_file: &str) {
+        self.binlog_file = Arc::from(binlog_file);
+    }
+
+    /// Handles the event. TableMapEvent and RotateEvent are tracked, rows of row events are accumulated.
+    /// Returns batches that are complete after the event.
+    pub fn add(
+        &mut self,
+        header: &EventHeader,
+        event: &BinlogEvent,
+    ) -> Result<Vec<TableBatch>, Error> {
+        let position = header
+            .next_event_position
+            .saturating_sub(header.event_length) as u64;
+        let row = |change_type, cells: &Vec<Option<MySqlValue>>| ChangeRow {
+            change_type,
+            binlog_file: self.binlog_file.clone(),
+            binlog_position: position,
+            timestamp: header.timestamp,
+            cells: cells.clone(),
+        };
+
+        let (table_id, rows) = match event {
+            BinlogEvent::TableMapEvent(x) => {
+                self.table_map.insert(x.table_id, x.clone());
+                return Ok(Vec::new());
+            }
+            BinlogEvent::RotateEvent(x) => {
+                self.set_binlog_file(&x.binlog_filename);
+                return Ok(Vec::new());
+            }
+            BinlogEvent::WriteRowsEvent(x) => {
+                let rows = x.rows.iter().map(|r| row(ChangeType::Insert, &r.cells));
+                (x.table_id, rows.collect::<Vec<ChangeRow>>())
+            }
+            BinlogEvent::UpdateRowsEvent(x) => {
+                let mut rows = Vec::new();
+                for r in &x.rows {
+                    if self.options.include_before_update {
+                        rows.push(row(ChangeType::UpdateBefore, &r.before_update.cells));
+                    }
+                    rows.push(row(ChangeType::UpdateAfter, &r.after_update.cells));
+                }
+                (x.table_id, rows)
+            }
+            BinlogEvent::DeleteRowsEvent(x) => {
+                let rows = x.rows.iter().map(|r| row(ChangeType::Delete, &r.cells));
+                (x.table_id, rows.collect())
+            }
+            _ => return Ok(Vec::new()),
+        };
+
+        let table = self.table_map.get(&table_id).ok_or_else(|| {
+            Error::String(format!("No TableMapEvent found for table id {}", table_id))
+        })?;
+        let schema = Arc::new(table_schema(table)?);
+        let key = (table.database_name.clone(), table.table_name.clone());
+
+        let mut result = Vec::new();
+        // Rows of the previous table shape are written in a separate batch
+        if let Some(pending) = self.pending.get(&key) {
+            if pending.schema != schema {
+                let pending = self.pending.remove(&key).unwrap();
+                result.push(build_batch(pending)?);
+            }
+        }
+
+        let pending = self.pending.entry(key.clone()).or_insert(PendingTable {
+            table: table.clone(),
+            schema,
+            rows: Vec::new(),
+        });
+        pending.rows.extend(rows);
+
+        if pending.rows.len() >= self.options.batch_size {
+            let pending = self.pending.remove(&key).unwrap();
+            result.push(build_batch(pending)?);
+        }
+        Ok(result)
+    }
+
+    /// Returns batches of all accumulated rows.
+    pub fn flush(&mut self) -> Result<Vec<TableBatch>, Error> {
+        let pending = std::mem::take(&mut self.pending);
+        pending.into_values().map(build_batch).collect()
+    }
+}
+
+fn build_batch(pending: PendingTable) -> Result<TableBatch, Error> {
+    let rows = &pending.rows;
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|x| x.change_type.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|x| x.binlog_file.as_ref()),
+        )),
+        Arc::new(UInt64Array::from_iter_values(
+            rows.iter().map(|x| x.binlog_position),
+        )),
+        Arc::new(
+            TimestampMillisecondArray::from_iter_values(
+                rows.iter().map(|x| x.timestamp as i64 * 1000),
+            )
+            .with_timezone("UTC"),
+        ),
+    ];
+
+    let fields = &pending.schema.fields()[METADATA_COLUMNS..];
+    for (index, field) in fields.iter().enumerate() {
+        let column = ColumnBuilder {
+            table: &pending.table,
+            index,
+            name: field.name(),
+            unsigned: pending.table.is_unsigned(index).unwrap_or(false),
+        };
+        columns.push(column.build(field.data_type(), rows)?);
+    }
+
+    Ok(TableBatch {
+        database: pending.table.database_name.clone(),
+        table: pending.table.table_name.clone(),
+        batch: RecordBatch::try_new(pending.schema.clone(), columns)?,
+    })
+}
+
+/// Builds array of a table column from cells of the rows.
+struct ColumnBuilder<'a> {
+    table: &'a TableMapEvent,
+    index: usize,
+    name: &'a str,
+    unsigned: bool,
+}
+
+macro_rules! primitive_array {
+    ($self:ident, $rows:ident, $array:ty, $native:ty) => {
+        Arc::new(<$array>::from(
+            $self.collect($rows, |x| $self.convert::<$native>(x))?,
+        ))
+    };
+}
+
+impl ColumnBuilder<'_> {
+    fn build(&self, data_type: &DataType, rows: &[ChangeRow]) -> Result<ArrayRef, Error> {
+        let array: ArrayRef = match data_type {
+            DataType::Int8 => primitive_array!(self, rows, Int8Array, i8),
+            DataType::Int16 => primitive_array!(self, rows, Int16Array, i16),
+            DataType::Int32 => primitive_array!(self, rows, Int32Array, i32),
+            DataType::UInt8 => primitive_array!(self, rows, UInt8Array, u8),
+            DataType::UInt16 => primitive_array!(self, rows, UInt16Array, u16),
+            DataType::UInt32 => primitive_array!(self, rows, UInt32Array, u32),
+            DataType::Float32 => primitive_array!(self, rows, Float32Array, f32),
+            DataType::Float64 => primitive_array!(self, rows, Float64Array, f64),
+            DataType::Boolean => primitive_array!(self, rows, BooleanArray, bool),
+            DataType::Int64 => Arc::new(Int64Array::from(self.collect(rows, |x| match x {
+                MySqlValue::Time(x) => Ok(x.to_micros()),
+                _ => self.convert::<i64>(x),
+            })?)),
+            DataType::UInt64 => Arc::new(UInt64Array::from(self.collect(rows, |x| match x {
+                MySqlValue::Bit(bits) => Ok(bits.iter().fold(0, |x, bit| (x << 1) | *bit as u64)),
+                _ => self.convert::<u64>(x),
+            })?)),
+            DataType::Decimal128(precision, scale) => {
+                let values = self.collect(rows, |x| match x {
+                    MySqlValue::Decimal(x) => {
+                        let mut digits = format!("{}{}", x.integral, x.fractional);
+                        for _ in x.scale as i8..*scale {
+                            digits.push('0');
+                        }
+                        let value = digits.parse::<i128>()?;
+                        Ok(if x.negative { -value } else { value })
+                    }
+                    _ => Err(self.unexpected_value(x)),
+                })?;
+                Arc::new(
+                    Decimal128Array::from(values).with_precision_and_scale(*precision, *scale)?,
+                )
+            }
+            DataType::Utf8 => {
+                let (column_type, _) = self.table.actual_column_type(self.index);
+                Arc::new(StringArray::from(self.collect(rows, |x| match x {
+                    MySqlValue::String(x) => Ok(x.clone()),
+                    MySqlValue::Decimal(x) => Ok(x.to_string()),
+                    MySqlValue::Enum(x) => Ok(self.table.enum_string(self.index, *x)),
+                    MySqlValue::Set(x) => Ok(self.table.set_string(self.index, *x)),
+                    MySqlValue::Blob(x) if column_type == ColumnType::Json as u8 => decode_json(x),
+                    MySqlValue::Blob(x) => Ok(String::from_utf8_lossy(x).to_string()),
+                    _ => Err(self.unexpected_value(x)),
+                })?))
+            }
+            DataType::Binary => {
+                let values = self.collect(rows, |x| self.convert::<Vec<u8>>(x))?;
+                Arc::new(BinaryArray::from_iter(values))
+            }
+            DataType::Date32 => Arc::new(Date32Array::from(self.collect_optional(rows, |x| {
+                match x {
+                    MySqlValue::Date(x) if x.check_complete().is_ok() => {
+                        Ok(Some(x.to_epoch_days() as i32))
+                    }
+                    // Zero dates are written as nulls
+                    MySqlValue::Date(_) => Ok(None),
+                    _ => Err(self.unexpected_value(x)),
+                }
+            })?)),
+            DataType::Timestamp(TimeUnit::Microsecond, None) => {
+                let values = self.collect_optional(rows, |x| {
+                    let value = self.convert::<DateTime>(x)?;
+                    match value.date().check_complete() {
+                        Ok(_) => Ok(Some(value.to_epoch_micros())),
+                        Err(_) => Ok(None),
+                    }
+                })?;
+                Arc::new(TimestampMicrosecondArray::from(values))
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, Some(timezone)) => {
+                let values = self.collect(rows, |x| match x {
+                    MySqlValue::Timestamp(x) => Ok(*x as i64 * 1000),
+                    _ => Err(self.unexpected_value(x)),
+                })?;
+                Arc::new(TimestampMicrosecondArray::from(values).with_timezone(timezone.clone()))
+            }
+            DataType::Null => Arc::new(NullArray::new(rows.len())),
+            _ => {
+                return Err(Error::String(format!(
+                    "Column {}: unsupported type {}",
+                    self.name, data_type
+                )))
+            }
+        };
+        Ok(array)
+    }
+
+    fn collect<T>(
+        &self,
+        rows: &[ChangeRow],
+        convert: impl Fn(&MySqlValue) -> Result<T, Error>,
+    ) -> Result<Vec<Option<T>>, Error> {
+        self.collect_optional(rows, |x| convert(x).map(Some))
+    }
+
+    fn collect_optional<T>(
+        &self,
+        rows: &[ChangeRow],
+        convert: impl Fn(&MySqlValue) -> Result<Option<T>, Error>,
+    ) -> Result<Vec<Option<T>>, Error> {
+        rows.iter()
+            .map(|row| match row.cells.get(self.index) {
+                Some(Some(value)) => convert(value),
+                _ => Ok(None),
+            })
+            .collect()
+    }
+
+    fn convert<T: FromMySqlValue>(&self, value: &MySqlValue) -> Result<T, Error> {
+        T::from_mysql_value(Some(value), self.unsigned)
+            .map_err(|e| Error::String(format!("Column {}: {}", self.name, e)))
+    }
+
+    fn unexpected_value(&self, value: &MySqlValue) -> Error {
+        Error::String(format!(
+            "Column {}: unexpected value {:?}",
+            self.name, value
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchAccumulator;
+    use crate::arrow::arrow_options::ArrowOptions;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::rotate_event::RotateEvent;
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::events::row_events::row_data::{RowData, UpdateRowData};
+    use crate::events::row_events::update_rows_event::UpdateRowsEvent;
+    use crate::events::row_events::write_rows_event::WriteRowsEvent;
+    use crate::test_fixtures::{create_header, create_row, create_table};
+    use crate::test_fixtures::{ID, NAME, PRICE, TABLE_ID};
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Decimal128Type, Int32Type, UInt64Type};
+    use arrow_schema::DataType;
+
+    #[test]
+    fn accumulate_rows_into_batches() {
+        let mut options = ArrowOptions::default();
+        options.batch_size = 3;
+        let mut accumulator = BatchAccumulator::new(options);
+
+        let rotate = BinlogEvent::RotateEvent(RotateEvent {
+            binlog_filename: "mysql-bin.000001".to_string(),
+            binlog_position: 4,
+        });
+        let table = BinlogEvent::TableMapEvent(create_table(&[ID, NAME, PRICE]));
+        let insert = BinlogEvent::WriteRowsEvent(WriteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 3,
+            columns_present: vec![true; 3],
+            rows: vec![create_row(1, "a"), create_row(2, "b")],
+        });
+        let update = BinlogEvent::UpdateRowsEvent(UpdateRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 3,
+            columns_before_update: vec![true; 3],
+            columns_after_update: vec![true; 3],
+            rows: vec![UpdateRowData::new(create_row(1, "a"), create_row(1, "c"))],
+        });
+
+        for event in [rotate, table, insert] {
+            assert!(accumulator
+                .add(&create_header(200), &event)
+                .unwrap()
+                .is_empty());
+        }
+        let batches = accumulator.add(&create_header(400), &update).unwrap();
+        assert_eq!(1, batches.len());
+
+        let batch = &batches[0].batch;
+        assert_eq!("orders", batches[0].table);
+        assert_eq!(4, batch.num_rows());
+        assert_eq!(
+            &DataType::Decimal128(10, 2),
+            batch.schema().field(6).data_type()
+        );
+
+        let change_types = batch.column(0).as_string::<i32>();
+        assert_eq!("insert", change_types.value(0));
+        assert_eq!("update_before", change_types.value(2));
+        assert_eq!("update_after", change_types.value(3));
+        assert_eq!(
+            "mysql-bin.000001",
+            batch.column(1).as_string::<i32>().value(0)
+        );
+        assert_eq!(400, batch.column(2).as_primitive::<UInt64Type>().value(3));
+        assert_eq!(2, batch.column(4).as_primitive::<Int32Type>().value(1));
+        assert_eq!("c", batch.column(5).as_string::<i32>().value(3));
+        assert_eq!(
+            -12345,
+            batch.column(6).as_primitive::<Decimal128Type>().value(0)
+        );
+        assert!(accumulator.flush().unwrap().is_empty());
+    }
+
+    #[test]
+    fn flush_batch_when_schema_changes() {
+        let mut accumulator = BatchAccumulator::new(ArrowOptions::default());
+        let insert = BinlogEvent::WriteRowsEvent(WriteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 2,
+            columns_present: vec![true; 2],
+            rows: vec![RowData::new(vec![
+                Some(MySqlValue::Int(1)),
+                Some(MySqlValue::String("a".to_string())),
+            ])],
+        });
+
+        let table = BinlogEvent::TableMapEvent(create_table(&[ID, NAME]));
+        accumulator.add(&create_header(200), &table).unwrap();
+        accumulator.add(&create_header(300), &insert).unwrap();
+
+        // ALTER TABLE changes INT column to BIGINT
+        let table = BinlogEvent::TableMapEvent(create_table(&[("id", 8, 0), NAME]));
+        accumulator.add(&create_header(400), &table).unwrap();
+        let batches = accumulator.add(&create_header(500), &insert).unwrap();
+
+        assert_eq!(1, batches.len());
+        assert_eq!(
+            &DataType::Int32,
+            batches[0].batch.schema().field(4).data_type()
+        );
+        let batches = accumulator.flush().unwrap();
+        assert_eq!(1, batches.len());
+        assert_eq!(
+            &DataType::Int64,
+            batches[0].batch.schema().field(4).data_type()
+        );
+    }
+}
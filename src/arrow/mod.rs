@@ -0,0 +1,6 @@
+pub mod arrow_options;
+pub mod batch_accumulator;
+pub mod table_schema;
+
+#[cfg(feature = "parquet")]
+pub mod parquet_writer;
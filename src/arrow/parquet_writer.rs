@@ -0,0 +1,224 @@
+use crate::arrow::arrow_options::ArrowOptions;
+use crate::arrow::batch_accumulator::{BatchAccumulator, TableBatch};
+use crate::binlog_reader::BinlogReader;
+use crate::errors::Error;
+use arrow_schema::SchemaRef;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::Compression;
+use parquet::file::properties::WriterProperties;
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Settings of Parquet writer.
+#[derive(Clone, Debug)]
+pub struct ParquetOptions {
+    /// Directory of the files. Files are written to database/table/part-N.parquet.
+    pub directory: PathBuf,
+
+    /// File is closed when its size exceeds the value in bytes. Defaults to 128 MB.
+    pub max_file_size: usize,
+
+    /// File is closed when it's open longer than the duration. Defaults to 1 hour.
+    pub max_file_age: Duration,
+
+    /// Compression of the column chunks. Defaults to Snappy.
+    pub compression: Compression,
+}
+
+impl ParquetOptions {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            max_file_size: 128 * 1024 * 1024,
+            max_file_age: Duration::from_secs(3600),
+            compression: Compression::SNAPPY,
+        }
+    }
+}
+
+struct OpenFile {
+    writer: ArrowWriter<File>,
+    schema: SchemaRef,
+    opened: Instant,
+}
+
+/// Writes record batches to per-table Parquet files.
+/// A new file is started when the current one exceeds size or age limits or when the table schema changes.
+pub struct ParquetWriter {
+    pub options: ParquetOptions,
+    files: HashMap<(String, String), OpenFile>,
+    written_files: Vec<PathBuf>,
+}
+
+impl ParquetWriter {
+    pub fn new(options: ParquetOptions) -> Self {
+        Self {
+            options,
+            files: HashMap::new(),
+            written_files: Vec::new(),
+        }
+    }
+
+    /// Writes the batch to the current file of the table.
+    pub fn write(&mut self, table_batch: &TableBatch) -> Result<(), Error> {
+        let key = (table_batch.database.clone(), table_batch.table.clone());
+        let schema = table_batch.batch.schema();
+
+        if let Some(file) = self.files.get(&key) {
+            let size = file.writer.bytes_written() + file.writer.in_progress_size();
+            if file.schema != schema
+                || size >= self.options.max_file_size
+                || file.opened.elapsed() >= self.options.max_file_age
+            {
+                self.close_file(&key)?;
+            }
+        }
+
+        if !self.files.contains_key(&key) {
+            let file = self.open_file(&table_batch.database, &table_batch.table, schema)?;
+            self.files.insert(key.clone(), file);
+        }
+
+        let file = self.files.get_mut(&key).unwrap();
+        file.writer.write(&table_batch.batch)?;
+        Ok(())
+    }
+
+    /// Closes files that are open longer than max_file_age.
+    /// Call it periodically when some tables are rarely changed.
+    pub fn close_expired(&mut self) -> Result<(), Error> {
+        let expired: Vec<(String, String)> = self
+            .files
+            .iter()
+            .filter(|(_, file)| file.opened.elapsed() >= self.options.max_file_age)
+            .map(|(key, _)| key.clone())
+            .collect();
+
+        for key in expired {
+            self.close_file(&key)?;
+        }
+        Ok(())
+    }
+
+    /// Closes all files. Returns paths of the files written by the writer.
+    pub fn close(mut self) -> Result<Vec<PathBuf>, Error> {
+        let keys: Vec<(String, String)> = self.files.keys().cloned().collect();
+        for key in keys {
+            self.close_file(&key)?;
+        }
+        Ok(self.written_files)
+    }
+
+    fn open_file(
+        &mut self,
+        database: &str,
+        table: &str,
+        schema: SchemaRef,
+    ) -> Result<OpenFile, Error> {
+        let directory = self.options.directory.join(database).join(table);
+        fs::create_dir_all(&directory)?;
+        let path = next_file_path(&directory);
+
+        let properties = WriterProperties::builder()
+            .set_compression(self.options.compression)
+            .build();
+        let writer = ArrowWriter::try_new(File::create(&path)?, schema.clone(), Some(properties))?;
+
+        self.written_files.push(path);
+        Ok(OpenFile {
+            writer,
+            schema,
+            opened: Instant::now(),
+        })
+    }
+
+    fn close_file(&mut self, key: &(String, String)) -> Result<(), Error> {
+        if let Some(file) = self.files.remove(key) {
+            file.writer.close()?;
+        }
+        Ok(())
+    }
+}
+
+/// Files of previous runs are kept, so the sequence continues after existing files.
+fn next_file_path(directory: &Path) -> PathBuf {
+    let mut sequence = 0;
+    loop {
+        let path = directory.join(format!("part-{:05}.parquet", sequence));
+        if !path.exists() {
+            return path;
+        }
+        sequence += 1;
+    }
+}
+
+/// Exports row events of the binlog file to per-table Parquet files.
+/// The binlog file name is written to the binlog file column of the rows.
+/// Returns paths of the written files.
+pub fn export_binlog(
+    reader: BinlogReader,
+    binlog_file: &str,
+    arrow_options: ArrowOptions,
+    parquet_options: ParquetOptions,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut accumulator = BatchAccumulator::new(arrow_options);
+    accumulator.set_binlog_file(binlog_file);
+    let mut writer = ParquetWriter::new(parquet_options);
+
+    for result in reader.read_events() {
+        let (header, event) = result?;
+        for batch in accumulator.add(&header, &event)? {
+            writer.write(&batch)?;
+        }
+    }
+    for batch in accumulator.flush()? {
+        writer.write(&batch)?;
+    }
+    writer.close()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ParquetOptions, ParquetWriter};
+    use crate::arrow::batch_accumulator::TableBatch;
+    use arrow_array::{Int32Array, RecordBatch};
+    use arrow_schema::{DataType, Field, Schema};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+    use std::fs::{self, File};
+    use std::sync::Arc;
+
+    #[test]
+    fn roll_files_by_size() {
+        let directory =
+            std::env::temp_dir().join(format!("mysql_cdc_parquet_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&directory);
+
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, true)]));
+        let batch = TableBatch {
+            database: "shop".to_string(),
+            table: "orders".to_string(),
+            batch: RecordBatch::try_new(schema, vec![Arc::new(Int32Array::from(vec![1, 2, 3]))])
+                .unwrap(),
+        };
+
+        let mut options = ParquetOptions::new(&directory);
+        options.max_file_size = 0;
+        let mut writer = ParquetWriter::new(options);
+        writer.write(&batch).unwrap();
+        writer.write(&batch).unwrap();
+        let files = writer.close().unwrap();
+
+        assert_eq!(2, files.len());
+        assert_eq!(directory.join("shop/orders/part-00001.parquet"), files[1]);
+        let reader = ParquetRecordBatchReaderBuilder::try_new(File::open(&files[1]).unwrap())
+            .unwrap()
+            .build()
+            .unwrap();
+        let rows: usize = reader.map(|x| x.unwrap().num_rows()).sum();
+        assert_eq!(3, rows);
+
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
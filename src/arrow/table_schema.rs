@@ -0,0 +1,134 @@
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::table_map_event::TableMapEvent;
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use std::collections::HashMap;
+
+/// Type of the change: insert, update_before, update_after or delete.
+pub const CHANGE_TYPE_COLUMN: &str = "_change_type";
+
+/// Binlog file name of the row event.
+pub const BINLOG_FILE_COLUMN: &str = "_binlog_file";
+
+/// Binlog position of the row event.
+pub const BINLOG_POSITION_COLUMN: &str = "_binlog_position";
+
+/// Creation time of the row event.
+pub const TIMESTAMP_COLUMN: &str = "_timestamp";
+
+/// Number of metadata columns preceding table columns.
+pub const METADATA_COLUMNS: usize = 4;
+
+/// Type of the change written to the change type column.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ChangeType {
+    Insert,
+    UpdateBefore,
+    UpdateAfter,
+    Delete,
+}
+
+impl ChangeType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeType::Insert => "insert",
+            ChangeType::UpdateBefore => "update_before",
+            ChangeType::UpdateAfter => "update_after",
+            ChangeType::Delete => "delete",
+        }
+    }
+}
+
+/// Returns Arrow schema of the table.
+/// Metadata columns describing the change are followed by table columns.
+/// Database and table names are stored in schema metadata.
+pub fn table_schema(table: &TableMapEvent) -> Result<Schema, Error> {
+    let mut fields = vec![
+        Field::new(CHANGE_TYPE_COLUMN, DataType::Utf8, false),
+        Field::new(BINLOG_FILE_COLUMN, DataType::Utf8, false),
+        Field::new(BINLOG_POSITION_COLUMN, DataType::UInt64, false),
+        Field::new(
+            TIMESTAMP_COLUMN,
+            DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+            false,
+        ),
+    ];
+
+    let names = table
+        .table_metadata
+        .as_ref()
+        .and_then(|x| x.column_names.as_ref());
+    for index in 0..table.column_types.len() {
+        let name = match names.and_then(|x| x.get(index)) {
+            Some(name) => name.clone(),
+            None => format!("column_{}", index),
+        };
+        // Columns are nullable because zero dates and columns missing
+        // with binlog_row_image=MINIMAL are written as nulls.
+        fields.push(Field::new(name, column_data_type(table, index)?, true));
+    }
+
+    let metadata = HashMap::from([
+        ("database".to_string(), table.database_name.clone()),
+        ("table".to_string(), table.table_name.clone()),
+    ]);
+    Ok(Schema::new_with_metadata(fields, metadata))
+}
+
+/// Returns Arrow type of the column from column type, metadata and signedness.
+pub fn column_data_type(table: &TableMapEvent, index: usize) -> Result<DataType, Error> {
+    let (column_type, metadata) = table.actual_column_type(index);
+    let column_type = ColumnType::from_code(column_type)?;
+    let unsigned = table.is_unsigned(index).unwrap_or(false);
+
+    let result = match column_type {
+        ColumnType::Tiny if unsigned => DataType::UInt8,
+        ColumnType::Tiny => DataType::Int8,
+        ColumnType::Short if unsigned => DataType::UInt16,
+        ColumnType::Short => DataType::Int16,
+        ColumnType::Int24 | ColumnType::Long if unsigned => DataType::UInt32,
+        ColumnType::Int24 | ColumnType::Long => DataType::Int32,
+        ColumnType::LongLong if unsigned => DataType::UInt64,
+        ColumnType::LongLong => DataType::Int64,
+        ColumnType::Float => DataType::Float32,
+        ColumnType::Double => DataType::Float64,
+        ColumnType::Decimal | ColumnType::NewDecimal => {
+            let precision = (metadata & 0xFF) as u8;
+            let scale = (metadata >> 8) as i8;
+            match precision <= 38 {
+                true => DataType::Decimal128(precision, scale),
+                // Written as string to keep the exact value
+                false => DataType::Utf8,
+            }
+        }
+        ColumnType::String
+        | ColumnType::VarChar
+        | ColumnType::VarString
+        | ColumnType::TinyBlob
+        | ColumnType::MediumBlob
+        | ColumnType::LongBlob
+        | ColumnType::Blob => match table.is_binary_column(index) {
+            true => DataType::Binary,
+            false => DataType::Utf8,
+        },
+        ColumnType::Bit => match (metadata >> 8) * 8 + (metadata & 0xFF) {
+            1 => DataType::Boolean,
+            _ => DataType::UInt64,
+        },
+        ColumnType::Enum | ColumnType::Set => DataType::Utf8,
+        ColumnType::Year => DataType::UInt16,
+        ColumnType::Date | ColumnType::NewDate => DataType::Date32,
+        // TIME can be negative or exceed 24 hours, so it's written as microseconds
+        ColumnType::Time | ColumnType::Time2 => DataType::Int64,
+        ColumnType::DateTime | ColumnType::DateTime2 => {
+            DataType::Timestamp(TimeUnit::Microsecond, None)
+        }
+        ColumnType::TimeStamp | ColumnType::TimeStamp2 => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        }
+        ColumnType::Json => DataType::Utf8,
+        ColumnType::Geometry => DataType::Binary,
+        ColumnType::Null => DataType::Null,
+    };
+    Ok(result)
+}
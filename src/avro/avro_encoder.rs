@@ -0,0 +1,378 @@
+use crate::avro::avro_options::AvroOptions;
+use crate::avro::avro_schema::{AvroField, AvroType, TableSchema};
+use crate::avro::schema_tracker::SchemaTracker;
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::event_header::EventHeader;
+use crate::events::row_events::from_value::FromMySqlValue;
+use crate::events::row_events::json_binary::decode_json;
+use crate::events::row_events::mysql_value::{DateTime, Decimal, MySqlValue};
+use crate::events::row_events::row_data::RowData;
+use crate::events::table_map_event::TableMapEvent;
+
+/// Changed row encoded as Avro datum of the envelope schema.
+#[derive(Clone, Debug)]
+pub struct AvroRecord {
+    /// Gets database name of the table.
+    pub database: String,
+
+    /// Gets name of the table.
+    pub table: String,
+
+    /// Gets version of the table schema in SchemaTracker.
+    pub schema_version: u32,
+
+    /// Gets envelope in Avro binary encoding without container file header.
+    pub data: Vec<u8>,
+}
+
+/// Encodes row events to Avro records with schemas generated from table maps.
+/// Use TableSchema::envelope_schema of the record version to decode the data.
+pub struct AvroEncoder {
+    pub schemas: SchemaTracker,
+}
+
+impl AvroEncoder {
+    pub fn new(options: AvroOptions) -> Self {
+        Self {
+            schemas: SchemaTracker::new(options),
+        }
+    }
+
+    /// Encodes rows of the row event. Returns empty list for other events.
+    /// The table must be the TableMapEvent preceding the row event.
+    pub fn encode(
+        &mut self,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        table: &TableMapEvent,
+    ) -> Result<Vec<AvroRecord>, Error> {
+        let table_id = match event {
+            BinlogEvent::WriteRowsEvent(x) => x.table_id,
+            BinlogEvent::UpdateRowsEvent(x) => x.table_id,
+            BinlogEvent::DeleteRowsEvent(x) => x.table_id,
+            _ => return Ok(Vec::new()),
+        };
+        if table_id != table.table_id {
+            return Err(Error::String(format!(
+                "Row event of table id {} doesn't match TableMapEvent of table id {}",
+                table_id, table.table_id
+            )));
+        }
+
+        let version = self.schemas.track(table)?.version;
+        let schema = self
+            .schemas
+            .get(&table.database_name, &table.table_name, version)
+            .ok_or_else(|| Error::String("Table schema is not tracked".to_string()))?;
+
+        let ts_ms = header.timestamp as i64 * 1000;
+        let changes: Vec<(Option<&RowData>, Option<&RowData>, &str)> = match event {
+            BinlogEvent::WriteRowsEvent(x) => x.rows.iter().map(|r| (None, Some(r), "c")).collect(),
+            BinlogEvent::UpdateRowsEvent(x) => x
+                .rows
+                .iter()
+                .map(|r| (Some(&r.before_update), Some(&r.after_update), "u"))
+                .collect(),
+            BinlogEvent::DeleteRowsEvent(x) => {
+                x.rows.iter().map(|r| (Some(r), None, "d")).collect()
+            }
+            _ => Vec::new(),
+        };
+
+        let mut result = Vec::new();
+        for (before, after, op) in changes {
+            let mut data = Vec::new();
+            write_optional_row(before, schema, table, &mut data)?;
+            write_optional_row(after, schema, table, &mut data)?;
+            write_bytes(op.as_bytes(), &mut data);
+            write_long(ts_ms, &mut data);
+
+            result.push(AvroRecord {
+                database: table.database_name.clone(),
+                table: table.table_name.clone(),
+                schema_version: version,
+                data,
+            });
+        }
+        Ok(result)
+    }
+}
+
+fn write_optional_row(
+    row: Option<&RowData>,
+    schema: &TableSchema,
+    table: &TableMapEvent,
+    out: &mut Vec<u8>,
+) -> Result<(), Error> {
+    match row {
+        Some(row) => {
+            write_long(1, out);
+            for field in &schema.fields {
+                let cell = row.cells.get(field.column_index).and_then(|x| x.as_ref());
+                write_field(field, cell, table, out)?;
+            }
+        }
+        None => write_long(0, out),
+    }
+    Ok(())
+}
+
+fn write_field(
+    field: &AvroField,
+    cell: Option<&MySqlValue>,
+    table: &TableMapEvent,
+    out: &mut Vec<u8>,
+) -> Result<(), Error> {
+    let mut value = Vec::new();
+    let present = match cell {
+        Some(cell) => write_value(field, cell, table, &mut value)?,
+        None => false,
+    };
+
+    match (field.nullable, present) {
+        (true, true) => write_long(1, out),
+        (true, false) => write_long(0, out),
+        (false, true) => {}
+        (false, false) => {
+            return Err(Error::String(format!(
+                "Column {} is NOT NULL but has no value. Make sure binlog_row_image=FULL",
+                field.name
+            )))
+        }
+    }
+    out.extend_from_slice(&value);
+    Ok(())
+}
+
+/// Writes the value of the field type. Returns false for zero dates written as null.
+fn write_value(
+    field: &AvroField,
+    value: &MySqlValue,
+    table: &TableMapEvent,
+    out: &mut Vec<u8>,
+) -> Result<bool, Error> {
+    let unsigned = table.is_unsigned(field.column_index).unwrap_or(false);
+    let convert_error = |e: String| Error::String(format!("Column {}: {}", field.name, e));
+    let unexpected_value = || {
+        Error::String(format!(
+            "Column {}: unexpected value {:?}",
+            field.name, value
+        ))
+    };
+
+    match &field.avro_type {
+        AvroType::Boolean => {
+            let value = bool::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            out.push(value as u8);
+        }
+        AvroType::Int => {
+            let value = i32::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            write_long(value as i64, out);
+        }
+        AvroType::Long => {
+            let value = match value {
+                MySqlValue::Time(x) => x.to_micros(),
+                MySqlValue::Bit(bits) => bits.iter().fold(0, |x, bit| (x << 1) | *bit as i64),
+                _ => i64::from_mysql_value(Some(value), unsigned).map_err(convert_error)?,
+            };
+            write_long(value, out);
+        }
+        AvroType::Float => {
+            let value = f32::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        AvroType::Double => {
+            let value = f64::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        AvroType::Bytes => {
+            let value =
+                Vec::<u8>::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            write_bytes(&value, out);
+        }
+        AvroType::String => {
+            let value = match value {
+                MySqlValue::String(x) => x.clone(),
+                MySqlValue::Enum(x) => table.enum_string(field.column_index, *x),
+                MySqlValue::Set(x) => table.set_string(field.column_index, *x),
+                MySqlValue::Blob(x) => {
+                    let (column_type, _) = table.actual_column_type(field.column_index);
+                    match column_type == ColumnType::Json as u8 {
+                        true => decode_json(x)?,
+                        false => String::from_utf8_lossy(x).to_string(),
+                    }
+                }
+                _ => return Err(unexpected_value()),
+            };
+            write_bytes(value.as_bytes(), out);
+        }
+        AvroType::Decimal { scale, .. } => {
+            let value = match value {
+                MySqlValue::BigInt(x) => Decimal::parse(&x.to_string())?,
+                _ => Decimal::from_mysql_value(Some(value), unsigned).map_err(convert_error)?,
+            };
+            write_bytes(&rescale(value, *scale).to_unscaled_bytes(), out);
+        }
+        AvroType::Date => match value {
+            MySqlValue::Date(x) if x.check_complete().is_ok() => write_long(x.to_epoch_days(), out),
+            MySqlValue::Date(_) => return Ok(write_zero_date(field, out)),
+            _ => return Err(unexpected_value()),
+        },
+        AvroType::TimestampMicros => match value {
+            MySqlValue::Timestamp(x) => write_long(*x as i64 * 1000, out),
+            _ => return Err(unexpected_value()),
+        },
+        AvroType::LocalTimestampMicros => {
+            let value = DateTime::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            match value.date().check_complete() {
+                Ok(_) => write_long(value.to_epoch_micros(), out),
+                Err(_) => return Ok(write_zero_date(field, out)),
+            }
+        }
+        AvroType::Uuid => {
+            let value =
+                Vec::<u8>::from_mysql_value(Some(value), unsigned).map_err(convert_error)?;
+            if value.len() != 16 {
+                return Err(unexpected_value());
+            }
+            write_bytes(format_uuid(&value).as_bytes(), out);
+        }
+        AvroType::Enum { symbols, .. } => match value {
+            // Zero index is used by the server for invalid empty value
+            MySqlValue::Enum(x) if *x >= 1 && *x as usize <= symbols.len() => {
+                write_long(*x as i64 - 1, out)
+            }
+            _ => return Err(unexpected_value()),
+        },
+    }
+    Ok(true)
+}
+
+/// Zero dates are written as null or epoch for NOT NULL columns.
+fn write_zero_date(field: &AvroField, out: &mut Vec<u8>) -> bool {
+    if !field.nullable {
+        write_long(0, out);
+    }
+    !field.nullable
+}
+
+/// Decimal bytes don't contain the scale, so values must have scale of the schema.
+fn rescale(mut value: Decimal, scale: u8) -> Decimal {
+    value.fractional.truncate(scale as usize);
+    while value.fractional.len() < scale as usize {
+        value.fractional.push('0');
+    }
+    value.scale = scale;
+    value
+}
+
+fn format_uuid(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|x| format!("{:02x}", x)).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Writes int and long values as zig-zag variable-length numbers.
+fn write_long(value: i64, out: &mut Vec<u8>) {
+    let mut value = ((value << 1) ^ (value >> 63)) as u64;
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Writes bytes and strings prefixed by length.
+fn write_bytes(value: &[u8], out: &mut Vec<u8>) {
+    write_long(value.len() as i64, out);
+    out.extend_from_slice(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_long, AvroEncoder};
+    use crate::avro::avro_options::AvroOptions;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::row_events::mysql_value::{Date, MySqlValue};
+    use crate::events::row_events::write_rows_event::WriteRowsEvent;
+    use crate::test_fixtures::{create_header, create_row, create_table};
+    use crate::test_fixtures::{ID, NAME, PRICE, TABLE_ID};
+
+    #[test]
+    fn write_zig_zag_numbers() {
+        let encode = |x| {
+            let mut out = Vec::new();
+            write_long(x, &mut out);
+            out
+        };
+        assert_eq!(vec![0x00], encode(0));
+        assert_eq!(vec![0x01], encode(-1));
+        assert_eq!(vec![0x02], encode(1));
+        assert_eq!(vec![0x7F], encode(-64));
+        assert_eq!(vec![0x80, 0x01], encode(64));
+        assert_eq!(vec![0xAC, 0x02], encode(150));
+    }
+
+    #[test]
+    fn encode_insert_and_track_schema_changes() {
+        let table = create_table(&[ID, NAME, PRICE, ("day", 10, 0)]);
+        let mut row = create_row(1, "ab");
+        row.cells.push(Some(MySqlValue::Date(Date {
+            year: 1970,
+            month: 1,
+            day: 2,
+        })));
+        let event = BinlogEvent::WriteRowsEvent(WriteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 4,
+            columns_present: vec![true; 4],
+            rows: vec![row],
+        });
+
+        let mut encoder = AvroEncoder::new(AvroOptions::new("cdc"));
+        let records = encoder.encode(&create_header(4), &event, &table).unwrap();
+
+        assert_eq!(1, records.len());
+        assert_eq!(1, records[0].schema_version);
+        #[rustfmt::skip]
+        let expected = vec![
+            0x00, // before is null
+            0x02, // after union index
+            0x02, // id 1
+            0x02, 0x04, b'a', b'b', // name
+            0x02, 0x04, 0xCF, 0xC7, // price
+            0x02, 0x02, // day 1
+            0x02, b'c', // op
+            0xD0, 0x0F, // ts_ms 1000
+        ];
+        assert_eq!(expected, records[0].data);
+
+        // Same table map doesn't create a new version
+        assert!(!encoder.schemas.track(&table).unwrap().changed);
+
+        // Dropped column changes the schema
+        let table = create_table(&[ID, NAME, PRICE]);
+        let version = encoder.schemas.track(&table).unwrap();
+        assert!(version.changed);
+        assert_eq!(2, version.version);
+        assert_eq!(2, encoder.schemas.versions("shop", "orders").len());
+        assert_eq!(
+            3,
+            encoder
+                .schemas
+                .get("shop", "orders", 2)
+                .unwrap()
+                .fields
+                .len()
+        );
+    }
+}
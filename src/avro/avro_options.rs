@@ -0,0 +1,18 @@
+/// Settings of Avro encoder.
+#[derive(Clone, Debug)]
+pub struct AvroOptions {
+    /// Namespace of generated schemas. Database name is appended to the namespace.
+    pub namespace: String,
+
+    /// Defines whether BINARY(16) columns are written as uuid strings. Defaults to false.
+    pub binary16_as_uuid: bool,
+}
+
+impl AvroOptions {
+    pub fn new(namespace: &str) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            binary16_as_uuid: false,
+        }
+    }
+}
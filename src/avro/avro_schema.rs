@@ -0,0 +1,361 @@
+use crate::avro::avro_options::AvroOptions;
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::table_map_event::TableMapEvent;
+use serde_json::{json, Value};
+
+/// Avro type of a column.
+/// <a href="https://avro.apache.org/docs/1.11.1/specification/">See more</a>
+#[derive(Clone, PartialEq, Debug)]
+pub enum AvroType {
+    Boolean,
+    Int,
+    Long,
+    Float,
+    Double,
+    Bytes,
+    String,
+
+    /// Unscaled value as big-endian two's complement bytes.
+    Decimal {
+        precision: u8,
+        scale: u8,
+    },
+
+    /// Number of days since 1970-01-01.
+    Date,
+
+    /// Microseconds since 1970-01-01 00:00:00 UTC. Used for TIMESTAMP columns.
+    TimestampMicros,
+
+    /// Microseconds since 1970-01-01 00:00:00 in unspecified time zone. Used for DATETIME columns.
+    LocalTimestampMicros,
+
+    /// UUID string. Used for BINARY(16) columns if enabled in AvroOptions.
+    Uuid,
+
+    /// ENUM column with values that are valid Avro names.
+    Enum {
+        name: String,
+        symbols: Vec<String>,
+    },
+}
+
+impl AvroType {
+    /// Returns Avro schema of the type.
+    pub fn schema(&self) -> Value {
+        match self {
+            AvroType::Boolean => json!("boolean"),
+            AvroType::Int => json!("int"),
+            AvroType::Long => json!("long"),
+            AvroType::Float => json!("float"),
+            AvroType::Double => json!("double"),
+            AvroType::Bytes => json!("bytes"),
+            AvroType::String => json!("string"),
+            AvroType::Decimal { precision, scale } => json!({
+                "type": "bytes",
+                "logicalType": "decimal",
+                "precision": precision,
+                "scale": scale
+            }),
+            AvroType::Date => json!({ "type": "int", "logicalType": "date" }),
+            AvroType::TimestampMicros => {
+                json!({ "type": "long", "logicalType": "timestamp-micros" })
+            }
+            AvroType::LocalTimestampMicros => {
+                json!({ "type": "long", "logicalType": "local-timestamp-micros" })
+            }
+            AvroType::Uuid => json!({ "type": "string", "logicalType": "uuid" }),
+            AvroType::Enum { name, symbols } => json!({
+                "type": "enum",
+                "name": name,
+                "symbols": symbols
+            }),
+        }
+    }
+}
+
+/// Field of the row record.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AvroField {
+    /// Gets column name converted to a valid Avro name.
+    pub name: String,
+
+    /// Gets index of the column in the table.
+    pub column_index: usize,
+
+    /// Gets Avro type of the column.
+    pub avro_type: AvroType,
+
+    /// Gets whether the column is nullable. Nullable fields are unions with null.
+    pub nullable: bool,
+}
+
+/// Avro schema of table rows derived from TableMapEvent.
+/// Column names, ENUM values and charsets are sent by the server with binlog_row_metadata=FULL.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableSchema {
+    /// Gets namespace of the row record.
+    pub namespace: String,
+
+    /// Gets name of the row record.
+    pub name: String,
+
+    /// Gets fields of the row record in column order.
+    pub fields: Vec<AvroField>,
+}
+
+impl TableSchema {
+    /// Creates schema from column types, metadata and nullability of the table.
+    pub fn from_table_map(table: &TableMapEvent, options: &AvroOptions) -> Result<Self, Error> {
+        let names = table
+            .table_metadata
+            .as_ref()
+            .and_then(|x| x.column_names.as_ref());
+
+        let mut fields: Vec<AvroField> = Vec::new();
+        for index in 0..table.column_types.len() {
+            let name = match names.and_then(|x| x.get(index)) {
+                Some(name) => to_avro_name(name),
+                None => format!("column_{}", index),
+            };
+            // Column names are unique in MySQL but may clash after replacing characters
+            let name = match fields.iter().any(|x| x.name == name) {
+                true => format!("{}_{}", name, index),
+                false => name,
+            };
+
+            fields.push(AvroField {
+                avro_type: column_avro_type(table, index, &name, options)?,
+                nullable: table.null_bitmap.get(index).copied().unwrap_or(true),
+                name,
+                column_index: index,
+            });
+        }
+
+        let database = to_avro_name(&table.database_name);
+        Ok(Self {
+            namespace: match options.namespace.is_empty() {
+                true => database,
+                false => format!("{}.{}", options.namespace, database),
+            },
+            name: to_avro_name(&table.table_name),
+            fields,
+        })
+    }
+
+    /// Returns full name of the row record.
+    pub fn full_name(&self) -> String {
+        format!("{}.{}", self.namespace, self.name)
+    }
+
+    /// Returns Avro schema of the row record.
+    pub fn row_schema(&self) -> Value {
+        let fields: Vec<Value> = self
+            .fields
+            .iter()
+            .map(|field| match field.nullable {
+                true => json!({
+                    "name": field.name,
+                    "type": ["null", field.avro_type.schema()],
+                    "default": null
+                }),
+                false => json!({ "name": field.name, "type": field.avro_type.schema() }),
+            })
+            .collect();
+
+        json!({
+            "type": "record",
+            "name": self.name,
+            "namespace": self.namespace,
+            "fields": fields
+        })
+    }
+
+    /// Returns Avro schema of the change envelope written by AvroEncoder.
+    /// The envelope has before and after images of the row, operation code c, u, d
+    /// and the event timestamp in milliseconds.
+    pub fn envelope_schema(&self) -> Value {
+        json!({
+            "type": "record",
+            "name": "Envelope",
+            "namespace": self.full_name(),
+            "fields": [
+                { "name": "before", "type": ["null", self.row_schema()], "default": null },
+                { "name": "after", "type": ["null", self.full_name()], "default": null },
+                { "name": "op", "type": "string" },
+                { "name": "ts_ms", "type": "long" }
+            ]
+        })
+    }
+}
+
+fn column_avro_type(
+    table: &TableMapEvent,
+    index: usize,
+    field_name: &str,
+    options: &AvroOptions,
+) -> Result<AvroType, Error> {
+    let (column_type, metadata) = table.actual_column_type(index);
+    let column_type = ColumnType::from_code(column_type)?;
+    let unsigned = table.is_unsigned(index).unwrap_or(false);
+
+    let result = match column_type {
+        ColumnType::Tiny | ColumnType::Short | ColumnType::Int24 | ColumnType::Year => {
+            AvroType::Int
+        }
+        ColumnType::Long if unsigned => AvroType::Long,
+        ColumnType::Long => AvroType::Int,
+        // BIGINT UNSIGNED doesn't fit long
+        ColumnType::LongLong if unsigned => AvroType::Decimal {
+            precision: 20,
+            scale: 0,
+        },
+        ColumnType::LongLong => AvroType::Long,
+        ColumnType::Float => AvroType::Float,
+        ColumnType::Double => AvroType::Double,
+        ColumnType::Decimal | ColumnType::NewDecimal => AvroType::Decimal {
+            precision: (metadata & 0xFF) as u8,
+            scale: (metadata >> 8) as u8,
+        },
+        ColumnType::String
+        | ColumnType::VarChar
+        | ColumnType::VarString
+        | ColumnType::TinyBlob
+        | ColumnType::MediumBlob
+        | ColumnType::LongBlob
+        | ColumnType::Blob => match table.is_binary_column(index) {
+            true if options.binary16_as_uuid
+                && column_type == ColumnType::String
+                && metadata == 16 =>
+            {
+                AvroType::Uuid
+            }
+            true => AvroType::Bytes,
+            false => AvroType::String,
+        },
+        // BIT(1) is used as boolean, longer values are written as numbers
+        ColumnType::Bit => match (metadata >> 8) * 8 + (metadata & 0xFF) {
+            1 => AvroType::Boolean,
+            _ => AvroType::Long,
+        },
+        ColumnType::Enum => match table.enum_values(index) {
+            Some(symbols) if is_valid_enum(symbols) => AvroType::Enum {
+                name: format!("{}_enum", field_name),
+                symbols: symbols.clone(),
+            },
+            _ => AvroType::String,
+        },
+        // SET values are written as comma separated strings
+        ColumnType::Set => AvroType::String,
+        ColumnType::Date | ColumnType::NewDate => AvroType::Date,
+        // TIME can be negative or exceed 24 hours, so time-micros isn't used
+        ColumnType::Time | ColumnType::Time2 => AvroType::Long,
+        ColumnType::DateTime | ColumnType::DateTime2 => AvroType::LocalTimestampMicros,
+        ColumnType::TimeStamp | ColumnType::TimeStamp2 => AvroType::TimestampMicros,
+        ColumnType::Json => AvroType::String,
+        ColumnType::Geometry => AvroType::Bytes,
+        ColumnType::Null => AvroType::String,
+    };
+    Ok(result)
+}
+
+/// Avro enum symbols must be unique names.
+fn is_valid_enum(symbols: &[String]) -> bool {
+    let unique = symbols
+        .iter()
+        .enumerate()
+        .all(|(i, x)| !symbols[..i].contains(x));
+    unique && symbols.iter().all(|x| to_avro_name(x) == *x)
+}
+
+/// Replaces characters that are not allowed in Avro names.
+/// Names must start with [A-Za-z_] and contain only [A-Za-z0-9_].
+pub(crate) fn to_avro_name(name: &str) -> String {
+    let mut result: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if !result.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        result.insert(0, '_');
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_avro_name, AvroType, TableSchema};
+    use crate::avro::avro_options::AvroOptions;
+    use crate::metadata::default_charset::DefaultCharset;
+    use crate::test_fixtures::{create_table, PRICE};
+    use serde_json::json;
+
+    #[test]
+    fn convert_names() {
+        assert_eq!("order_id", to_avro_name("order_id"));
+        assert_eq!("order_id", to_avro_name("order-id"));
+        assert_eq!("_1st", to_avro_name("1st"));
+        assert_eq!("_", to_avro_name(""));
+    }
+
+    #[test]
+    fn create_schema_from_table_map() {
+        let mut table = create_table(&[
+            ("id", 8, 0),
+            PRICE,
+            ("uuid", 254, 0xFE10),
+            ("status", 254, 0xF701),
+            ("created", 18, 6),
+            ("updated", 7, 0),
+            ("day", 10, 0),
+        ]);
+        table.null_bitmap = vec![false, true, false, true, false, true, true];
+        let metadata = table.table_metadata.as_mut().unwrap();
+        metadata.default_charset = Some(DefaultCharset::new(63, vec![]));
+        metadata.enum_string_values = Some(vec![vec!["new".to_string(), "paid".to_string()]]);
+        metadata.simple_primary_keys = Some(vec![0]);
+
+        let mut options = AvroOptions::new("cdc");
+        options.binary16_as_uuid = true;
+        let schema = TableSchema::from_table_map(&table, &options).unwrap();
+
+        assert_eq!("cdc.shop.orders", schema.full_name());
+        let types: Vec<AvroType> = schema.fields.iter().map(|x| x.avro_type.clone()).collect();
+        assert_eq!(
+            vec![
+                AvroType::Long,
+                AvroType::Decimal {
+                    precision: 10,
+                    scale: 2
+                },
+                AvroType::Uuid,
+                AvroType::Enum {
+                    name: "status_enum".to_string(),
+                    symbols: vec!["new".to_string(), "paid".to_string()]
+                },
+                AvroType::LocalTimestampMicros,
+                AvroType::TimestampMicros,
+                AvroType::Date,
+            ],
+            types
+        );
+
+        let row_schema = schema.row_schema();
+        assert_eq!(
+            json!({ "name": "id", "type": "long" }),
+            row_schema["fields"][0]
+        );
+        assert_eq!(
+            json!({
+                "name": "price",
+                "type": ["null", { "type": "bytes", "logicalType": "decimal", "precision": 10, "scale": 2 }],
+                "default": null
+            }),
+            row_schema["fields"][1]
+        );
+
+        let envelope = schema.envelope_schema();
+        assert_eq!("cdc.shop.orders", envelope["namespace"]);
+        assert_eq!("cdc.shop.orders", envelope["fields"][1]["type"][1]);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod avro_encoder;
+pub mod avro_options;
+pub mod avro_schema;
+pub mod schema_tracker;
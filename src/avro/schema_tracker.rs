@@ -0,0 +1,68 @@
+use crate::avro::avro_options::AvroOptions;
+use crate::avro::avro_schema::TableSchema;
+use crate::errors::Error;
+use crate::events::table_map_event::TableMapEvent;
+use std::collections::HashMap;
+
+/// Version of the table schema returned by SchemaTracker.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SchemaVersion {
+    /// Gets version number of the schema starting from 1.
+    pub version: u32,
+
+    /// Gets whether the schema is seen for the first time or differs from the previous version.
+    pub changed: bool,
+}
+
+/// Keeps schema history of tables.
+/// TableMapEvent is written before every row event. A new version is created
+/// only when the table map of the same table changes shape after ALTER TABLE.
+pub struct SchemaTracker {
+    pub options: AvroOptions,
+    tables: HashMap<(String, String), Vec<TableSchema>>,
+}
+
+impl SchemaTracker {
+    pub fn new(options: AvroOptions) -> Self {
+        Self {
+            options,
+            tables: HashMap::new(),
+        }
+    }
+
+    /// Derives schema of the table map and compares it with the latest version of the table.
+    pub fn track(&mut self, table: &TableMapEvent) -> Result<SchemaVersion, Error> {
+        let schema = TableSchema::from_table_map(table, &self.options)?;
+        let key = (table.database_name.clone(), table.table_name.clone());
+        let versions = self.tables.entry(key).or_default();
+
+        let changed = versions.last() != Some(&schema);
+        if changed {
+            versions.push(schema);
+        }
+        Ok(SchemaVersion {
+            version: versions.len() as u32,
+            changed,
+        })
+    }
+
+    /// Returns schema of the table by version number.
+    pub fn get(&self, database: &str, table: &str, version: u32) -> Option<&TableSchema> {
+        let versions = self.versions(database, table);
+        versions.get((version as usize).checked_sub(1)?)
+    }
+
+    /// Returns the latest schema of the table and its version number.
+    pub fn latest(&self, database: &str, table: &str) -> Option<(u32, &TableSchema)> {
+        let versions = self.versions(database, table);
+        versions.last().map(|x| (versions.len() as u32, x))
+    }
+
+    /// Returns all schema versions of the table starting from the first one.
+    pub fn versions(&self, database: &str, table: &str) -> &[TableSchema] {
+        self.tables
+            .get(&(database.to_string(), table.to_string()))
+            .map(|x| x.as_slice())
+            .unwrap_or(&[])
+    }
+}
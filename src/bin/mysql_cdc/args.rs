@@ -0,0 +1,189 @@
+use clap::{Parser, ValueEnum};
+use mysql_cdc::errors::Error;
+use mysql_cdc::events::row_events::mysql_value::Date;
+use std::path::PathBuf;
+
+/// Reads MySQL/MariaDB binary logs from files or a live server
+/// and prints events like mysqlbinlog.
+#[derive(Parser, Debug)]
+#[command(name = "mysql_cdc", version)]
+pub struct Args {
+    /// Binlog files to read. With --host or --socket, the binlog file name to start replication from.
+    pub files: Vec<String>,
+
+    /// Reads events from the server instead of files.
+    #[arg(long, short = 'H')]
+    pub host: Option<String>,
+
+    /// Reads events from the server connecting to its Unix socket file.
+    #[arg(long, short = 'S')]
+    pub socket: Option<PathBuf>,
+
+    /// Port of the server.
+    #[arg(long, short = 'P', default_value_t = 3306)]
+    pub port: u16,
+
+    /// User name for the server.
+    #[arg(long, short = 'u', default_value = "root")]
+    pub user: String,
+
+    /// Password for the server.
+    #[arg(long, short = 'p', default_value = "")]
+    pub password: String,
+
+    /// PEM file with RSA public key of the server for sha256_password and caching_sha2_password.
+    #[arg(long)]
+    pub server_public_key_path: Option<PathBuf>,
+
+    /// Allows mysql_clear_password plugin that sends the password unencrypted.
+    #[arg(long)]
+    pub enable_cleartext_plugin: bool,
+
+    /// Compresses packets exchanged with the server.
+    #[arg(long, value_enum, default_value_t = Compression::Uncompressed)]
+    pub compression_algorithm: Compression,
+
+    /// Level of the compression algorithm.
+    #[arg(long)]
+    pub compression_level: Option<u32>,
+
+    /// Seconds to wait for TCP connection to each address of the host.
+    #[arg(long, default_value_t = 10)]
+    pub connect_timeout: u64,
+
+    /// Server id of the replica. Must be unique among replicas of the server.
+    #[arg(long, default_value_t = 65535)]
+    pub server_id: u32,
+
+    /// Waits for new events after reaching the end of the last binlog on the server.
+    #[arg(long)]
+    pub stop_never: bool,
+
+    /// Starts reading at the position of the first file.
+    #[arg(long, short = 'j')]
+    pub start_position: Option<u64>,
+
+    /// Stops reading at the position of the last file, or the start file when reading from the server.
+    #[arg(long)]
+    pub stop_position: Option<u64>,
+
+    /// Starts reading at the first event with timestamp equal or later than 'YYYY-MM-DD HH:MM:SS' UTC.
+    #[arg(long, value_parser = parse_datetime)]
+    pub start_datetime: Option<u32>,
+
+    /// Stops reading at the first event with timestamp equal or later than 'YYYY-MM-DD HH:MM:SS' UTC.
+    #[arg(long, value_parser = parse_datetime)]
+    pub stop_datetime: Option<u32>,
+
+    /// Skips transactions of MySQL GTID set or MariaDB GTID list.
+    /// When reading from the server, replication starts from the GTIDs.
+    #[arg(long)]
+    pub start_gtid: Option<String>,
+
+    /// Stops reading after the transaction with the GTID.
+    #[arg(long)]
+    pub stop_gtid: Option<String>,
+
+    /// Prints only events of the database. Can be repeated.
+    #[arg(long, short = 'd')]
+    pub database: Vec<String>,
+
+    /// Prints only row events of the table given as name or database.name. Can be repeated.
+    #[arg(long, short = 't')]
+    pub table: Vec<String>,
+
+    /// Output format.
+    #[arg(long, short = 'f', value_enum, default_value_t = Format::Human)]
+    pub format: Format,
+
+    /// Prints statements that undo row events of the selected range in reverse order.
+    #[arg(long)]
+    pub flashback: bool,
+
+    /// Renders rows of an event as one statement in SQL format.
+    #[arg(long)]
+    pub multi_row: bool,
+
+    /// Decodes rows as pseudo-SQL. Repeat to add column types like mysqlbinlog -vv.
+    #[arg(long, short = 'v', action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum Format {
+    /// Event headers and descriptions like mysqlbinlog.
+    Human,
+
+    /// One JSON object per event.
+    Json,
+
+    /// SQL statements.
+    Sql,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug, ValueEnum)]
+pub enum Compression {
+    Uncompressed,
+    Zlib,
+    Zstd,
+}
+
+/// Parses 'YYYY-MM-DD HH:MM:SS' to seconds since 1970-01-01 UTC.
+pub fn parse_datetime(value: &str) -> Result<u32, String> {
+    let parse = || -> Result<u32, Error> {
+        let value = value.trim();
+        let (date, time) = value.split_once([' ', 'T']).unwrap_or((value, "00:00:00"));
+        let date: Vec<&str> = date.split('-').collect();
+        let time: Vec<&str> = time.split(':').collect();
+        if date.len() != 3 || time.len() != 3 {
+            return Err(Error::String("Invalid format".to_string()));
+        }
+
+        let date = Date {
+            year: date[0].parse()?,
+            month: date[1].parse()?,
+            day: date[2].parse()?,
+        };
+        let (hour, minute, second) = (
+            time[0].parse::<u8>()? as i64,
+            time[1].parse::<u8>()? as i64,
+            time[2].parse::<u8>()? as i64,
+        );
+        // Invalid days like 02-30 don't survive the round trip through epoch days
+        let days = date.to_epoch_days();
+        if date.month == 0 || date.day == 0 || Date::from_epoch_days(days) != date {
+            return Err(Error::String("Invalid date".to_string()));
+        }
+        if hour > 23 || minute > 59 || second > 59 {
+            return Err(Error::String("Invalid time".to_string()));
+        }
+
+        let seconds = days * 86400 + hour * 3600 + minute * 60 + second;
+        u32::try_from(seconds).map_err(|_| Error::String("Out of range".to_string()))
+    };
+    parse().map_err(|e| format!("Expected 'YYYY-MM-DD HH:MM:SS': {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_datetime;
+
+    #[test]
+    fn parse_datetimes() {
+        assert_eq!(Ok(0), parse_datetime("1970-01-01 00:00:00"));
+        assert_eq!(Ok(1704164645), parse_datetime("2024-01-02 03:04:05"));
+        assert_eq!(Ok(1704153600), parse_datetime("2024-01-02"));
+        assert!(parse_datetime("2024-01-02 03:04").is_err());
+        assert!(parse_datetime("1969-12-31 23:59:59").is_err());
+        assert_eq!(Ok(1704153600), parse_datetime(" 2024-01-02 "));
+        assert_eq!(Ok(1709164800), parse_datetime("2024-02-29"));
+        assert!(parse_datetime("2023-02-29").is_err());
+        assert!(parse_datetime("2024-13-45 99:99:99").is_err());
+        assert!(parse_datetime("2024-00-10").is_err());
+        assert!(parse_datetime("2024-01-00").is_err());
+        assert!(parse_datetime("2024-01-02 24:00:00").is_err());
+        assert!(parse_datetime("2024-01-02 03:60:00").is_err());
+        assert!(parse_datetime("2024-01-02 03:04:60").is_err());
+        assert!(parse_datetime("2024-01-02 -1:04:05").is_err());
+    }
+}
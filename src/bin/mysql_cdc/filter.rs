@@ -0,0 +1,461 @@
+use crate::args::Args;
+use mysql_cdc::errors::Error;
+use mysql_cdc::events::binlog_event::BinlogEvent;
+use mysql_cdc::events::event_header::EventHeader;
+use mysql_cdc::events::table_map_event::TableMapEvent;
+use mysql_cdc::providers::mariadb::gtid::gtid_list::GtidList;
+use mysql_cdc::providers::mysql::events::gtid_tagged_event::GtidTaggedEvent;
+use mysql_cdc::providers::mysql::gtid::gtid_set::GtidSet;
+use std::collections::HashMap;
+
+/// GTID set of MySQL or GTID list of MariaDB.
+pub enum GtidPosition {
+    MySql(GtidSet),
+    MariaDb(GtidList),
+}
+
+impl GtidPosition {
+    /// MySQL GTIDs have server uuid followed by colon.
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        match value.contains(':') {
+            true => Ok(GtidPosition::MySql(GtidSet::parse(value)?)),
+            false => Ok(GtidPosition::MariaDb(GtidList::parse(value)?)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Decision {
+    Print,
+    Skip,
+    Stop,
+}
+
+/// Selects events by position, time, GTID, database and table.
+pub struct EventFilter {
+    start_position: Option<u64>,
+    stop_position: Option<u64>,
+    start_datetime: Option<u32>,
+    stop_datetime: Option<u32>,
+    start_gtid: Option<GtidPosition>,
+    stop_gtid: Option<String>,
+    databases: Vec<String>,
+    tables: Vec<String>,
+
+    /// Positions are checked only in the first and the last file.
+    pub first_file: bool,
+    pub last_file: bool,
+
+    skip_transaction: bool,
+    stop_after_transaction: bool,
+    finished: bool,
+}
+
+impl EventFilter {
+    pub fn new(args: &Args) -> Result<Self, Error> {
+        Ok(Self {
+            start_position: args.start_position,
+            stop_position: args.stop_position,
+            start_datetime: args.start_datetime,
+            stop_datetime: args.stop_datetime,
+            start_gtid: args
+                .start_gtid
+                .as_deref()
+                .map(GtidPosition::parse)
+                .transpose()?,
+            stop_gtid: args.stop_gtid.clone(),
+            databases: args.database.clone(),
+            tables: args.table.clone(),
+            first_file: true,
+            last_file: true,
+            skip_transaction: false,
+            stop_after_transaction: false,
+            finished: false,
+        })
+    }
+
+    /// Returns true if the last printed event finished the transaction of --stop-gtid.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    pub fn check(
+        &mut self,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Decision {
+        let position = header
+            .next_event_position
+            .saturating_sub(header.event_length) as u64;
+        if self.last_file && self.stop_position.is_some_and(|x| position >= x) {
+            return Decision::Stop;
+        }
+        // Artificial events like fake RotateEvent have zero timestamp
+        if header.timestamp > 0 && self.stop_datetime.is_some_and(|x| header.timestamp >= x) {
+            return Decision::Stop;
+        }
+
+        if let Some(gtid) = self.check_gtid(event) {
+            return gtid;
+        }
+        if self.skip_transaction {
+            return Decision::Skip;
+        }
+
+        // Format description and table maps are needed to decode following events
+        if let BinlogEvent::FormatDescriptionEvent(_) = event {
+            return Decision::Print;
+        }
+        if self.first_file && self.start_position.is_some_and(|x| position < x) {
+            return Decision::Skip;
+        }
+        if header.timestamp > 0 && self.start_datetime.is_some_and(|x| header.timestamp < x) {
+            return Decision::Skip;
+        }
+
+        let table = match event {
+            BinlogEvent::TableMapEvent(x) => Some(x),
+            BinlogEvent::WriteRowsEvent(x) => tables.get(&x.table_id),
+            BinlogEvent::UpdateRowsEvent(x) => tables.get(&x.table_id),
+            BinlogEvent::DeleteRowsEvent(x) => tables.get(&x.table_id),
+            _ => None,
+        };
+        let included = match (event, table) {
+            (_, Some(table)) => self.includes_table(&table.database_name, &table.table_name),
+            (BinlogEvent::QueryEvent(x), None) => {
+                let transaction = ["BEGIN", "COMMIT", "ROLLBACK"].contains(&&*x.sql_statement);
+                transaction || self.includes_database(&x.database_name)
+            }
+            _ => true,
+        };
+        if !included {
+            return Decision::Skip;
+        }
+
+        if self.stop_after_transaction {
+            self.finished = match event {
+                BinlogEvent::XidEvent(_) => true,
+                BinlogEvent::QueryEvent(x) => x.sql_statement != "BEGIN",
+                _ => false,
+            };
+        }
+        Decision::Print
+    }
+
+    fn check_gtid(&mut self, event: &BinlogEvent) -> Option<Decision> {
+        let (gtid, executed) = match (event, &self.start_gtid) {
+            (BinlogEvent::MySqlGtidEvent(x), Some(GtidPosition::MySql(set))) => {
+                (x.gtid.to_string(), set.contains(&x.gtid))
+            }
+            (BinlogEvent::MySqlGtidTaggedEvent(x), Some(GtidPosition::MySql(set))) => {
+                (tagged_gtid(x), set.contains_tagged(&x.gtid, &x.tag))
+            }
+            (BinlogEvent::MariaDbGtidEvent(x), Some(GtidPosition::MariaDb(list))) => {
+                (x.gtid.to_string(), list.contains(&x.gtid))
+            }
+            (BinlogEvent::MySqlGtidEvent(x), _) => (x.gtid.to_string(), false),
+            (BinlogEvent::MySqlGtidTaggedEvent(x), _) => (tagged_gtid(x), false),
+            (BinlogEvent::MariaDbGtidEvent(x), _) => (x.gtid.to_string(), false),
+            _ => return None,
+        };
+
+        // Next transaction started after the stop transaction
+        if self.stop_after_transaction {
+            return Some(Decision::Stop);
+        }
+        self.stop_after_transaction = self.stop_gtid.as_ref() == Some(&gtid);
+        self.skip_transaction = executed;
+        None
+    }
+
+    fn includes_database(&self, database: &str) -> bool {
+        self.databases.is_empty() || self.databases.iter().any(|x| x == database)
+    }
+
+    fn includes_table(&self, database: &str, table: &str) -> bool {
+        if !self.includes_database(database) {
+            return false;
+        }
+        self.tables.is_empty()
+            || self.tables.iter().any(|x| match x.split_once('.') {
+                Some((db, name)) => db == database && name == table,
+                None => x == table,
+            })
+    }
+}
+
+/// Formats tagged GTID like uuid:tag:transaction_id.
+fn tagged_gtid(event: &GtidTaggedEvent) -> String {
+    format!(
+        "{}:{}:{}",
+        event.gtid.source_id, event.tag, event.gtid.transaction_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decision, EventFilter};
+    use crate::args::Args;
+    use clap::Parser;
+    use mysql_cdc::events::binlog_event::BinlogEvent;
+    use mysql_cdc::events::event_header::EventHeader;
+    use mysql_cdc::events::query_event::QueryEvent;
+    use mysql_cdc::events::row_events::write_rows_event::WriteRowsEvent;
+    use mysql_cdc::events::table_map_event::TableMapEvent;
+    use mysql_cdc::events::xid_event::XidEvent;
+    use mysql_cdc::providers::mysql::events::gtid_event::GtidEvent;
+    use mysql_cdc::providers::mysql::events::gtid_tagged_event::GtidTaggedEvent;
+    use mysql_cdc::providers::mysql::gtid::gtid::Gtid;
+    use std::collections::HashMap;
+
+    const SERVER_UUID: &str = "24bc7850-2c16-11e6-a073-0242ac110002";
+
+    fn create_filter(args: &[&str]) -> EventFilter {
+        let args = Args::parse_from([&["mysql_cdc"], args].concat());
+        EventFilter::new(&args).unwrap()
+    }
+
+    fn create_header(timestamp: u32, position: u32) -> EventHeader {
+        EventHeader {
+            timestamp,
+            event_type: 0,
+            server_id: 1,
+            event_length: 100,
+            next_event_position: position + 100,
+            event_flags: 0,
+        }
+    }
+
+    fn query(database: &str, sql: &str) -> BinlogEvent {
+        BinlogEvent::QueryEvent(QueryEvent {
+            thread_id: 1,
+            duration: 0,
+            error_code: 0,
+            status_variables: Vec::new(),
+            database_name: database.to_string(),
+            sql_statement: sql.to_string(),
+        })
+    }
+
+    fn gtid(transaction_id: u64) -> BinlogEvent {
+        let gtid = Gtid::parse(&format!("{}:{}", SERVER_UUID, transaction_id)).unwrap();
+        BinlogEvent::MySqlGtidEvent(GtidEvent {
+            gtid,
+            flags: 0,
+            last_committed: None,
+            sequence_number: None,
+            immediate_commit_timestamp: None,
+            original_commit_timestamp: None,
+            transaction_length: None,
+            immediate_server_version: None,
+            original_server_version: None,
+        })
+    }
+
+    fn tagged_gtid(transaction_id: u64) -> BinlogEvent {
+        let gtid = Gtid::parse(&format!("{}:{}", SERVER_UUID, transaction_id)).unwrap();
+        BinlogEvent::MySqlGtidTaggedEvent(GtidTaggedEvent {
+            gtid,
+            tag: "abc".to_string(),
+            flags: 0,
+            last_committed: 0,
+            sequence_number: 0,
+            immediate_commit_timestamp: 0,
+            original_commit_timestamp: 0,
+            transaction_length: 0,
+            immediate_server_version: 80400,
+            original_server_version: 80400,
+            commit_group_ticket: None,
+        })
+    }
+
+    fn table_map(table_id: u64, database: &str, table: &str) -> TableMapEvent {
+        TableMapEvent {
+            table_id,
+            database_name: database.to_string(),
+            table_name: table.to_string(),
+            column_types: vec![3],
+            column_metadata: vec![0],
+            null_bitmap: vec![false],
+            table_metadata: None,
+        }
+    }
+
+    fn write_rows(table_id: u64) -> BinlogEvent {
+        BinlogEvent::WriteRowsEvent(WriteRowsEvent {
+            table_id,
+            flags: 0,
+            columns_number: 1,
+            columns_present: vec![true],
+            rows: Vec::new(),
+        })
+    }
+
+    /// Checks events of the transaction at consecutive positions.
+    fn check_all(filter: &mut EventFilter, events: &[BinlogEvent]) -> Vec<Decision> {
+        let tables = HashMap::new();
+        events
+            .iter()
+            .enumerate()
+            .map(|(i, event)| {
+                filter.check(&create_header(1, 1000 + i as u32 * 100), event, &tables)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn check_positions_in_first_and_last_file() {
+        let mut filter = create_filter(&["--start-position", "300", "--stop-position", "600"]);
+        let tables = HashMap::new();
+        let event = query("shop", "BEGIN");
+
+        assert_eq!(
+            Decision::Skip,
+            filter.check(&create_header(1, 200), &event, &tables)
+        );
+        assert_eq!(
+            Decision::Print,
+            filter.check(&create_header(1, 300), &event, &tables)
+        );
+        assert_eq!(
+            Decision::Stop,
+            filter.check(&create_header(1, 600), &event, &tables)
+        );
+
+        filter.first_file = false;
+        filter.last_file = false;
+        assert_eq!(
+            Decision::Print,
+            filter.check(&create_header(1, 200), &event, &tables)
+        );
+        assert_eq!(
+            Decision::Print,
+            filter.check(&create_header(1, 600), &event, &tables)
+        );
+    }
+
+    #[test]
+    fn check_datetimes() {
+        let mut filter = create_filter(&[
+            "--start-datetime",
+            "2024-01-02 00:00:00",
+            "--stop-datetime",
+            "2024-01-02 03:04:05",
+        ]);
+        let tables = HashMap::new();
+        let event = query("shop", "BEGIN");
+
+        let check = |filter: &mut EventFilter, timestamp| {
+            filter.check(&create_header(timestamp, 4), &event, &tables)
+        };
+        assert_eq!(Decision::Skip, check(&mut filter, 1704153599));
+        assert_eq!(Decision::Print, check(&mut filter, 1704153600));
+        assert_eq!(Decision::Print, check(&mut filter, 1704164644));
+        assert_eq!(Decision::Stop, check(&mut filter, 1704164645));
+        // Artificial events are not filtered by time
+        assert_eq!(Decision::Print, check(&mut filter, 0));
+    }
+
+    #[test]
+    fn skip_transactions_of_start_gtid() {
+        let start_gtid = format!("{}:1-5", SERVER_UUID);
+        let mut filter = create_filter(&["--start-gtid", &start_gtid]);
+
+        let events = [
+            gtid(5),
+            query("shop", "BEGIN"),
+            query("shop", "DELETE FROM orders"),
+            BinlogEvent::XidEvent(XidEvent { xid: 1 }),
+            gtid(6),
+            query("shop", "BEGIN"),
+            query("shop", "INSERT INTO orders VALUES (1)"),
+            BinlogEvent::XidEvent(XidEvent { xid: 2 }),
+        ];
+        let expected = [[Decision::Skip; 4], [Decision::Print; 4]].concat();
+        assert_eq!(expected, check_all(&mut filter, &events));
+        assert!(!filter.is_finished());
+    }
+
+    #[test]
+    fn stop_after_transaction_of_stop_gtid() {
+        let stop_gtid = format!("{}:2", SERVER_UUID);
+        let mut filter = create_filter(&["--stop-gtid", &stop_gtid]);
+
+        let events = [
+            gtid(1),
+            query("shop", "CREATE TABLE orders (id INT)"),
+            gtid(2),
+            query("shop", "BEGIN"),
+        ];
+        assert_eq!(vec![Decision::Print; 4], check_all(&mut filter, &events));
+        assert!(!filter.is_finished());
+
+        let events = [BinlogEvent::XidEvent(XidEvent { xid: 1 })];
+        assert_eq!(vec![Decision::Print], check_all(&mut filter, &events));
+        assert!(filter.is_finished());
+
+        assert_eq!(vec![Decision::Stop], check_all(&mut filter, &[gtid(3)]));
+    }
+
+    #[test]
+    fn check_tagged_gtids() {
+        let start_gtid = format!("{}:abc:1-5", SERVER_UUID);
+        let stop_gtid = format!("{}:abc:6", SERVER_UUID);
+        let mut filter = create_filter(&["--start-gtid", &start_gtid, "--stop-gtid", &stop_gtid]);
+
+        let events = [
+            tagged_gtid(5),
+            query("shop", "CREATE TABLE orders (id INT)"),
+            gtid(5),
+            query("shop", "CREATE TABLE items (id INT)"),
+            tagged_gtid(6),
+            query("shop", "DROP TABLE items"),
+        ];
+        let expected = [&[Decision::Skip; 2][..], &[Decision::Print; 4]].concat();
+        assert_eq!(expected, check_all(&mut filter, &events));
+        assert!(filter.is_finished());
+    }
+
+    #[test]
+    fn filter_databases_and_tables() {
+        let args = [
+            "--database",
+            "shop",
+            "--table",
+            "shop.orders",
+            "--table",
+            "items",
+        ];
+        let mut filter = create_filter(&args);
+        let header = create_header(1, 4);
+
+        let mut tables = HashMap::new();
+        let mut check_table = |filter: &mut EventFilter, database, table| {
+            let table_map = table_map(tables.len() as u64, database, table);
+            let table_id = table_map.table_id;
+            let event = BinlogEvent::TableMapEvent(table_map.clone());
+            let decision = filter.check(&header, &event, &tables);
+            tables.insert(table_id, table_map);
+            let rows = filter.check(&header, &write_rows(table_id), &tables);
+            assert_eq!(decision, rows);
+            decision
+        };
+        assert_eq!(Decision::Print, check_table(&mut filter, "shop", "orders"));
+        assert_eq!(Decision::Print, check_table(&mut filter, "shop", "items"));
+        assert_eq!(
+            Decision::Skip,
+            check_table(&mut filter, "shop", "customers")
+        );
+        assert_eq!(Decision::Skip, check_table(&mut filter, "other", "orders"));
+        assert_eq!(Decision::Skip, check_table(&mut filter, "other", "items"));
+
+        let tables = HashMap::new();
+        let mut check_query = |database, sql| filter.check(&header, &query(database, sql), &tables);
+        assert_eq!(Decision::Print, check_query("shop", "DROP TABLE customers"));
+        assert_eq!(Decision::Skip, check_query("other", "DROP TABLE orders"));
+        // Transaction boundaries are printed in any database
+        assert_eq!(Decision::Print, check_query("other", "BEGIN"));
+        assert_eq!(Decision::Print, check_query("other", "COMMIT"));
+    }
+}
@@ -0,0 +1,168 @@
+mod args;
+mod filter;
+mod output;
+
+use args::{Args, Compression};
+use clap::Parser;
+use filter::{Decision, EventFilter, GtidPosition};
+use mysql_cdc::binlog_client::BinlogClient;
+use mysql_cdc::binlog_options::BinlogOptions;
+use mysql_cdc::binlog_reader::BinlogReader;
+use mysql_cdc::compression_mode::CompressionMode;
+use mysql_cdc::errors::Error;
+use mysql_cdc::events::binlog_event::BinlogEvent;
+use mysql_cdc::events::event_header::EventHeader;
+use mysql_cdc::events::table_map_event::TableMapEvent;
+use mysql_cdc::replica_options::ReplicaOptions;
+use mysql_cdc::sql::sql_options::SqlOptions;
+use output::Printer;
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::process::ExitCode;
+use std::time::Duration;
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("mysql_cdc: {:?}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> Result<(), Error> {
+    let mut filter = EventFilter::new(&args)?;
+    let stdout = BufWriter::new(io::stdout().lock());
+    let sql_options = SqlOptions {
+        multi_row: args.multi_row,
+        ..Default::default()
+    };
+    let mut printer = Printer::new(
+        stdout,
+        args.format,
+        args.verbose,
+        sql_options,
+        args.flashback,
+    );
+
+    match args.host.is_some() || args.socket.is_some() {
+        true => read_server(&args, &mut filter, &mut printer)?,
+        false => read_files(&args, &mut filter, &mut printer)?,
+    }
+    printer.finish()?;
+    Ok(())
+}
+
+fn read_server<W: Write>(
+    args: &Args,
+    filter: &mut EventFilter,
+    printer: &mut Printer<W>,
+) -> Result<(), Error> {
+    let binlog = match (&args.start_gtid, args.files.first()) {
+        (Some(gtid), _) => match GtidPosition::parse(gtid)? {
+            GtidPosition::MySql(gtid_set) => BinlogOptions::from_mysql_gtid(gtid_set),
+            GtidPosition::MariaDb(gtid_list) => BinlogOptions::from_mariadb_gtid(gtid_list),
+        },
+        (None, Some(file)) => {
+            let position = args.start_position.unwrap_or(4);
+            let position = u32::try_from(position)
+                .map_err(|_| Error::String(format!("Invalid start position {}", position)))?;
+            BinlogOptions::from_position(file.clone(), position)
+        }
+        (None, None) => BinlogOptions::from_start(),
+    };
+
+    let options = ReplicaOptions {
+        hostname: args.host.clone().unwrap_or_default(),
+        port: args.port,
+        socket_path: args.socket.clone(),
+        username: args.user.clone(),
+        password: args.password.clone(),
+        server_public_key_path: args.server_public_key_path.clone(),
+        enable_cleartext_plugin: args.enable_cleartext_plugin,
+        compression: match args.compression_algorithm {
+            Compression::Uncompressed => CompressionMode::Disabled,
+            Compression::Zlib => CompressionMode::Zlib,
+            Compression::Zstd => CompressionMode::Zstd,
+        },
+        compression_level: args.compression_level,
+        server_id: args.server_id,
+        connect_timeout: Duration::from_secs(args.connect_timeout),
+        blocking: args.stop_never,
+        binlog,
+        ..Default::default()
+    };
+
+    // Positions apply to the file replication starts from
+    filter.first_file = true;
+    filter.last_file = true;
+    let mut binlog_file = args.files.first().cloned().unwrap_or_default();
+    let mut tables = HashMap::new();
+
+    let mut client = BinlogClient::new(options);
+    for result in client.replicate()? {
+        let (header, event) = result?;
+        if let BinlogEvent::RotateEvent(x) = &event {
+            if !binlog_file.is_empty() && x.binlog_filename != binlog_file {
+                filter.first_file = false;
+                filter.last_file = false;
+            }
+            binlog_file = x.binlog_filename.clone();
+        }
+        if !handle_event(&binlog_file, &header, &event, &mut tables, filter, printer)? {
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn read_files<W: Write>(
+    args: &Args,
+    filter: &mut EventFilter,
+    printer: &mut Printer<W>,
+) -> Result<(), Error> {
+    if args.files.is_empty() {
+        return Err(Error::String(
+            "Binlog files, --host or --socket must be specified".to_string(),
+        ));
+    }
+
+    for (index, binlog_file) in args.files.iter().enumerate() {
+        filter.first_file = index == 0;
+        filter.last_file = index == args.files.len() - 1;
+
+        let reader = BinlogReader::new(File::open(binlog_file)?)?;
+        let mut tables = HashMap::new();
+        for result in reader.read_events() {
+            let (header, event) = result?;
+            if !handle_event(binlog_file, &header, &event, &mut tables, filter, printer)? {
+                return Ok(());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints the event if it passes the filter. Returns false when reading must stop.
+fn handle_event<W: Write>(
+    binlog_file: &str,
+    header: &EventHeader,
+    event: &BinlogEvent,
+    tables: &mut HashMap<u64, TableMapEvent>,
+    filter: &mut EventFilter,
+    printer: &mut Printer<W>,
+) -> Result<bool, Error> {
+    if let BinlogEvent::TableMapEvent(x) = event {
+        tables.insert(x.table_id, x.clone());
+    }
+
+    match filter.check(header, event, tables) {
+        Decision::Print => printer.print(binlog_file, header, event, tables)?,
+        Decision::Skip => return Ok(true),
+        Decision::Stop => return Ok(false),
+    }
+    Ok(!filter.is_finished())
+}
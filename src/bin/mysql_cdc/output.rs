@@ -0,0 +1,334 @@
+use crate::args::Format;
+use mysql_cdc::errors::Error;
+use mysql_cdc::events::binlog_event::BinlogEvent;
+use mysql_cdc::events::event_header::EventHeader;
+use mysql_cdc::events::row_events::mysql_value::DateTime;
+use mysql_cdc::events::row_events::row_data::RowData;
+use mysql_cdc::events::table_map_event::TableMapEvent;
+use mysql_cdc::sql::flashback::FlashbackGenerator;
+use mysql_cdc::sql::flashback_options::FlashbackOptions;
+use mysql_cdc::sql::sql_generator::SqlGenerator;
+use mysql_cdc::sql::sql_options::SqlOptions;
+use mysql_cdc::sql::value_formatter::format_value;
+use serde_json::json;
+use std::collections::HashMap;
+use std::io::Write;
+
+/// Writes events in the selected format.
+pub struct Printer<W: Write> {
+    out: W,
+    format: Format,
+    verbose: u8,
+    database: String,
+    generator: SqlGenerator,
+    flashback: Option<FlashbackGenerator>,
+}
+
+impl<W: Write> Printer<W> {
+    pub fn new(
+        out: W,
+        format: Format,
+        verbose: u8,
+        sql_options: SqlOptions,
+        flashback: bool,
+    ) -> Self {
+        let flashback = flashback.then(|| {
+            FlashbackGenerator::new(FlashbackOptions {
+                sql: sql_options.clone(),
+                ..Default::default()
+            })
+        });
+        Self {
+            out,
+            format,
+            verbose,
+            database: String::new(),
+            generator: SqlGenerator::new(sql_options),
+            flashback,
+        }
+    }
+
+    pub fn print(
+        &mut self,
+        binlog_file: &str,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Result<(), Error> {
+        // Undo statements are printed in reverse order at the end
+        if let Some(flashback) = &mut self.flashback {
+            flashback.add(header, event)?;
+            return Ok(());
+        }
+        match self.format {
+            Format::Human => self.print_human(header, event, tables),
+            Format::Json => self.print_json(binlog_file, header, event, tables),
+            Format::Sql => self.print_sql(header, event, tables),
+        }
+    }
+
+    /// Prints undo statements in flashback mode and flushes the output.
+    pub fn finish(&mut self) -> Result<(), Error> {
+        if let Some(flashback) = self.flashback.take() {
+            for statement in flashback.finish() {
+                writeln!(self.out, "{};", statement)?;
+            }
+        }
+        self.out.flush()?;
+        Ok(())
+    }
+
+    fn print_human(
+        &mut self,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Result<(), Error> {
+        let position = header
+            .next_event_position
+            .saturating_sub(header.event_length);
+        let time = DateTime::from_epoch_micros(header.timestamp as i64 * 1_000_000);
+        writeln!(self.out, "# at {}", position)?;
+        writeln!(
+            self.out,
+            "#{:02}{:02}{:02} {:2}:{:02}:{:02} server id {}  end_log_pos {} \t{}",
+            time.year % 100,
+            time.month,
+            time.day,
+            time.hour,
+            time.minute,
+            time.second,
+            header.server_id,
+            header.next_event_position,
+            event_name(event)
+        )?;
+
+        match event {
+            BinlogEvent::QueryEvent(x) => {
+                if !x.database_name.is_empty() {
+                    writeln!(self.out, "use `{}`;", x.database_name)?;
+                }
+                writeln!(self.out, "{};", x.sql_statement)?;
+            }
+            BinlogEvent::TableMapEvent(x) => writeln!(
+                self.out,
+                "# Table_map: `{}`.`{}` mapped to number {}",
+                x.database_name, x.table_name, x.table_id
+            )?,
+            BinlogEvent::RotateEvent(x) => writeln!(
+                self.out,
+                "# Rotate to {}  pos: {}",
+                x.binlog_filename, x.binlog_position
+            )?,
+            BinlogEvent::MySqlGtidEvent(x) | BinlogEvent::MySqlAnonymousGtidEvent(x) => {
+                writeln!(self.out, "# GTID {}", x.gtid)?
+            }
+            BinlogEvent::MariaDbGtidEvent(x) => writeln!(self.out, "# GTID {}", x.gtid)?,
+            BinlogEvent::XidEvent(x) => writeln!(self.out, "# Xid = {}\nCOMMIT;", x.xid)?,
+            BinlogEvent::WriteRowsEvent(_)
+            | BinlogEvent::UpdateRowsEvent(_)
+            | BinlogEvent::DeleteRowsEvent(_) => self.print_rows(event, tables)?,
+            BinlogEvent::FormatDescriptionEvent(_) | BinlogEvent::StopEvent => {}
+            _ => writeln!(self.out, "# {:?}", event)?,
+        }
+        Ok(())
+    }
+
+    fn print_json(
+        &mut self,
+        binlog_file: &str,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Result<(), Error> {
+        let mut value = json!({
+            "file": binlog_file,
+            "header": header,
+            "event": event,
+        });
+        if let Some(table) = row_table(event, tables) {
+            value["database"] = json!(table.database_name);
+            value["table"] = json!(table.table_name);
+        }
+        writeln!(self.out, "{}", value)?;
+        Ok(())
+    }
+
+    fn print_sql(
+        &mut self,
+        header: &EventHeader,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Result<(), Error> {
+        match event {
+            BinlogEvent::QueryEvent(x) => {
+                if !x.database_name.is_empty() && x.database_name != self.database {
+                    self.database = x.database_name.clone();
+                    writeln!(self.out, "USE `{}`;", x.database_name)?;
+                }
+                writeln!(self.out, "SET TIMESTAMP={};", header.timestamp)?;
+                writeln!(self.out, "{};", x.sql_statement)?;
+            }
+            BinlogEvent::XidEvent(_) => writeln!(self.out, "COMMIT;")?,
+            BinlogEvent::MySqlGtidEvent(x) => writeln!(self.out, "-- GTID {}", x.gtid)?,
+            BinlogEvent::MariaDbGtidEvent(x) => writeln!(self.out, "-- GTID {}", x.gtid)?,
+            BinlogEvent::WriteRowsEvent(_)
+            | BinlogEvent::UpdateRowsEvent(_)
+            | BinlogEvent::DeleteRowsEvent(_) => {
+                if let Some(table) = row_table(event, tables) {
+                    for statement in self.generator.generate(event, table)? {
+                        writeln!(self.out, "{};", statement)?;
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Writes rows as pseudo-SQL comments like mysqlbinlog --verbose.
+    fn print_rows(
+        &mut self,
+        event: &BinlogEvent,
+        tables: &HashMap<u64, TableMapEvent>,
+    ) -> Result<(), Error> {
+        let table = match row_table(event, tables) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        if self.verbose == 0 {
+            writeln!(
+                self.out,
+                "# {}: table id {}",
+                event_name(event),
+                table.table_id
+            )?;
+            return Ok(());
+        }
+
+        let name = format!("`{}`.`{}`", table.database_name, table.table_name);
+        match event {
+            BinlogEvent::WriteRowsEvent(x) => {
+                for row in &x.rows {
+                    writeln!(self.out, "### INSERT INTO {}\n### SET", name)?;
+                    self.print_row(row, table)?;
+                }
+            }
+            BinlogEvent::UpdateRowsEvent(x) => {
+                for row in &x.rows {
+                    writeln!(self.out, "### UPDATE {}\n### WHERE", name)?;
+                    self.print_row(&row.before_update, table)?;
+                    writeln!(self.out, "### SET")?;
+                    self.print_row(&row.after_update, table)?;
+                }
+            }
+            BinlogEvent::DeleteRowsEvent(x) => {
+                for row in &x.rows {
+                    writeln!(self.out, "### DELETE FROM {}\n### WHERE", name)?;
+                    self.print_row(row, table)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn print_row(&mut self, row: &RowData, table: &TableMapEvent) -> Result<(), Error> {
+        let names = table
+            .table_metadata
+            .as_ref()
+            .and_then(|x| x.column_names.as_ref());
+
+        for (index, cell) in row.cells.iter().enumerate() {
+            let unsigned = table.is_unsigned(index).unwrap_or(false);
+            let name = match names.and_then(|x| x.get(index)) {
+                Some(name) => format!("`{}`", name),
+                None => format!("@{}", index + 1),
+            };
+            let value = format_value(table, index, cell.as_ref())?;
+            write!(self.out, "###   {}={}", name, value)?;
+
+            if self.verbose > 1 {
+                let (column_type, metadata) = table.actual_column_type(index);
+                write!(
+                    self.out,
+                    " /* {} meta={} nullable={} is_null={} */",
+                    type_name(column_type, metadata, unsigned),
+                    metadata,
+                    table.null_bitmap.get(index).copied().unwrap_or(true) as u8,
+                    cell.is_none() as u8
+                )?;
+            }
+            writeln!(self.out)?;
+        }
+        Ok(())
+    }
+}
+
+fn row_table<'a>(
+    event: &BinlogEvent,
+    tables: &'a HashMap<u64, TableMapEvent>,
+) -> Option<&'a TableMapEvent> {
+    match event {
+        BinlogEvent::WriteRowsEvent(x) => tables.get(&x.table_id),
+        BinlogEvent::UpdateRowsEvent(x) => tables.get(&x.table_id),
+        BinlogEvent::DeleteRowsEvent(x) => tables.get(&x.table_id),
+        _ => None,
+    }
+}
+
+/// Returns event type name like in mysqlbinlog output.
+fn event_name(event: &BinlogEvent) -> String {
+    let name = match event {
+        BinlogEvent::QueryEvent(_) => "Query",
+        BinlogEvent::TableMapEvent(_) => "Table_map",
+        BinlogEvent::WriteRowsEvent(_) => "Write_rows",
+        BinlogEvent::UpdateRowsEvent(_) => "Update_rows",
+        BinlogEvent::DeleteRowsEvent(_) => "Delete_rows",
+        BinlogEvent::XidEvent(_) => "Xid",
+        BinlogEvent::RotateEvent(_) => "Rotate",
+        BinlogEvent::FormatDescriptionEvent(_) => "Start",
+        BinlogEvent::MySqlGtidEvent(_) | BinlogEvent::MariaDbGtidEvent(_) => "GTID",
+        BinlogEvent::MySqlAnonymousGtidEvent(_) => "Anonymous_GTID",
+        BinlogEvent::MySqlPrevGtidsEvent(_) => "Previous_gtids",
+        _ => {
+            // Variant name of other events
+            let debug = format!("{:?}", event);
+            let end = debug.find(['(', ' ', '{']).unwrap_or(debug.len());
+            return debug[..end].to_string();
+        }
+    };
+    name.to_string()
+}
+
+/// Returns column type description like mysqlbinlog -vv.
+fn type_name(column_type: u8, metadata: u16, unsigned: bool) -> String {
+    let unsigned = if unsigned { " UNSIGNED" } else { "" };
+    match column_type {
+        1 => format!("TINYINT{}", unsigned),
+        2 => format!("SHORTINT{}", unsigned),
+        3 => format!("INT{}", unsigned),
+        4 => "FLOAT".to_string(),
+        5 => "DOUBLE".to_string(),
+        7 => "TIMESTAMP".to_string(),
+        8 => format!("LONGINT{}", unsigned),
+        9 => format!("MEDIUMINT{}", unsigned),
+        10 | 14 => "DATE".to_string(),
+        11 => "TIME".to_string(),
+        12 => "DATETIME".to_string(),
+        13 => "YEAR".to_string(),
+        15 => format!("VARSTRING({})", metadata),
+        16 => format!("BIT({})", (metadata >> 8) * 8 + (metadata & 0xFF)),
+        17 => format!("TIMESTAMP({})", metadata),
+        18 => format!("DATETIME({})", metadata),
+        19 => format!("TIME({})", metadata),
+        245 => "JSON".to_string(),
+        246 => format!("DECIMAL({},{})", metadata & 0xFF, metadata >> 8),
+        247 => "ENUM".to_string(),
+        248 => "SET".to_string(),
+        249..=252 => "BLOB".to_string(),
+        254 => format!("STRING({})", metadata),
+        255 => "GEOMETRY".to_string(),
+        _ => format!("TYPE({})", column_type),
+    }
+}
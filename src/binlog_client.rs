@@ -14,9 +14,13 @@ use crate::starting_strategy::StartingStrategy;
 /// MySql replication client streaming binlog events in real-time.
 pub struct BinlogClient {
     pub options: ReplicaOptions,
+
+    /// Index of the server in `ReplicaOptions::servers` the client is attached to.
+    pub(crate) server: usize,
     transaction: bool,
     maria_gtid: Option<MariaGtid>,
     mysql_gtid: Option<MySqlGtid>,
+    mysql_tag: String,
 }
 
 impl BinlogClient {
@@ -27,15 +31,18 @@ impl BinlogClient {
 
         Self {
             options,
+            server: 0,
             transaction: false,
             maria_gtid: None,
             mysql_gtid: None,
+            mysql_tag: String::new(),
         }
     }
 
-    /// Replicates binlog events from the server
+    /// Replicates binlog events from the server.
+    /// Failover servers are tried when the current server is unavailable.
     pub fn replicate(&mut self) -> Result<BinlogEvents, Error> {
-        let (mut channel, provider) = self.connect()?;
+        let (mut channel, provider) = self.connect_with_failover()?;
 
         // Reset on reconnect
         self.transaction = false;
@@ -74,11 +81,21 @@ impl BinlogClient {
         match event {
             BinlogEvent::MariaDbGtidEvent(x) => {
                 self.maria_gtid = Some(x.gtid.clone());
+                // Standalone event groups like DDL don't have BEGIN and XID events.
+                self.transaction = !x.is_standalone();
             }
             BinlogEvent::MySqlGtidEvent(x) => {
                 self.mysql_gtid = Some(x.gtid.clone());
+                self.mysql_tag.clear();
+            }
+            BinlogEvent::MySqlGtidTaggedEvent(x) => {
+                self.mysql_gtid = Some(x.gtid.clone());
+                self.mysql_tag = x.tag.clone();
             }
-            BinlogEvent::XidEvent(_) => {
+            BinlogEvent::MySqlAnonymousGtidEvent(_) => {
+                self.mysql_gtid = None;
+            }
+            BinlogEvent::XidEvent(_) | BinlogEvent::MySqlXaPrepareEvent(_) => {
                 self.commit_gtid();
             }
             BinlogEvent::QueryEvent(x) => {
@@ -87,7 +104,10 @@ impl BinlogClient {
                 }
                 if x.sql_statement == "BEGIN" {
                     self.transaction = true;
-                } else if x.sql_statement == "COMMIT" || x.sql_statement == "ROLLBACK" {
+                } else if x.sql_statement == "COMMIT"
+                    || x.sql_statement == "ROLLBACK"
+                    || x.sql_statement.starts_with("XA PREPARE")
+                {
                     self.commit_gtid();
                 } else if !self.transaction {
                     // Auto-commit query like DDL
@@ -126,8 +146,55 @@ impl BinlogClient {
         }
         if let Some(gtid) = &self.mysql_gtid {
             if let Some(set) = &mut self.options.binlog.gtid_set {
-                set.add_gtid(gtid.clone()).unwrap();
+                set.add_tagged_gtid(gtid.clone(), &self.mysql_tag).unwrap();
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::binlog_options::BinlogOptions;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::xid_event::XidEvent;
+    use crate::providers::mysql::events::gtid_tagged_event::GtidTaggedEvent;
+    use crate::providers::mysql::gtid::gtid::Gtid;
+    use crate::providers::mysql::gtid::gtid_set::GtidSet;
+    use crate::replica_options::ReplicaOptions;
+    use crate::test_fixtures::create_header;
+
+    const SERVER_UUID: &str = "24bc7850-2c16-11e6-a073-0242ac110002";
+
+    #[test]
+    fn commit_tagged_gtid() {
+        let gtid_set = GtidSet::parse(&format!("{}:1-5", SERVER_UUID)).unwrap();
+        let mut client = BinlogClient::new(ReplicaOptions {
+            binlog: BinlogOptions::from_mysql_gtid(gtid_set),
+            ..Default::default()
+        });
+
+        let gtid = Gtid::parse(&format!("{}:3", SERVER_UUID)).unwrap();
+        let event = BinlogEvent::MySqlGtidTaggedEvent(GtidTaggedEvent {
+            gtid,
+            tag: "abc".to_string(),
+            flags: 0,
+            last_committed: 0,
+            sequence_number: 1,
+            immediate_commit_timestamp: 0,
+            original_commit_timestamp: 0,
+            transaction_length: 0,
+            immediate_server_version: 80400,
+            original_server_version: 80400,
+            commit_group_ticket: None,
+        });
+        client.commit(&create_header(4), &event);
+        client.commit(
+            &create_header(104),
+            &BinlogEvent::XidEvent(XidEvent { xid: 1 }),
+        );
+
+        let gtid_set = client.options.binlog.gtid_set.as_ref().unwrap();
+        assert_eq!(format!("{}:1-5:abc:3", SERVER_UUID), gtid_set.to_string());
+    }
+}
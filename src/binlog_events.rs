@@ -8,10 +8,12 @@ use crate::packet_channel::PacketChannel;
 use crate::responses::end_of_file_packet::EndOfFilePacket;
 use crate::responses::error_packet::ErrorPacket;
 use crate::responses::response_type::ResponseType;
+use std::collections::VecDeque;
 
 pub struct BinlogEvents {
     pub channel: PacketChannel,
     pub parser: EventParser,
+    pending: VecDeque<(EventHeader, BinlogEvent)>,
 }
 
 impl BinlogEvents {
@@ -19,9 +21,16 @@ impl BinlogEvents {
         let mut parser = EventParser::new();
         parser.checksum_type = checksum;
 
-        Self { channel, parser }
+        Self {
+            channel,
+            parser,
+            pending: VecDeque::new(),
+        }
     }
 
+    /// Parses the event of the network packet.
+    /// TransactionPayloadEvent is returned as is, use `into_events` to get its inner events.
+    /// The iterator returns inner events of compressed transactions one by one.
     pub fn read_event(&mut self, packet: &[u8]) -> Result<(EventHeader, BinlogEvent), Error> {
         let header = EventHeader::parse(&packet[1..])?;
         let event_slice = &packet[1 + EVENT_HEADER_SIZE..];
@@ -39,14 +48,25 @@ impl Iterator for BinlogEvents {
     type Item = Result<(EventHeader, BinlogEvent), Error>;
 
     /// Reads binlog event packets from network stream.
+    /// Events of compressed transactions are returned one by one.
     /// <a href="https://mariadb.com/kb/en/3-binlog-network-stream/">See more</a>
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+
         let (packet, _) = match self.channel.read_packet() {
             Ok(x) => x,
-            Err(e) => return Some(Err(Error::IoError(e))),
+            Err(e) => return Some(Err(e)),
         };
         match packet[0] {
-            ResponseType::OK => Some(self.read_event(&packet)),
+            ResponseType::OK => match self.read_event(&packet) {
+                Ok((header, BinlogEvent::TransactionPayloadEvent(x))) => {
+                    self.pending.extend(x.into_events(&header));
+                    self.next()
+                }
+                result => Some(result),
+            },
             ResponseType::ERROR => Some(self.read_error(&packet)),
             ResponseType::END_OF_FILE => {
                 let _ = EndOfFilePacket::parse(&packet[1..]);
@@ -5,6 +5,7 @@ use crate::starting_strategy::StartingStrategy;
 
 /// Replication options used when client connects to the server.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BinlogOptions {
     /// Binary log file name.
     /// The value is automatically changed on the RotateEvent.
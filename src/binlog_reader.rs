@@ -2,8 +2,12 @@ use crate::constants;
 use crate::errors::Error;
 use crate::events::binlog_event::BinlogEvent;
 use crate::events::event_header::EventHeader;
-use crate::events::event_parser::EventParser;
-use constants::EVENT_HEADER_SIZE;
+use crate::events::event_parser::{CustomParser, EventParser};
+use crate::providers::mariadb::encryption::binlog_decryptor::BinlogDecryptor;
+use crate::providers::mariadb::encryption::key_provider::KeyProvider;
+use byteorder::{LittleEndian, ReadBytesExt};
+use constants::{EVENT_HEADER_SIZE, EVENT_LENGTH_OFFSET};
+use std::collections::VecDeque;
 use std::fs::File;
 use std::io::{ErrorKind, Read};
 
@@ -14,10 +18,27 @@ pub struct BinlogReader {
     stream: File,
     parser: EventParser,
     payload_buffer: Vec<u8>,
+    pending: VecDeque<(EventHeader, BinlogEvent)>,
+    position: u64,
+    key_provider: Option<Box<dyn KeyProvider>>,
+    decryptor: Option<BinlogDecryptor>,
 }
 
 impl BinlogReader {
-    pub fn new(mut stream: File) -> Result<Self, Error> {
+    pub fn new(stream: File) -> Result<Self, Error> {
+        Self::create(stream, None)
+    }
+
+    /// Reads MariaDB binlog files encrypted with encrypt_binlog option.
+    /// Events following StartEncryptionEvent are decrypted with keys of the provider.
+    pub fn with_key_provider(
+        stream: File,
+        key_provider: Box<dyn KeyProvider>,
+    ) -> Result<Self, Error> {
+        Self::create(stream, Some(key_provider))
+    }
+
+    fn create(mut stream: File, key_provider: Option<Box<dyn KeyProvider>>) -> Result<Self, Error> {
         let mut header = [0; constants::FIRST_EVENT_POSITION];
         stream.read_exact(&mut header)?;
 
@@ -29,6 +50,10 @@ impl BinlogReader {
             stream,
             parser: EventParser::new(),
             payload_buffer: vec![0; constants::PAYLOAD_BUFFER_SIZE],
+            pending: VecDeque::new(),
+            position: constants::FIRST_EVENT_POSITION as u64,
+            key_provider,
+            decryptor: None,
         })
     }
 
@@ -36,10 +61,34 @@ impl BinlogReader {
         self
     }
 
+    /// Registers parser for the event type code. See EventParser::register_parser.
+    pub fn register_parser(&mut self, event_type: u8, parser: CustomParser) -> Result<(), Error> {
+        self.parser.register_parser(event_type, parser)
+    }
+
+    /// Reads the next event of the file.
+    /// TransactionPayloadEvent is returned as is, use `into_events` to get its inner events.
+    /// The iterator returns inner events of compressed transactions one by one.
     pub fn read_event(&mut self) -> Result<(EventHeader, BinlogEvent), Error> {
+        let (header, binlog_event) = self.read_next_event()?;
+        self.position += header.event_length as u64;
+
+        if let BinlogEvent::MariaDbStartEncryptionEvent(event) = &binlog_event {
+            let key_provider = self.key_provider.as_deref().ok_or_else(|| {
+                Error::String("Binlog file is encrypted. Key provider is required".to_string())
+            })?;
+            self.decryptor = Some(BinlogDecryptor::new(event, key_provider)?);
+        }
+        Ok((header, binlog_event))
+    }
+
+    fn read_next_event(&mut self) -> Result<(EventHeader, BinlogEvent), Error> {
         // Parse header
         let mut header_buffer = [0; EVENT_HEADER_SIZE];
         self.stream.read_exact(&mut header_buffer)?;
+        if self.decryptor.is_some() {
+            return self.read_encrypted_event(&header_buffer);
+        }
         let header = EventHeader::parse(&header_buffer)?;
 
         let payload_length = header.event_length as usize - EVENT_HEADER_SIZE;
@@ -57,13 +106,51 @@ impl BinlogReader {
             Ok((header, binlog_event))
         }
     }
+
+    fn read_encrypted_event(
+        &mut self,
+        header_buffer: &[u8; EVENT_HEADER_SIZE],
+    ) -> Result<(EventHeader, BinlogEvent), Error> {
+        // Event length is the only header field that is not encrypted.
+        let event_length =
+            (&header_buffer[EVENT_LENGTH_OFFSET..]).read_u32::<LittleEndian>()? as usize;
+        if event_length < EVENT_HEADER_SIZE {
+            return Err(Error::String("Encrypted event is truncated".to_string()));
+        }
+
+        let mut event = vec![0; event_length];
+        event[..EVENT_HEADER_SIZE].copy_from_slice(header_buffer);
+        self.stream.read_exact(&mut event[EVENT_HEADER_SIZE..])?;
+
+        if let Some(decryptor) = &self.decryptor {
+            decryptor.decrypt(&mut event, self.position as u32)?;
+        }
+
+        let header = EventHeader::parse(&event)?;
+        let binlog_event = self
+            .parser
+            .parse_event(&header, &event[EVENT_HEADER_SIZE..])?;
+        Ok((header, binlog_event))
+    }
 }
 
 impl Iterator for BinlogReader {
     type Item = Result<(EventHeader, BinlogEvent), Error>;
 
+    /// Reads binlog events from the file.
+    /// Events of compressed transactions are returned one by one.
     fn next(&mut self) -> Option<Self::Item> {
-        let result = self.read_event();
+        if let Some(event) = self.pending.pop_front() {
+            return Some(Ok(event));
+        }
+
+        let result = match self.read_event() {
+            Ok((header, BinlogEvent::TransactionPayloadEvent(x))) => {
+                self.pending.extend(x.into_events(&header));
+                return self.next();
+            }
+            result => result,
+        };
         if let Err(error) = &result {
             if let Error::IoError(io_error) = error {
                 if let ErrorKind::UnexpectedEof = io_error.kind() {
@@ -74,3 +161,71 @@ impl Iterator for BinlogReader {
         Some(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::BinlogReader;
+    use crate::constants::{EVENT_HEADER_SIZE, FIRST_EVENT_POSITION};
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::providers::mariadb::encryption::binlog_decryptor::tests::{encrypt_event, KEY_FILE};
+    use crate::providers::mariadb::encryption::file_key_provider::FileKeyProvider;
+    use crate::providers::mariadb::encryption::key_provider::EncryptionAlgorithm;
+    use crate::providers::mariadb::events::start_encryption_event::StartEncryptionEvent;
+    use std::fs::File;
+
+    fn create_event(event_type: u8, position: usize, payload: &[u8]) -> Vec<u8> {
+        let event_length = EVENT_HEADER_SIZE + payload.len();
+        let mut event = vec![1, 0, 0, 0, event_type, 1, 0, 0, 0];
+        event.extend_from_slice(&(event_length as u32).to_le_bytes());
+        event.extend_from_slice(&((position + event_length) as u32).to_le_bytes());
+        event.extend_from_slice(&[0, 0]);
+        event.extend_from_slice(payload);
+        event
+    }
+
+    #[test]
+    fn read_encrypted_events() {
+        let algorithm = EncryptionAlgorithm::AesCbc;
+        let start_event = StartEncryptionEvent {
+            scheme: 1,
+            key_version: 1,
+            nonce: [7; 12],
+        };
+        let mut payload = vec![start_event.scheme];
+        payload.extend_from_slice(&start_event.key_version.to_le_bytes());
+        payload.extend_from_slice(&start_event.nonce);
+
+        let mut binlog = vec![0xfe, 0x62, 0x69, 0x6e];
+        binlog.extend(create_event(164, FIRST_EVENT_POSITION, &payload));
+        let position = binlog.len();
+        let xid_event = create_event(16, position, &42u64.to_le_bytes());
+        binlog.extend(encrypt_event(
+            &xid_event,
+            &start_event,
+            algorithm,
+            position as u32,
+        ));
+
+        let path = std::env::temp_dir().join(format!("mysql_cdc_{}.bin", std::process::id()));
+        std::fs::write(&path, &binlog).unwrap();
+
+        let provider = FileKeyProvider::new(KEY_FILE, algorithm).unwrap();
+        let reader =
+            BinlogReader::with_key_provider(File::open(&path).unwrap(), Box::new(provider));
+        let events = std::thread::spawn(move || reader.unwrap().collect::<Vec<_>>())
+            .join()
+            .unwrap();
+        assert_eq!(2, events.len());
+        match &events[1] {
+            Ok((header, BinlogEvent::XidEvent(x))) => {
+                assert_eq!(42, x.xid);
+                assert_eq!(binlog.len() as u32, header.next_event_position);
+            }
+            x => panic!("Unexpected event {:?}", x),
+        }
+
+        let mut reader = BinlogReader::new(File::open(&path).unwrap()).unwrap();
+        assert!(reader.next().unwrap().is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,35 +1,21 @@
-use crate::{constants::auth_plugin_names::AuthPlugin, extensions::encrypt_password};
 use std::io::{self, Cursor, Write};
 
+/// Response to Authentication Switch Request with auth data of the requested plugin.
+/// <a href="https://mariadb.com/kb/en/library/connection/#authentication-switch-request">See more</a>
 pub struct AuthPluginSwitchCommand {
-    pub password: String,
-    pub scramble: String,
-    pub auth_plugin_name: String,
-    pub auth_plugin: AuthPlugin,
+    pub auth_data: Vec<u8>,
 }
 
 impl AuthPluginSwitchCommand {
-    pub fn new(
-        password: &String,
-        scramble: &String,
-        auth_plugin_name: &String,
-        auth_plugin: AuthPlugin,
-    ) -> Self {
-        Self {
-            password: password.clone(),
-            scramble: scramble.clone(),
-            auth_plugin_name: auth_plugin_name.clone(),
-            auth_plugin: auth_plugin,
-        }
+    pub fn new(auth_data: Vec<u8>) -> Self {
+        Self { auth_data }
     }
 
     pub fn serialize(&self) -> Result<Vec<u8>, io::Error> {
         let mut vec = Vec::new();
         let mut cursor = Cursor::new(&mut vec);
 
-        let encrypted_password =
-            encrypt_password(&self.password, &self.scramble, &self.auth_plugin);
-        cursor.write(&encrypted_password)?;
+        cursor.write_all(&self.auth_data)?;
 
         Ok(vec)
     }
@@ -1,9 +1,9 @@
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::io::{self, Cursor, Write};
 
-use crate::constants::auth_plugin_names::AuthPlugin;
+use crate::compression_mode::CompressionMode;
 use crate::constants::capability_flags;
-use crate::extensions::{encrypt_password, write_null_term_string};
+use crate::extensions::write_null_term_string;
 use crate::replica_options::ReplicaOptions;
 use crate::responses::handshake_packet::HandshakePacket;
 
@@ -14,18 +14,17 @@ pub struct AuthenticateCommand {
     pub max_packet_size: u32,
     pub client_collation: u8,
     pub username: String,
-    pub password: String,
+    pub auth_data: Vec<u8>,
     pub database: Option<String>,
-    pub scramble: String,
-    pub auth_plugin: AuthPlugin,
     pub auth_plugin_name: String,
+    pub compression_level: u8,
 }
 
 impl AuthenticateCommand {
     pub fn new(
         options: &ReplicaOptions,
         handshake: &HandshakePacket,
-        auth_plugin: AuthPlugin,
+        auth_data: Vec<u8>,
         client_collation: u8,
     ) -> Self {
         let mut client_capabilities = capability_flags::LONG_FLAG
@@ -37,6 +36,31 @@ impl AuthenticateCommand {
             client_capabilities |= capability_flags::CONNECT_WITH_DB;
         }
 
+        if handshake.server_capabilities & capability_flags::DEPRECATE_EOF != 0 {
+            client_capabilities |= capability_flags::DEPRECATE_EOF;
+        }
+
+        // RSA encrypted password of sha256_password is longer than 255 bytes
+        if handshake.server_capabilities & capability_flags::PLUGIN_AUTH_LENENC_CLIENT_DATA != 0 {
+            client_capabilities |= capability_flags::PLUGIN_AUTH_LENENC_CLIENT_DATA;
+        }
+
+        let server_capabilities = handshake.server_capabilities;
+        match options.compression {
+            CompressionMode::Zlib if server_capabilities & capability_flags::COMPRESS != 0 => {
+                client_capabilities |= capability_flags::COMPRESS;
+            }
+            CompressionMode::Zstd
+                if server_capabilities & capability_flags::ZSTD_COMPRESSION_ALGORITHM != 0 =>
+            {
+                client_capabilities |= capability_flags::ZSTD_COMPRESSION_ALGORITHM;
+            }
+            _ => {}
+        }
+        let compression_level = options
+            .compression_level
+            .unwrap_or(options.compression.default_level());
+
         let client_capabilities = client_capabilities as u32;
 
         Self {
@@ -44,11 +68,10 @@ impl AuthenticateCommand {
             max_packet_size: 0,
             client_collation,
             username: options.username.clone(),
-            password: options.password.clone(),
+            auth_data,
             database: options.database.clone(),
-            scramble: handshake.scramble.clone(),
             auth_plugin_name: handshake.auth_plugin_name.clone(),
-            auth_plugin: auth_plugin,
+            compression_level: compression_level as u8,
         }
     }
 
@@ -67,16 +90,27 @@ impl AuthenticateCommand {
 
         write_null_term_string(&mut cursor, &self.username)?;
 
-        let encrypted_password =
-            encrypt_password(&self.password, &self.scramble, &self.auth_plugin);
-        cursor.write_u8(encrypted_password.len() as u8)?;
-        cursor.write(&encrypted_password)?;
+        let lenenc_data =
+            self.client_capabilities as u64 & capability_flags::PLUGIN_AUTH_LENENC_CLIENT_DATA != 0;
+        if lenenc_data && self.auth_data.len() > 250 {
+            cursor.write_u8(0xFC)?;
+            cursor.write_u16::<LittleEndian>(self.auth_data.len() as u16)?;
+        } else {
+            cursor.write_u8(self.auth_data.len() as u8)?;
+        }
+        cursor.write_all(&self.auth_data)?;
 
         if let Some(database) = &self.database {
             write_null_term_string(&mut cursor, database)?;
         }
 
         write_null_term_string(&mut cursor, &self.auth_plugin_name)?;
+
+        let zstd_compression =
+            self.client_capabilities as u64 & capability_flags::ZSTD_COMPRESSION_ALGORITHM != 0;
+        if zstd_compression {
+            cursor.write_u8(self.compression_level)?;
+        }
         Ok(vec)
     }
 }
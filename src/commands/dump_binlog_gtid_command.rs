@@ -1,5 +1,5 @@
 use crate::commands::command_type::CommandType;
-use crate::providers::mysql::gtid::gtid_set::GtidSet;
+use crate::providers::mysql::gtid::gtid_set::{GtidSet, TAGGED_FORMAT};
 use byteorder::{LittleEndian, WriteBytesExt};
 use std::io::{self, Cursor, Write};
 
@@ -37,21 +37,45 @@ impl DumpBinlogGtidCommand {
         let position = self.binlog_position as u64;
         cursor.write_u64::<LittleEndian>(position)?;
 
+        // Tagged GTIDs require the format of MySQL 8.3+ where each tag is a separate set
+        let tagged = gtid_set.has_tags();
+        let sets: Vec<_> = gtid_set
+            .uuid_sets
+            .values()
+            .flat_map(|x| {
+                x.intervals_by_tag()
+                    .map(move |(tag, y)| (&x.source_id, tag, y))
+            })
+            .collect();
+
         let mut data_length = 8; /* Number of uuid_sets */
-        for uuid_set in gtid_set.uuid_sets.values() {
+        for (_, tag, intervals) in &sets {
             data_length += 16; /* SourceId */
+            if tagged {
+                data_length += 1 + tag.len(); /* Tag */
+            }
             data_length += 8; /* Number of intervals */
-            data_length += uuid_set.intervals.len() * (8 + 8) /* Start-End */;
+            data_length += intervals.len() * (8 + 8) /* Start-End */;
         }
 
         cursor.write_u32::<LittleEndian>(data_length as u32)?;
-        cursor.write_u64::<LittleEndian>(gtid_set.uuid_sets.len() as u64)?;
+        match tagged {
+            true => cursor.write_u64::<LittleEndian>(
+                TAGGED_FORMAT << 56 | (sets.len() as u64) << 8 | TAGGED_FORMAT,
+            )?,
+            false => cursor.write_u64::<LittleEndian>(sets.len() as u64)?,
+        }
 
-        for uuid_set in gtid_set.uuid_sets.values() {
-            cursor.write(&uuid_set.source_id.data)?;
-            cursor.write_u64::<LittleEndian>(uuid_set.intervals.len() as u64)?;
+        for (source_id, tag, intervals) in sets {
+            cursor.write_all(&source_id.data)?;
+            if tagged {
+                // Length of the tag up to 32 characters as variable-length integer
+                cursor.write_u8((tag.len() << 1) as u8)?;
+                cursor.write_all(tag.as_bytes())?;
+            }
+            cursor.write_u64::<LittleEndian>(intervals.len() as u64)?;
 
-            for interval in &uuid_set.intervals {
+            for interval in intervals {
                 cursor.write_u64::<LittleEndian>(interval.start)?;
                 cursor.write_u64::<LittleEndian>(interval.end + 1)?;
             }
@@ -60,3 +84,26 @@ impl DumpBinlogGtidCommand {
         Ok(vec)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DumpBinlogGtidCommand;
+    use crate::providers::mysql::events::prev_gtids_event::PreviousGtidsEvent;
+    use crate::providers::mysql::gtid::gtid_set::GtidSet;
+    use std::io::Cursor;
+
+    #[test]
+    fn serialize_tagged_gtid_set() {
+        let text = "24bc7850-2c16-11e6-a073-0242ac110001:1-5:tag:3:7-9";
+        let command = DumpBinlogGtidCommand::new(1, "mysql-bin.000001".to_string(), 4);
+        let packet = command.serialize(&GtidSet::parse(text).unwrap()).unwrap();
+
+        // GtidSet follows the filename, position and data length
+        let data = &packet[11 + 16 + 8 + 4..];
+        assert_eq!(8 + (16 + 1 + 8 + 16) + (16 + 4 + 8 + 32), data.len());
+        assert_eq!(&[1, 2, 0, 0, 0, 0, 0, 1], &data[..8]);
+
+        let event = PreviousGtidsEvent::parse(&mut Cursor::new(data)).unwrap();
+        assert_eq!(text, event.gtid_set.to_string());
+    }
+}
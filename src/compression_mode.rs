@@ -0,0 +1,24 @@
+/// Represents compression of the protocol packets.
+/// Falls back to uncompressed connection when the server doesn't support the algorithm.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CompressionMode {
+    /// Packets are not compressed.
+    Disabled,
+
+    /// zlib compression supported by MySQL and MariaDB.
+    Zlib,
+
+    /// zstd compression supported by MySQL 8.0.18 and later.
+    Zstd,
+}
+
+impl CompressionMode {
+    /// Gets compression level used when it's not specified.
+    pub fn default_level(&self) -> u32 {
+        match self {
+            CompressionMode::Disabled => 0,
+            CompressionMode::Zlib => 6,
+            CompressionMode::Zstd => 3,
+        }
+    }
+}
@@ -4,8 +4,6 @@ use crate::constants::checksum_type::ChecksumType;
 use crate::errors::Error;
 use crate::extensions::check_error_packet;
 use crate::packet_channel::PacketChannel;
-use crate::responses::response_type::ResponseType;
-use crate::responses::result_set_row_packet::ResultSetRowPacket;
 use crate::starting_strategy::StartingStrategy;
 
 impl BinlogClient {
@@ -19,18 +17,16 @@ impl BinlogClient {
             return Ok(());
         }
 
-        let command = QueryCommand::new("show master status".to_string());
-        channel.write_packet(&command.serialize()?, 0)?;
-
-        let result_set = self.read_result_set(channel)?;
-        if result_set.len() != 1 {
+        let result_set = channel.query("show master status")?;
+        if result_set.rows.len() != 1 {
             return Err(Error::String(
                 "Could not read master binlog position.".to_string(),
             ));
         }
 
-        self.options.binlog.filename = result_set[0].cells[0].clone();
-        self.options.binlog.position = result_set[0].cells[1].parse()?;
+        let row = &result_set.rows[0];
+        self.options.binlog.filename = row.get_at(0)?.unwrap_or_default();
+        self.options.binlog.position = row.get_at(1)?.unwrap_or_default();
         Ok(())
     }
 
@@ -55,39 +51,14 @@ impl BinlogClient {
         let (packet, _) = channel.read_packet()?;
         check_error_packet(&packet, "Setting master_binlog_checksum error.")?;
 
-        let command = QueryCommand::new("SELECT @master_binlog_checksum".to_string());
-        channel.write_packet(&command.serialize()?, 0)?;
-        let result_set = self.read_result_set(channel)?;
+        let result_set = channel.query("SELECT @master_binlog_checksum")?;
+        let checksum: Option<String> = match result_set.rows.first() {
+            Some(row) => row.get_at(0)?,
+            None => None,
+        };
 
         // When replication is started fake RotateEvent comes before FormatDescriptionEvent.
         // In order to deserialize the event we have to obtain checksum type length in advance.
-        Ok(ChecksumType::from_name(&result_set[0].cells[0])?)
-    }
-
-    fn read_result_set(
-        &self,
-        channel: &mut PacketChannel,
-    ) -> Result<Vec<ResultSetRowPacket>, Error> {
-        let (packet, _) = channel.read_packet()?;
-        check_error_packet(&packet, "Reading result set error.")?;
-
-        loop {
-            // Skip through metadata
-            let (packet, _) = channel.read_packet()?;
-            if packet[0] == ResponseType::END_OF_FILE {
-                break;
-            }
-        }
-
-        let mut result_set = Vec::new();
-        loop {
-            let (packet, _) = channel.read_packet()?;
-            check_error_packet(&packet, "Query result set error.")?;
-            if packet[0] == ResponseType::END_OF_FILE {
-                break;
-            }
-            result_set.push(ResultSetRowPacket::parse(&packet)?);
-        }
-        Ok(result_set)
+        Ok(ChecksumType::from_name(&checksum.unwrap_or_default())?)
     }
 }
@@ -1,39 +1,67 @@
-use openssl::rsa::{Padding, Rsa};
+use ed25519_dalek::{Signer, SigningKey};
+use openssl::hash::MessageDigest;
+use openssl::pkcs5::pbkdf2_hmac;
+use openssl::rand::rand_bytes;
 
 use crate::binlog_client::BinlogClient;
 use crate::commands::auth_plugin_switch_command::AuthPluginSwitchCommand;
 use crate::commands::authenticate_command::AuthenticateCommand;
 use crate::commands::ssl_request_command::SslRequestCommand;
+use crate::compression_mode::CompressionMode;
 use crate::constants::auth_plugin_names::AuthPlugin;
 use crate::constants::database_provider::DatabaseProvider;
 use crate::constants::{auth_plugin_names, capability_flags, NULL_TERMINATOR, UTF8_MB4_GENERAL_CI};
 use crate::errors::Error;
-use crate::extensions::{check_error_packet, xor};
+use crate::extensions::{
+    check_error_packet, ed25519_sign, rsa_encrypt_password, scramble_sha1, scramble_sha256,
+};
 use crate::packet_channel::PacketChannel;
 use crate::responses::auth_switch_packet::AuthPluginSwitchPacket;
 use crate::responses::handshake_packet::HandshakePacket;
 use crate::responses::response_type::ResponseType;
 use crate::ssl_mode::SslMode;
 
+/// Prefix of extra authentication data sent by the server.
+const AUTH_MORE_DATA: u8 = 0x01;
+
 impl BinlogClient {
+    /// Connects to the current server.
     pub fn connect(&self) -> Result<(PacketChannel, DatabaseProvider), Error> {
-        let mut channel = PacketChannel::new(&self.options)?;
+        self.connect_to(self.server)
+    }
+
+    /// Connects to the server with the index in `ReplicaOptions::servers`.
+    pub(crate) fn connect_to(
+        &self,
+        server: usize,
+    ) -> Result<(PacketChannel, DatabaseProvider), Error> {
+        let mut channel = PacketChannel::new(&self.options, server)?;
         let (packet, seq_num) = channel.read_packet()?;
         check_error_packet(&packet, "Initial handshake error.")?;
         let handshake = HandshakePacket::parse(&packet)?;
 
         let auth_plugin = self.get_auth_plugin(&handshake.auth_plugin_name)?;
-        self.authenticate(&mut channel, &handshake, auth_plugin, seq_num + 1)?;
+        let capabilities = self.authenticate(&mut channel, &handshake, auth_plugin, seq_num + 1)?;
+
+        channel.deprecate_eof = capabilities & capability_flags::DEPRECATE_EOF != 0;
+        channel.server_version = handshake.server_version.clone();
+        if capabilities & capability_flags::COMPRESS != 0 {
+            channel.enable_compression(CompressionMode::Zlib, self.compression_level());
+        }
+        if capabilities & capability_flags::ZSTD_COMPRESSION_ALGORITHM != 0 {
+            channel.enable_compression(CompressionMode::Zstd, self.compression_level());
+        }
         Ok((channel, DatabaseProvider::from(&handshake.server_version)))
     }
 
+    /// Returns capabilities of the client that were sent to the server.
     fn authenticate(
         &self,
         channel: &mut PacketChannel,
         handshake: &HandshakePacket,
         auth_plugin: AuthPlugin,
         mut seq_num: u8,
-    ) -> Result<(), Error> {
+    ) -> Result<u64, Error> {
         let mut use_ssl = false;
         if self.options.ssl_mode != SslMode::Disabled {
             let ssl_available = (handshake.server_capabilities & capability_flags::SSL) != 0;
@@ -51,30 +79,30 @@ impl BinlogClient {
             }
         }
 
+        let scramble = handshake.scramble.as_bytes();
+        let auth_data = self.get_auth_data(&auth_plugin, scramble, use_ssl)?;
         let auth_command =
-            AuthenticateCommand::new(&self.options, handshake, auth_plugin, UTF8_MB4_GENERAL_CI);
+            AuthenticateCommand::new(&self.options, handshake, auth_data, UTF8_MB4_GENERAL_CI);
         channel.write_packet(&auth_command.serialize()?, seq_num)?;
         let (packet, seq_num) = channel.read_packet()?;
         check_error_packet(&packet, "Authentication error.")?;
 
         match packet[0] {
-            ResponseType::OK => return Ok(()),
+            ResponseType::OK => {}
             ResponseType::AUTH_PLUGIN_SWITCH => {
                 let switch_packet = AuthPluginSwitchPacket::parse(&packet[1..])?;
                 self.handle_auth_plugin_switch(channel, switch_packet, seq_num + 1, use_ssl)?;
-                Ok(())
-            }
-            _ => {
-                self.authenticate_sha_256(
-                    channel,
-                    &packet,
-                    &handshake.scramble,
-                    seq_num + 1,
-                    use_ssl,
-                )?;
-                Ok(())
             }
+            _ => self.continue_authentication(
+                channel,
+                &auth_plugin,
+                &packet,
+                scramble,
+                seq_num + 1,
+                use_ssl,
+            )?,
         }
+        Ok(auth_command.client_capabilities as u64)
     }
 
     fn handle_auth_plugin_switch(
@@ -85,84 +113,297 @@ impl BinlogClient {
         use_ssl: bool,
     ) -> Result<(), Error> {
         let auth_plugin = self.get_auth_plugin(&switch_packet.auth_plugin_name)?;
-        let auth_switch_command = AuthPluginSwitchCommand::new(
-            &self.options.password,
-            &switch_packet.auth_plugin_data,
-            &switch_packet.auth_plugin_name,
-            auth_plugin,
-        );
+        let data = &switch_packet.auth_plugin_data;
+        let scramble = match auth_plugin {
+            AuthPlugin::Ed25519 | AuthPlugin::Parsec => &data[..],
+            // Scramble of password hashing plugins is null-terminated
+            _ => data.strip_suffix(&[NULL_TERMINATOR]).unwrap_or(data),
+        };
+
+        let auth_data = self.get_auth_data(&auth_plugin, scramble, use_ssl)?;
+        let auth_switch_command = AuthPluginSwitchCommand::new(auth_data);
         channel.write_packet(&auth_switch_command.serialize()?, seq_num)?;
         let (packet, seq_num) = channel.read_packet()?;
         check_error_packet(&packet, "Authentication switch error.")?;
 
-        if switch_packet.auth_plugin_name == auth_plugin_names::CACHING_SHA2_PASSWORD {
-            self.authenticate_sha_256(
-                channel,
-                &packet,
-                &switch_packet.auth_plugin_data,
-                seq_num + 1,
-                use_ssl,
-            )?;
+        self.continue_authentication(
+            channel,
+            &auth_plugin,
+            &packet,
+            scramble,
+            seq_num + 1,
+            use_ssl,
+        )
+    }
+
+    /// Returns the first response of the auth plugin to the scramble.
+    fn get_auth_data(
+        &self,
+        auth_plugin: &AuthPlugin,
+        scramble: &[u8],
+        use_ssl: bool,
+    ) -> Result<Vec<u8>, Error> {
+        let password = &self.options.password;
+        match auth_plugin {
+            AuthPlugin::MySqlNativePassword => Ok(scramble_sha1(password, scramble)),
+            AuthPlugin::CachingSha2Password => Ok(scramble_sha256(password, scramble)),
+            AuthPlugin::Sha256Password => {
+                if password.is_empty() {
+                    return Ok(vec![NULL_TERMINATOR]);
+                }
+                if use_ssl {
+                    return Ok([password.as_bytes(), &[NULL_TERMINATOR]].concat());
+                }
+                match self.read_public_key()? {
+                    Some(public_key) => rsa_encrypt_password(password, scramble, &public_key),
+                    // Request public key
+                    None => Ok(vec![AUTH_MORE_DATA]),
+                }
+            }
+            AuthPlugin::MySqlClearPassword => {
+                Ok([password.as_bytes(), &[NULL_TERMINATOR]].concat())
+            }
+            AuthPlugin::Ed25519 => Ok(ed25519_sign(password, scramble)),
+            // Empty response requests salt of the key derivation
+            AuthPlugin::Parsec => Ok(Vec::new()),
+        }
+    }
+
+    /// Handles the packets that plugins exchange after the first response.
+    fn continue_authentication(
+        &self,
+        channel: &mut PacketChannel,
+        auth_plugin: &AuthPlugin,
+        packet: &[u8],
+        scramble: &[u8],
+        seq_num: u8,
+        use_ssl: bool,
+    ) -> Result<(), Error> {
+        if packet[0] == ResponseType::OK {
+            return Ok(());
+        }
+        match auth_plugin {
+            AuthPlugin::CachingSha2Password => {
+                self.authenticate_sha_256(channel, packet, scramble, seq_num, use_ssl)
+            }
+            AuthPlugin::Sha256Password => {
+                let public_key = strip_auth_more_data(packet);
+                let encrypted_password =
+                    rsa_encrypt_password(&self.options.password, scramble, public_key)?;
+                channel.write_packet(&encrypted_password, seq_num)?;
+                let (packet, _seq_num) = channel.read_packet()?;
+                check_error_packet(&packet, "Authentication error.")?;
+                Ok(())
+            }
+            AuthPlugin::Parsec => self.authenticate_parsec(channel, packet, scramble, seq_num),
+            _ => Err(Error::String(
+                "Unexpected authentication response.".to_string(),
+            )),
         }
-        Ok(())
     }
 
     fn authenticate_sha_256(
         &self,
         channel: &mut PacketChannel,
         packet: &[u8],
-        scramble: &String,
+        scramble: &[u8],
         seq_num: u8,
         use_ssl: bool,
     ) -> Result<(), Error> {
         // See https://mariadb.com/kb/en/caching_sha2_password-authentication-plugin/
-        // Success authentication.
-        if packet[0] == 0x01 && packet[1] == 0x03 {
+        // Success authentication is followed by OK packet.
+        if packet[0] == AUTH_MORE_DATA && packet.get(1) == Some(&0x03) {
+            let (packet, _seq_num) = channel.read_packet()?;
+            check_error_packet(&packet, "Authentication error.")?;
             return Ok(());
         }
 
-        let mut password = self.options.password.as_bytes().to_vec();
-        password.push(NULL_TERMINATOR);
-
         // Send clear password if ssl is used.
         if use_ssl {
+            let mut password = self.options.password.as_bytes().to_vec();
+            password.push(NULL_TERMINATOR);
             channel.write_packet(&password, seq_num)?;
             let (packet, _seq_num) = channel.read_packet()?;
             check_error_packet(&packet, "Sending clear password error.")?;
             return Ok(());
         }
 
-        // Request public key.
-        channel.write_packet(&[0x02], seq_num)?;
-        let (packet, seq_num) = channel.read_packet()?;
-        check_error_packet(&packet, "Requesting caching_sha2_password public key.")?;
-
-        // Extract public key.
-        let public_key = &packet[1..];
-        let encrypted_password = xor(&password, &scramble.as_bytes());
-
-        let rsa = Rsa::public_key_from_pem(public_key)?;
-        let mut encrypted_body = vec![0u8; rsa.size() as usize];
-        rsa.public_encrypt(
-            &encrypted_password,
-            &mut encrypted_body,
-            Padding::PKCS1_OAEP,
+        let (public_key, seq_num) = match self.read_public_key()? {
+            Some(public_key) => (public_key, seq_num),
+            None => {
+                // Request public key.
+                channel.write_packet(&[0x02], seq_num)?;
+                let (packet, seq_num) = channel.read_packet()?;
+                check_error_packet(&packet, "Requesting caching_sha2_password public key.")?;
+                (strip_auth_more_data(&packet).to_vec(), seq_num + 1)
+            }
+        };
+
+        let encrypted_body = rsa_encrypt_password(&self.options.password, scramble, &public_key)?;
+        channel.write_packet(&encrypted_body, seq_num)?;
+        let (packet, _seq_num) = channel.read_packet()?;
+        check_error_packet(&packet, "Authentication error.")?;
+        Ok(())
+    }
+
+    /// Signs server and client scrambles with Ed25519 key derived from the password.
+    /// See https://mariadb.com/kb/en/authentication-plugin-parsec/
+    fn authenticate_parsec(
+        &self,
+        channel: &mut PacketChannel,
+        packet: &[u8],
+        scramble: &[u8],
+        seq_num: u8,
+    ) -> Result<(), Error> {
+        // Algorithm 'P' is PBKDF2 with SHA-512, next byte defines number of iterations
+        let salt = strip_auth_more_data(packet);
+        if salt.len() < 2 || salt[0] != b'P' || salt[1] > 3 {
+            return Err(Error::String(
+                "Unsupported parsec key derivation parameters.".to_string(),
+            ));
+        }
+        let iterations = 1024 << salt[1];
+
+        let mut key = [0u8; 32];
+        pbkdf2_hmac(
+            self.options.password.as_bytes(),
+            &salt[2..],
+            iterations,
+            MessageDigest::sha512(),
+            &mut key,
         )?;
 
-        channel.write_packet(&encrypted_body, seq_num + 1)?;
+        let mut client_scramble = [0u8; 32];
+        rand_bytes(&mut client_scramble)?;
+        let message = [scramble, &client_scramble].concat();
+        let signature = SigningKey::from_bytes(&key).sign(&message);
+
+        let response = [&client_scramble[..], &signature.to_bytes()].concat();
+        channel.write_packet(&response, seq_num)?;
         let (packet, _seq_num) = channel.read_packet()?;
         check_error_packet(&packet, "Authentication error.")?;
         Ok(())
     }
 
+    fn compression_level(&self) -> u32 {
+        let options = &self.options;
+        options
+            .compression_level
+            .unwrap_or(options.compression.default_level())
+    }
+
+    fn read_public_key(&self) -> Result<Option<Vec<u8>>, Error> {
+        match &self.options.server_public_key_path {
+            Some(path) => Ok(Some(std::fs::read(path)?)),
+            None => Ok(None),
+        }
+    }
+
     fn get_auth_plugin(&self, auth_plugin_name: &String) -> Result<AuthPlugin, Error> {
-        if auth_plugin_name == auth_plugin_names::MY_SQL_NATIVE_PASSWORD {
-            return Ok(AuthPlugin::MySqlNativePassword);
+        match auth_plugin_name.as_str() {
+            auth_plugin_names::MY_SQL_NATIVE_PASSWORD => Ok(AuthPlugin::MySqlNativePassword),
+            auth_plugin_names::CACHING_SHA2_PASSWORD => Ok(AuthPlugin::CachingSha2Password),
+            auth_plugin_names::SHA256_PASSWORD => Ok(AuthPlugin::Sha256Password),
+            auth_plugin_names::MY_SQL_CLEAR_PASSWORD if self.options.enable_cleartext_plugin => {
+                Ok(AuthPlugin::MySqlClearPassword)
+            }
+            auth_plugin_names::MY_SQL_CLEAR_PASSWORD => Err(Error::String(
+                "mysql_clear_password auth plugin is disabled. Set enable_cleartext_plugin."
+                    .to_string(),
+            )),
+            auth_plugin_names::CLIENT_ED25519 => Ok(AuthPlugin::Ed25519),
+            auth_plugin_names::PARSEC => Ok(AuthPlugin::Parsec),
+            _ => {
+                let message = format!("{} auth plugin is not supported.", auth_plugin_name);
+                Err(Error::String(message.to_string()))
+            }
         }
-        if auth_plugin_name == auth_plugin_names::CACHING_SHA2_PASSWORD {
-            return Ok(AuthPlugin::CachingSha2Password);
+    }
+}
+
+/// Extra authentication data can be prefixed with 0x01.
+fn strip_auth_more_data(packet: &[u8]) -> &[u8] {
+    match packet.first() {
+        Some(&AUTH_MORE_DATA) => &packet[1..],
+        _ => packet,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::errors::Error;
+    use crate::replica_options::ReplicaOptions;
+    use crate::test_server::{rsa_key, ServerConfig, TestServer};
+    use ed25519_dalek::hazmat::ExpandedSecretKey;
+    use ed25519_dalek::VerifyingKey;
+    use sha2::{Digest, Sha512};
+
+    fn connect(
+        auth_plugin: &'static str,
+        password: &str,
+        configure: impl FnOnce(&mut ReplicaOptions),
+    ) -> Result<(), Error> {
+        let config = ServerConfig {
+            auth_plugin: Some(auth_plugin),
+            password: "Qwertyu1",
+            ..Default::default()
+        };
+        let server = TestServer::start_with(config, |_| unreachable!());
+        let mut options = ReplicaOptions {
+            hostname: "127.0.0.1".to_string(),
+            port: server.port,
+            password: password.to_string(),
+            ..Default::default()
+        };
+        configure(&mut options);
+
+        let result = BinlogClient::new(options).connect().map(|_| ());
+        server.join();
+        result
+    }
+
+    #[test]
+    fn authenticate_with_sha256_password() {
+        connect("sha256_password", "Qwertyu1", |_| {}).unwrap();
+        assert!(connect("sha256_password", "wrong", |_| {}).is_err());
+
+        let path = std::env::temp_dir().join("mysql_cdc_server_public_key.pem");
+        std::fs::write(&path, rsa_key().public_key_to_pem().unwrap()).unwrap();
+        connect("sha256_password", "Qwertyu1", |options| {
+            options.server_public_key_path = Some(path.clone())
+        })
+        .unwrap();
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn authenticate_with_clear_password() {
+        match connect("mysql_clear_password", "Qwertyu1", |_| {}) {
+            Err(Error::String(x)) => assert!(x.contains("enable_cleartext_plugin")),
+            _ => panic!("Disabled plugin error expected"),
         }
-        let message = format!("{} auth plugin is not supported.", auth_plugin_name);
-        Err(Error::String(message.to_string()))
+        connect("mysql_clear_password", "Qwertyu1", |options| {
+            options.enable_cleartext_plugin = true
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn authenticate_with_mariadb_plugins() {
+        connect("client_ed25519", "Qwertyu1", |_| {}).unwrap();
+        assert!(connect("client_ed25519", "wrong", |_| {}).is_err());
+        connect("parsec", "Qwertyu1", |_| {}).unwrap();
+        assert!(connect("parsec", "wrong", |_| {}).is_err());
+    }
+
+    #[test]
+    fn derive_mariadb_ed25519_public_key() {
+        // SELECT ed25519_password("secret") in MariaDB
+        let secret_key = ExpandedSecretKey::from_bytes(&Sha512::digest("secret").into());
+        assert_eq!(
+            "648814444520e4f56043a2ec9215e63be7992d2d270bc6dee873e36164786096",
+            hex::encode(VerifyingKey::from(&secret_key).as_bytes())
+        );
     }
 }
@@ -4,7 +4,23 @@ pub const MY_SQL_NATIVE_PASSWORD: &str = "mysql_native_password";
 /// Used by default in MySQL Server 8.0.
 pub const CACHING_SHA2_PASSWORD: &str = "caching_sha2_password";
 
+/// SHA-256 authentication of MySQL without caching. Deprecated in MySQL 8.0.
+pub const SHA256_PASSWORD: &str = "sha256_password";
+
+/// Sends the password as is. Used by LDAP and PAM authentication.
+pub const MY_SQL_CLEAR_PASSWORD: &str = "mysql_clear_password";
+
+/// Client side of MariaDB ed25519 plugin.
+pub const CLIENT_ED25519: &str = "client_ed25519";
+
+/// Password authentication with salted key derivation. Available since MariaDB 11.6.
+pub const PARSEC: &str = "parsec";
+
 pub enum AuthPlugin {
     MySqlNativePassword,
     CachingSha2Password,
+    Sha256Password,
+    MySqlClearPassword,
+    Ed25519,
+    Parsec,
 }
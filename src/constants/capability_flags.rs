@@ -21,3 +21,6 @@ pub const MULTI_STATEMENTS: u64 = 1 << 16;
 pub const MULTI_RESULTS: u64 = 1 << 17;
 pub const PS_MULTI_RESULTS: u64 = 1 << 18;
 pub const PLUGIN_AUTH: u64 = 1 << 19;
+pub const PLUGIN_AUTH_LENENC_CLIENT_DATA: u64 = 1 << 21;
+pub const DEPRECATE_EOF: u64 = 1 << 24;
+pub const ZSTD_COMPRESSION_ALGORITHM: u64 = 1 << 26;
@@ -2,6 +2,7 @@ use crate::errors::Error;
 
 /// Checksum type used in a binlog file.
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ChecksumType {
     /// Checksum is disabled.
     None = 0,
@@ -14,6 +14,7 @@ pub const UTF8_MB4_GENERAL_CI: u8 = 45;
 
 ///Event Constants
 pub const EVENT_HEADER_SIZE: usize = 19;
+pub const EVENT_LENGTH_OFFSET: usize = 9;
 pub const PAYLOAD_BUFFER_SIZE: usize = 32 * 1024;
 pub const FIRST_EVENT_POSITION: usize = 4;
 
@@ -0,0 +1,588 @@
+use crate::debezium::column_converter::ColumnConverter;
+use crate::debezium::debezium_options::{DebeziumOptions, DecimalHandlingMode};
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::event_header::EventHeader;
+use crate::events::row_events::row_data::RowData;
+use crate::events::table_map_event::TableMapEvent;
+use serde_json::{json, Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Type of the change.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Operation {
+    Create,
+    Update,
+    Delete,
+    /// Row read during snapshot.
+    Read,
+}
+
+impl Operation {
+    /// Returns value of the op field.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Operation::Create => "c",
+            Operation::Update => "u",
+            Operation::Delete => "d",
+            Operation::Read => "r",
+        }
+    }
+}
+
+/// Position of the change written to the source block.
+#[derive(Clone, Debug)]
+pub struct SourceInfo {
+    /// Gets id of the server that created the event.
+    pub server_id: u32,
+
+    /// Gets creation time of the event in milliseconds from Unix.
+    pub ts_ms: u64,
+
+    /// Gets binlog file name of the event.
+    pub file: String,
+
+    /// Gets binlog position of the event.
+    pub pos: u64,
+
+    /// Gets GTID of the transaction.
+    pub gtid: Option<String>,
+
+    /// Gets whether the row was read during snapshot.
+    pub snapshot: bool,
+}
+
+impl SourceInfo {
+    /// Creates source info of the event read from the binlog file.
+    pub fn from_header(header: &EventHeader, file: &str, gtid: Option<String>) -> Self {
+        Self {
+            server_id: header.server_id,
+            ts_ms: header.timestamp as u64 * 1000,
+            file: file.to_string(),
+            pos: header
+                .next_event_position
+                .saturating_sub(header.event_length) as u64,
+            gtid,
+            snapshot: false,
+        }
+    }
+}
+
+/// Kafka message of the change event.
+#[derive(Clone, Debug)]
+pub struct ChangeEvent {
+    /// Gets topic name as server_name.database.table.
+    pub topic: String,
+
+    /// Gets primary key of the row. None if the table doesn't have primary key.
+    pub key: Option<Value>,
+
+    /// Gets change event envelope. None for tombstone events.
+    pub value: Option<Value>,
+}
+
+impl ChangeEvent {
+    /// Returns key serialized as JSON like JsonConverter.
+    pub fn key_json(&self) -> Option<String> {
+        self.key.as_ref().map(|x| x.to_string())
+    }
+
+    /// Returns value serialized as JSON like JsonConverter.
+    pub fn value_json(&self) -> Option<String> {
+        self.value.as_ref().map(|x| x.to_string())
+    }
+}
+
+/// Encodes row events to change events of the Debezium MySQL connector.
+/// <a href="https://debezium.io/documentation/reference/stable/connectors/mysql.html#mysql-events">See more</a>
+pub struct DebeziumEncoder {
+    pub options: DebeziumOptions,
+}
+
+impl DebeziumEncoder {
+    pub fn new(options: DebeziumOptions) -> Self {
+        Self { options }
+    }
+
+    /// Encodes rows of the row event. Returns empty list for other events.
+    /// The table must be the TableMapEvent preceding the row event.
+    /// Update of primary key is encoded as delete, tombstone and create events.
+    pub fn encode(
+        &self,
+        event: &BinlogEvent,
+        table: &TableMapEvent,
+        source: &SourceInfo,
+    ) -> Result<Vec<ChangeEvent>, Error> {
+        let table_id = match event {
+            BinlogEvent::WriteRowsEvent(x) => x.table_id,
+            BinlogEvent::UpdateRowsEvent(x) => x.table_id,
+            BinlogEvent::DeleteRowsEvent(x) => x.table_id,
+            _ => return Ok(Vec::new()),
+        };
+        if table_id != table.table_id {
+            return Err(Error::String(format!(
+                "Row event of table id {} doesn't match TableMapEvent of table id {}",
+                table_id, table.table_id
+            )));
+        }
+
+        let converters = TableConverters::new(table, self.options.decimal_handling_mode)?;
+        let encode_row = |operation, before, after, row| {
+            self.encode_change(operation, before, after, row, &converters, source)
+        };
+
+        let mut result = Vec::new();
+        match event {
+            BinlogEvent::WriteRowsEvent(x) => {
+                for (i, row) in x.rows.iter().enumerate() {
+                    result.push(encode_row(Operation::Create, None, Some(row), i)?);
+                }
+            }
+            BinlogEvent::UpdateRowsEvent(x) => {
+                for (i, row) in x.rows.iter().enumerate() {
+                    let (before, after) = (&row.before_update, &row.after_update);
+                    // Change of primary key is sent as delete of the old key and create of the new key
+                    if converters.key_changed(before, after) {
+                        let event = encode_row(Operation::Delete, Some(before), None, i)?;
+                        self.push_delete(&mut result, event);
+                        result.push(encode_row(Operation::Create, None, Some(after), i)?);
+                    } else {
+                        result.push(encode_row(Operation::Update, Some(before), Some(after), i)?);
+                    }
+                }
+            }
+            BinlogEvent::DeleteRowsEvent(x) => {
+                for (i, row) in x.rows.iter().enumerate() {
+                    let event = encode_row(Operation::Delete, Some(row), None, i)?;
+                    self.push_delete(&mut result, event);
+                }
+            }
+            _ => {}
+        }
+        Ok(result)
+    }
+
+    /// Encodes a single row change. Row is the index of the row in the row event.
+    pub fn encode_row(
+        &self,
+        operation: Operation,
+        before: Option<&RowData>,
+        after: Option<&RowData>,
+        row: usize,
+        table: &TableMapEvent,
+        source: &SourceInfo,
+    ) -> Result<ChangeEvent, Error> {
+        let converters = TableConverters::new(table, self.options.decimal_handling_mode)?;
+        self.encode_change(operation, before, after, row, &converters, source)
+    }
+
+    fn encode_change(
+        &self,
+        operation: Operation,
+        before: Option<&RowData>,
+        after: Option<&RowData>,
+        row: usize,
+        converters: &TableConverters,
+        source: &SourceInfo,
+    ) -> Result<ChangeEvent, Error> {
+        let (table, columns) = (converters.table, &converters.columns);
+        let row_value = |data: Option<&RowData>| -> Result<Value, Error> {
+            match data {
+                Some(data) => Ok(Value::Object(self.row_payload(columns, data, None)?)),
+                None => Ok(Value::Null),
+            }
+        };
+
+        let payload = json!({
+            "before": row_value(before)?,
+            "after": row_value(after)?,
+            "source": self.source_payload(table, source, row),
+            "op": operation.code(),
+            "ts_ms": current_millis(),
+            "transaction": null
+        });
+
+        let key_columns = &converters.key_columns;
+        let key = match (key_columns.is_empty(), after.or(before)) {
+            (false, Some(data)) => {
+                let payload = Value::Object(self.row_payload(columns, data, Some(key_columns))?);
+                Some(self.with_schema(self.key_schema(table, columns, key_columns), payload))
+            }
+            _ => None,
+        };
+
+        Ok(ChangeEvent {
+            topic: self.topic(table),
+            key,
+            value: Some(self.with_schema(self.value_schema(table, columns), payload)),
+        })
+    }
+
+    /// Adds delete event followed by tombstone event if enabled.
+    fn push_delete(&self, result: &mut Vec<ChangeEvent>, event: ChangeEvent) {
+        let tombstone = ChangeEvent {
+            topic: event.topic.clone(),
+            key: event.key.clone(),
+            value: None,
+        };
+        result.push(event);
+        if self.options.tombstones_on_delete {
+            result.push(tombstone);
+        }
+    }
+
+    fn row_payload(
+        &self,
+        columns: &[ColumnConverter],
+        data: &RowData,
+        key_columns: Option<&Vec<usize>>,
+    ) -> Result<Map<String, Value>, Error> {
+        let mut result = Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            if key_columns.is_none_or(|x| x.contains(&i)) {
+                let value = column.value(data.cells.get(i).and_then(|x| x.as_ref()))?;
+                result.insert(column.name(), value);
+            }
+        }
+        Ok(result)
+    }
+
+    fn source_payload(&self, table: &TableMapEvent, source: &SourceInfo, row: usize) -> Value {
+        json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "connector": "mysql",
+            "name": self.options.server_name,
+            "ts_ms": source.ts_ms,
+            "snapshot": source.snapshot.to_string(),
+            "db": table.database_name,
+            "sequence": null,
+            "table": table.table_name,
+            "server_id": source.server_id,
+            "gtid": source.gtid,
+            "file": source.file,
+            "pos": source.pos,
+            "row": row,
+            "thread": null,
+            "query": null
+        })
+    }
+
+    fn with_schema(&self, schema: Value, payload: Value) -> Value {
+        match self.options.include_schema {
+            true => json!({ "schema": schema, "payload": payload }),
+            false => payload,
+        }
+    }
+
+    fn topic(&self, table: &TableMapEvent) -> String {
+        format!(
+            "{}.{}.{}",
+            self.options.server_name, table.database_name, table.table_name
+        )
+    }
+
+    fn key_schema(
+        &self,
+        table: &TableMapEvent,
+        columns: &[ColumnConverter],
+        key_columns: &[usize],
+    ) -> Value {
+        let fields = key_columns
+            .iter()
+            .filter_map(|x| columns.get(*x))
+            .map(|x| x.schema())
+            .collect::<Vec<Value>>();
+        json!({
+            "type": "struct",
+            "fields": fields,
+            "optional": false,
+            "name": format!("{}.Key", self.topic(table))
+        })
+    }
+
+    fn value_schema(&self, table: &TableMapEvent, columns: &[ColumnConverter]) -> Value {
+        let topic = self.topic(table);
+        let fields = columns.iter().map(|x| x.schema()).collect::<Vec<Value>>();
+        let row_schema = |field: &str| {
+            json!({
+                "type": "struct",
+                "fields": fields,
+                "optional": true,
+                "name": format!("{}.Value", topic),
+                "field": field
+            })
+        };
+
+        json!({
+            "type": "struct",
+            "fields": [
+                row_schema("before"),
+                row_schema("after"),
+                source_schema(),
+                { "type": "string", "optional": false, "field": "op" },
+                { "type": "int64", "optional": true, "field": "ts_ms" },
+                transaction_schema()
+            ],
+            "optional": false,
+            "name": format!("{}.Envelope", topic),
+            "version": 1
+        })
+    }
+}
+
+/// Converters of the table columns created once for all rows of the event.
+struct TableConverters<'a> {
+    table: &'a TableMapEvent,
+    columns: Vec<ColumnConverter<'a>>,
+    key_columns: Vec<usize>,
+}
+
+impl<'a> TableConverters<'a> {
+    fn new(
+        table: &'a TableMapEvent,
+        decimal_handling_mode: DecimalHandlingMode,
+    ) -> Result<Self, Error> {
+        let columns = table
+            .column_ordinals()
+            .iter()
+            .enumerate()
+            .map(|(i, ordinals)| ColumnConverter::new(table, i, ordinals, decimal_handling_mode))
+            .collect::<Result<Vec<ColumnConverter>, Error>>()?;
+        Ok(Self {
+            table,
+            columns,
+            key_columns: primary_key(table),
+        })
+    }
+
+    /// Returns true if the update changes primary key of the row.
+    fn key_changed(&self, before: &RowData, after: &RowData) -> bool {
+        self.key_columns
+            .iter()
+            .any(|x| before.cells.get(*x) != after.cells.get(*x))
+    }
+}
+
+/// Returns indexes of primary key columns. Requires binlog_row_metadata=FULL.
+fn primary_key(table: &TableMapEvent) -> Vec<usize> {
+    let metadata = match &table.table_metadata {
+        Some(x) => x,
+        None => return Vec::new(),
+    };
+    if let Some(keys) = &metadata.simple_primary_keys {
+        return keys.iter().map(|x| *x as usize).collect();
+    }
+    if let Some(keys) = &metadata.primary_keys_with_prefix {
+        return keys.iter().map(|(x, _)| *x as usize).collect();
+    }
+    Vec::new()
+}
+
+fn source_schema() -> Value {
+    json!({
+        "type": "struct",
+        "fields": [
+            { "type": "string", "optional": false, "field": "version" },
+            { "type": "string", "optional": false, "field": "connector" },
+            { "type": "string", "optional": false, "field": "name" },
+            { "type": "int64", "optional": false, "field": "ts_ms" },
+            {
+                "type": "string",
+                "optional": true,
+                "name": "io.debezium.data.Enum",
+                "version": 1,
+                "parameters": { "allowed": "true,last,false,incremental" },
+                "default": "false",
+                "field": "snapshot"
+            },
+            { "type": "string", "optional": false, "field": "db" },
+            { "type": "string", "optional": true, "field": "sequence" },
+            { "type": "string", "optional": true, "field": "table" },
+            { "type": "int64", "optional": false, "field": "server_id" },
+            { "type": "string", "optional": true, "field": "gtid" },
+            { "type": "string", "optional": false, "field": "file" },
+            { "type": "int64", "optional": false, "field": "pos" },
+            { "type": "int32", "optional": false, "field": "row" },
+            { "type": "int64", "optional": true, "field": "thread" },
+            { "type": "string", "optional": true, "field": "query" }
+        ],
+        "optional": false,
+        "name": "io.debezium.connector.mysql.Source",
+        "field": "source"
+    })
+}
+
+fn transaction_schema() -> Value {
+    json!({
+        "type": "struct",
+        "fields": [
+            { "type": "string", "optional": false, "field": "id" },
+            { "type": "int64", "optional": false, "field": "total_order" },
+            { "type": "int64", "optional": false, "field": "data_collection_order" }
+        ],
+        "optional": true,
+        "name": "event.block",
+        "version": 1,
+        "field": "transaction"
+    })
+}
+
+fn current_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|x| x.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DebeziumEncoder, SourceInfo};
+    use crate::debezium::debezium_options::{DebeziumOptions, DecimalHandlingMode};
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::row_events::delete_rows_event::DeleteRowsEvent;
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::events::row_events::row_data::{RowData, UpdateRowData};
+    use crate::events::row_events::update_rows_event::UpdateRowsEvent;
+    use crate::events::table_map_event::TableMapEvent;
+    use crate::metadata::default_charset::DefaultCharset;
+    use crate::test_fixtures::{self, ID, NAME, PRICE, TABLE_ID};
+    use serde_json::json;
+
+    fn create_table() -> TableMapEvent {
+        let mut table = test_fixtures::create_table(&[ID, NAME, PRICE, ("data", 252, 2)]);
+        let metadata = table.table_metadata.as_mut().unwrap();
+        // Second character column is binary
+        metadata.default_charset = Some(DefaultCharset::new(255, vec![(1, 63)]));
+        metadata.simple_primary_keys = Some(vec![0]);
+        table
+    }
+
+    fn create_row(name: &str) -> RowData {
+        let mut row = test_fixtures::create_row(1, name);
+        row.cells.push(Some(MySqlValue::Blob(vec![1, 2, 3])));
+        row
+    }
+
+    fn create_source() -> SourceInfo {
+        SourceInfo {
+            server_id: 1,
+            ts_ms: 1000,
+            file: "mysql-bin.000003".to_string(),
+            pos: 154,
+            gtid: None,
+            snapshot: false,
+        }
+    }
+
+    #[test]
+    fn encode_update_event() {
+        let event = BinlogEvent::UpdateRowsEvent(UpdateRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 4,
+            columns_before_update: vec![true; 4],
+            columns_after_update: vec![true; 4],
+            rows: vec![UpdateRowData::new(create_row("old"), create_row("new"))],
+        });
+
+        let mut options = DebeziumOptions::new("dbserver1");
+        options.include_schema = false;
+        let encoder = DebeziumEncoder::new(options);
+        let events = encoder
+            .encode(&event, &create_table(), &create_source())
+            .unwrap();
+
+        assert_eq!(1, events.len());
+        assert_eq!("dbserver1.shop.orders", events[0].topic);
+        assert_eq!(Some(json!({ "id": 1 })), events[0].key);
+
+        let value = events[0].value.as_ref().unwrap();
+        assert_eq!("u", value["op"]);
+        assert_eq!(
+            json!({ "id": 1, "name": "new", "price": "z8c=", "data": "AQID" }),
+            value["after"]
+        );
+        assert_eq!("old", value["before"]["name"]);
+        assert_eq!("mysql-bin.000003", value["source"]["file"]);
+        assert_eq!(154, value["source"]["pos"]);
+        assert_eq!("orders", value["source"]["table"]);
+        assert_eq!(0, value["source"]["row"]);
+    }
+
+    #[test]
+    fn encode_delete_event_with_schema_and_tombstone() {
+        let event = BinlogEvent::DeleteRowsEvent(DeleteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 4,
+            columns_present: vec![true; 4],
+            rows: vec![create_row("name")],
+        });
+
+        let encoder = DebeziumEncoder::new(DebeziumOptions::new("dbserver1"));
+        let events = encoder
+            .encode(&event, &create_table(), &create_source())
+            .unwrap();
+
+        assert_eq!(2, events.len());
+        assert!(events[1].value.is_none());
+
+        let value = events[0].value.as_ref().unwrap();
+        assert_eq!("d", value["payload"]["op"]);
+        assert!(value["payload"]["after"].is_null());
+
+        let schema = &value["schema"];
+        assert_eq!("dbserver1.shop.orders.Envelope", schema["name"]);
+        let fields = &schema["fields"][0]["fields"];
+        assert_eq!(
+            json!({ "type": "int32", "optional": false, "field": "id" }),
+            fields[0]
+        );
+        assert_eq!("string", fields[1]["type"]);
+        assert_eq!("org.apache.kafka.connect.data.Decimal", fields[2]["name"]);
+        assert_eq!("2", fields[2]["parameters"]["scale"]);
+        assert_eq!("10", fields[2]["parameters"]["connect.decimal.precision"]);
+        assert_eq!("bytes", fields[3]["type"]);
+
+        let key = events[0].key.as_ref().unwrap();
+        assert_eq!("dbserver1.shop.orders.Key", key["schema"]["name"]);
+        assert_eq!(json!({ "id": 1 }), key["payload"]);
+    }
+
+    #[test]
+    fn encode_primary_key_update_as_delete_and_create() {
+        let mut after = create_row("name");
+        after.cells[0] = Some(MySqlValue::Int(2));
+        let event = BinlogEvent::UpdateRowsEvent(UpdateRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 4,
+            columns_before_update: vec![true; 4],
+            columns_after_update: vec![true; 4],
+            rows: vec![UpdateRowData::new(create_row("name"), after)],
+        });
+
+        let mut options = DebeziumOptions::new("dbserver1");
+        options.include_schema = false;
+        let encoder = DebeziumEncoder::new(options);
+        let events = encoder
+            .encode(&event, &create_table(), &create_source())
+            .unwrap();
+
+        assert_eq!(3, events.len());
+        assert_eq!(Some(json!({ "id": 1 })), events[0].key);
+        let value = events[0].value.as_ref().unwrap();
+        assert_eq!("d", value["op"]);
+        assert_eq!(1, value["before"]["id"]);
+        assert!(value["after"].is_null());
+
+        assert_eq!(Some(json!({ "id": 1 })), events[1].key);
+        assert!(events[1].value.is_none());
+
+        assert_eq!(Some(json!({ "id": 2 })), events[2].key);
+        let value = events[2].value.as_ref().unwrap();
+        assert_eq!("c", value["op"]);
+        assert!(value["before"].is_null());
+        assert_eq!(2, value["after"]["id"]);
+    }
+}
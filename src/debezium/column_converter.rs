@@ -0,0 +1,358 @@
+use crate::constants::column_type::ColumnType;
+use crate::debezium::debezium_options::DecimalHandlingMode;
+use crate::errors::Error;
+use crate::events::row_events::from_value::FromMySqlValue;
+use crate::events::row_events::json_binary::decode_json;
+use crate::events::row_events::mysql_value::{Date, DateTime, Decimal, MySqlValue};
+use crate::events::table_map_event::{enum_string, set_string, ColumnOrdinals, TableMapEvent};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+
+/// Converts columns to Kafka Connect types the same way as Debezium MySQL connector.
+/// <a href="https://debezium.io/documentation/reference/stable/connectors/mysql.html#mysql-data-types">See more</a>
+pub struct ColumnConverter<'a> {
+    table: &'a TableMapEvent,
+    index: usize,
+    column_type: ColumnType,
+    metadata: u16,
+    decimal_handling_mode: DecimalHandlingMode,
+
+    // Looked up once because each lookup scans preceding columns
+    unsigned: bool,
+    binary: bool,
+    enum_values: Option<&'a Vec<String>>,
+    set_values: Option<&'a Vec<String>>,
+}
+
+impl<'a> ColumnConverter<'a> {
+    /// Ordinals must be the ones of the column from `TableMapEvent::column_ordinals`.
+    pub fn new(
+        table: &'a TableMapEvent,
+        index: usize,
+        ordinals: &ColumnOrdinals,
+        decimal_handling_mode: DecimalHandlingMode,
+    ) -> Result<Self, Error> {
+        let (column_type, metadata) = table.actual_column_type(index);
+        Ok(Self {
+            table,
+            index,
+            column_type: ColumnType::from_code(column_type)?,
+            metadata,
+            decimal_handling_mode,
+            unsigned: table.is_unsigned_at(index, ordinals).unwrap_or(false),
+            binary: table.is_binary_column_at(index, ordinals),
+            enum_values: table.enum_values_at(index, ordinals),
+            set_values: table.set_values_at(index, ordinals),
+        })
+    }
+
+    /// Gets column name from table metadata.
+    /// Columns are named by ordinal if the server doesn't send names (binlog_row_metadata=MINIMAL).
+    pub fn name(&self) -> String {
+        let names = self
+            .table
+            .table_metadata
+            .as_ref()
+            .and_then(|x| x.column_names.as_ref());
+
+        match names.and_then(|x| x.get(self.index)) {
+            Some(name) => name.clone(),
+            None => format!("column_{}", self.index),
+        }
+    }
+
+    /// Returns Kafka Connect schema of the column.
+    pub fn schema(&self) -> Value {
+        let mut schema = Map::new();
+        let (schema_type, name, parameters) = self.schema_type();
+        schema.insert("type".to_string(), json!(schema_type));
+        schema.insert("optional".to_string(), json!(self.optional()));
+        if let Some(name) = name {
+            schema.insert("name".to_string(), json!(name));
+            schema.insert("version".to_string(), json!(1));
+        }
+        if let Some(parameters) = parameters {
+            schema.insert("parameters".to_string(), parameters);
+        }
+        if self.column_type == ColumnType::Geometry {
+            schema.insert(
+                "fields".to_string(),
+                json!([
+                    { "type": "bytes", "optional": false, "field": "wkb" },
+                    { "type": "int32", "optional": true, "field": "srid" }
+                ]),
+            );
+        }
+        schema.insert("field".to_string(), json!(self.name()));
+        Value::Object(schema)
+    }
+
+    /// Converts a cell to JSON value matching the column schema.
+    pub fn value(&self, value: Option<&MySqlValue>) -> Result<Value, Error> {
+        let value = match value {
+            Some(x) => x,
+            None => return Ok(Value::Null),
+        };
+
+        let result = match self.column_type {
+            ColumnType::Tiny | ColumnType::Short | ColumnType::Int24 | ColumnType::Long => {
+                json!(self.convert::<i64>(value)?)
+            }
+            // BIGINT UNSIGNED is represented as long and may overflow like in Debezium
+            ColumnType::LongLong => match self.unsigned {
+                true => json!(self.convert::<u64>(value)? as i64),
+                false => json!(self.convert::<i64>(value)?),
+            },
+            ColumnType::Float | ColumnType::Double => json!(self.convert::<f64>(value)?),
+            ColumnType::Decimal | ColumnType::NewDecimal => {
+                let value = self.convert::<Decimal>(value)?;
+                match self.decimal_handling_mode {
+                    DecimalHandlingMode::Precise => {
+                        json!(STANDARD.encode(value.to_unscaled_bytes()))
+                    }
+                    DecimalHandlingMode::Double => json!(value.to_string().parse::<f64>().ok()),
+                    DecimalHandlingMode::String => json!(value.to_string()),
+                }
+            }
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob => {
+                let bytes = self.convert::<Vec<u8>>(value)?;
+                match self.binary {
+                    true => json!(STANDARD.encode(bytes)),
+                    false => json!(String::from_utf8_lossy(&bytes)),
+                }
+            }
+            ColumnType::Bit => match value {
+                MySqlValue::Bit(bits) if bits.len() == 1 => json!(bits[0]),
+                MySqlValue::Bit(bits) => json!(STANDARD.encode(bits_to_bytes(bits))),
+                _ => return Err(self.unexpected_value(value)),
+            },
+            ColumnType::Enum => match value {
+                MySqlValue::Enum(x) => json!(enum_string(self.enum_values, *x)),
+                _ => return Err(self.unexpected_value(value)),
+            },
+            ColumnType::Set => match value {
+                MySqlValue::Set(x) => json!(set_string(self.set_values, *x)),
+                _ => return Err(self.unexpected_value(value)),
+            },
+            ColumnType::Year => json!(self.convert::<i64>(value)?),
+            ColumnType::Date | ColumnType::NewDate => {
+                let date = self.convert::<Date>(value)?;
+                match date.check_complete() {
+                    Ok(_) => json!(date.to_epoch_days()),
+                    Err(_) => self.zero_value(),
+                }
+            }
+            ColumnType::Time | ColumnType::Time2 => match value {
+                MySqlValue::Time(x) => json!(x.to_micros()),
+                _ => return Err(self.unexpected_value(value)),
+            },
+            ColumnType::DateTime | ColumnType::DateTime2 => {
+                let value = self.convert::<DateTime>(value)?;
+                match value.date().check_complete() {
+                    Ok(_) => match self.fsp() <= 3 {
+                        true => json!(value.to_epoch_micros() / 1000),
+                        false => json!(value.to_epoch_micros()),
+                    },
+                    Err(_) => self.zero_value(),
+                }
+            }
+            ColumnType::TimeStamp | ColumnType::TimeStamp2 => match value {
+                MySqlValue::Timestamp(x) => json!(format_zoned_timestamp(*x, self.fsp())),
+                _ => return Err(self.unexpected_value(value)),
+            },
+            ColumnType::Json => json!(decode_json(&self.convert::<Vec<u8>>(value)?)?),
+            ColumnType::Geometry => {
+                // MySQL stores SRID in first 4 bytes followed by WKB
+                let bytes = self.convert::<Vec<u8>>(value)?;
+                if bytes.len() < 4 {
+                    return Err(self.unexpected_value(value));
+                }
+                let srid = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                json!({ "wkb": STANDARD.encode(&bytes[4..]), "srid": srid })
+            }
+            ColumnType::Null => Value::Null,
+        };
+        Ok(result)
+    }
+
+    /// Returns Kafka Connect type, logical type name and parameters.
+    fn schema_type(&self) -> (&'static str, Option<&'static str>, Option<Value>) {
+        match self.column_type {
+            ColumnType::Tiny => ("int16", None, None),
+            ColumnType::Short if self.unsigned => ("int32", None, None),
+            ColumnType::Short => ("int16", None, None),
+            ColumnType::Int24 => ("int32", None, None),
+            ColumnType::Long if self.unsigned => ("int64", None, None),
+            ColumnType::Long => ("int32", None, None),
+            ColumnType::LongLong => ("int64", None, None),
+            ColumnType::Float => ("float", None, None),
+            ColumnType::Double => ("double", None, None),
+            ColumnType::Decimal | ColumnType::NewDecimal => match self.decimal_handling_mode {
+                DecimalHandlingMode::Precise => {
+                    let precision = self.metadata & 0xFF;
+                    let scale = self.metadata >> 8;
+                    let parameters = json!({
+                        "scale": scale.to_string(),
+                        "connect.decimal.precision": precision.to_string()
+                    });
+                    (
+                        "bytes",
+                        Some("org.apache.kafka.connect.data.Decimal"),
+                        Some(parameters),
+                    )
+                }
+                DecimalHandlingMode::Double => ("double", None, None),
+                DecimalHandlingMode::String => ("string", None, None),
+            },
+            ColumnType::String
+            | ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob => match self.binary {
+                true => ("bytes", None, None),
+                false => ("string", None, None),
+            },
+            ColumnType::Bit => match self.bit_length() {
+                1 => ("boolean", None, None),
+                length => (
+                    "bytes",
+                    Some("io.debezium.data.Bits"),
+                    Some(json!({ "length": length.to_string() })),
+                ),
+            },
+            ColumnType::Enum => (
+                "string",
+                Some("io.debezium.data.Enum"),
+                self.enum_values.map(|x| json!({ "allowed": x.join(",") })),
+            ),
+            ColumnType::Set => (
+                "string",
+                Some("io.debezium.data.EnumSet"),
+                self.set_values.map(|x| json!({ "allowed": x.join(",") })),
+            ),
+            ColumnType::Year => ("int32", Some("io.debezium.time.Year"), None),
+            ColumnType::Date | ColumnType::NewDate => {
+                ("int32", Some("io.debezium.time.Date"), None)
+            }
+            ColumnType::Time | ColumnType::Time2 => {
+                ("int64", Some("io.debezium.time.MicroTime"), None)
+            }
+            ColumnType::DateTime | ColumnType::DateTime2 => match self.fsp() <= 3 {
+                true => ("int64", Some("io.debezium.time.Timestamp"), None),
+                false => ("int64", Some("io.debezium.time.MicroTimestamp"), None),
+            },
+            ColumnType::TimeStamp | ColumnType::TimeStamp2 => {
+                ("string", Some("io.debezium.time.ZonedTimestamp"), None)
+            }
+            ColumnType::Json => ("string", Some("io.debezium.data.Json"), None),
+            ColumnType::Geometry => ("struct", Some("io.debezium.data.geometry.Geometry"), None),
+            ColumnType::Null => ("string", None, None),
+        }
+    }
+
+    fn convert<T: FromMySqlValue>(&self, value: &MySqlValue) -> Result<T, Error> {
+        T::from_mysql_value(Some(value), self.unsigned)
+            .map_err(|e| Error::String(format!("Column {}: {}", self.name(), e)))
+    }
+
+    fn unexpected_value(&self, value: &MySqlValue) -> Error {
+        Error::String(format!(
+            "Column {}: unexpected value {:?}",
+            self.name(),
+            value
+        ))
+    }
+
+    /// Zero dates are converted to null or epoch for NOT NULL columns.
+    fn zero_value(&self) -> Value {
+        match self.optional() {
+            true => Value::Null,
+            false => json!(0),
+        }
+    }
+
+    fn optional(&self) -> bool {
+        self.table
+            .null_bitmap
+            .get(self.index)
+            .copied()
+            .unwrap_or(true)
+    }
+
+    fn bit_length(&self) -> u16 {
+        (self.metadata >> 8) * 8 + (self.metadata & 0xFF)
+    }
+
+    /// Fractional seconds precision of temporal columns.
+    fn fsp(&self) -> u16 {
+        match self.column_type {
+            ColumnType::DateTime2 | ColumnType::TimeStamp2 | ColumnType::Time2 => self.metadata,
+            _ => 0,
+        }
+    }
+}
+
+/// Bits are represented as little-endian byte array.
+/// Column values store bits from the most significant.
+fn bits_to_bytes(bits: &[bool]) -> Vec<u8> {
+    let mut result = vec![0u8; bits.len().div_ceil(8)];
+    for (i, bit) in bits.iter().enumerate() {
+        let position = bits.len() - 1 - i;
+        if *bit {
+            result[position / 8] |= 1 << (position % 8);
+        }
+    }
+    result
+}
+
+/// Formats TIMESTAMP as ISO-8601 string in UTC with fractional digits of the column.
+fn format_zoned_timestamp(millis: u64, fsp: u16) -> String {
+    let value = DateTime::from_epoch_micros(millis as i64 * 1000);
+    let mut result = format!(
+        "{}T{:02}:{:02}:{:02}",
+        value.date(),
+        value.hour,
+        value.minute,
+        value.second
+    );
+    if fsp > 0 {
+        let micros = format!("{:06}", millis % 1000 * 1000);
+        result.push('.');
+        result.push_str(&micros[..fsp.min(6) as usize]);
+    }
+    result.push('Z');
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bits_to_bytes, format_zoned_timestamp};
+
+    #[test]
+    fn format_timestamps() {
+        assert_eq!(
+            "2024-01-01T10:20:30Z",
+            format_zoned_timestamp(1704104430123, 0)
+        );
+        assert_eq!(
+            "2024-01-01T10:20:30.123000Z",
+            format_zoned_timestamp(1704104430123, 6)
+        );
+    }
+
+    #[test]
+    fn convert_bits_to_little_endian_bytes() {
+        // BIT(10) value 515
+        let bits: Vec<bool> = "1000000011".chars().map(|x| x == '1').collect();
+        assert_eq!(vec![0x03, 0x02], bits_to_bytes(&bits));
+    }
+}
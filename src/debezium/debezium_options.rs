@@ -0,0 +1,43 @@
+/// Settings of Debezium change event encoder.
+#[derive(Clone, Debug)]
+pub struct DebeziumOptions {
+    /// Logical name of the server used as topic prefix and in the source block.
+    /// Same as `topic.prefix` of the Debezium MySQL connector.
+    pub server_name: String,
+
+    /// Defines whether keys and values include the schema section.
+    /// Same as `schemas.enable` of JsonConverter. Defaults to true.
+    pub include_schema: bool,
+
+    /// Defines representation of DECIMAL columns. Defaults to DecimalHandlingMode::Precise.
+    pub decimal_handling_mode: DecimalHandlingMode,
+
+    /// Defines whether a delete event is followed by a tombstone event with null value.
+    /// Defaults to true.
+    pub tombstones_on_delete: bool,
+}
+
+impl DebeziumOptions {
+    pub fn new(server_name: &str) -> Self {
+        Self {
+            server_name: server_name.to_string(),
+            include_schema: true,
+            decimal_handling_mode: DecimalHandlingMode::Precise,
+            tombstones_on_delete: true,
+        }
+    }
+}
+
+/// Representation of DECIMAL columns.
+/// <a href="https://debezium.io/documentation/reference/stable/connectors/mysql.html#mysql-property-decimal-handling-mode">See more</a>
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DecimalHandlingMode {
+    /// Exact value as org.apache.kafka.connect.data.Decimal bytes.
+    Precise,
+
+    /// Value converted to double that may lose precision.
+    Double,
+
+    /// Exact value as string.
+    String,
+}
@@ -0,0 +1,4 @@
+pub mod change_event_encoder;
+pub mod debezium_options;
+
+mod column_converter;
@@ -12,6 +12,22 @@ pub enum Error {
     ParseIntError(ParseIntError),
     ErrorStack(ErrorStack),
     String(String),
+    Timeout(String),
+    ConversionError(ConversionError),
+    #[cfg(feature = "arrow")]
+    ArrowError(arrow_schema::ArrowError),
+    #[cfg(feature = "parquet")]
+    ParquetError(parquet::errors::ParquetError),
+}
+
+/// Error converting a column value to a Rust type.
+#[derive(Debug)]
+pub struct ConversionError {
+    /// Gets name of the column or its ordinal if names are unknown.
+    pub column: String,
+
+    /// Gets description of the error.
+    pub message: String,
 }
 
 impl From<io::Error> for Error {
@@ -49,3 +65,17 @@ impl From<ErrorStack> for Error {
         Error::ErrorStack(error)
     }
 }
+
+#[cfg(feature = "arrow")]
+impl From<arrow_schema::ArrowError> for Error {
+    fn from(error: arrow_schema::ArrowError) -> Self {
+        Error::ArrowError(error)
+    }
+}
+
+#[cfg(feature = "parquet")]
+impl From<parquet::errors::ParquetError> for Error {
+    fn from(error: parquet::errors::ParquetError) -> Self {
+        Error::ParquetError(error)
+    }
+}
@@ -0,0 +1,45 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+use crate::errors::Error;
+
+/// Contains the first block of a file loaded by LOAD DATA INFILE statement.
+/// <a href="https://mariadb.com/kb/en/begin_load_query_event/">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BeginLoadQueryEvent {
+    /// Gets id of the loaded file.
+    pub file_id: u32,
+
+    /// Gets the file data.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub data: Vec<u8>,
+}
+
+impl BeginLoadQueryEvent {
+    /// Supports all versions of MariaDB and MySQL.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let file_id = cursor.read_u32::<LittleEndian>()?;
+
+        let mut data = Vec::new();
+        cursor.read_to_end(&mut data)?;
+
+        Ok(Self { file_id, data })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BeginLoadQueryEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_begin_load_query_event() {
+        let mut payload = 3u32.to_le_bytes().to_vec();
+        payload.extend_from_slice(b"1,a\n2,b\n");
+
+        let event = BeginLoadQueryEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!(3, event.file_id);
+        assert_eq!(b"1,a\n2,b\n", event.data.as_slice());
+    }
+}
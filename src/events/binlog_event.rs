@@ -1,24 +1,39 @@
+use crate::events::begin_load_query_event::BeginLoadQueryEvent;
+use crate::events::execute_load_query_event::ExecuteLoadQueryEvent;
 use crate::events::format_description_event::FormatDescriptionEvent;
 use crate::events::heartbeat_event::HeartbeatEvent;
+use crate::events::ignorable_event::IgnorableEvent;
+use crate::events::incident_event::IncidentEvent;
 use crate::events::intvar_event::IntVarEvent;
 use crate::events::query_event::QueryEvent;
+use crate::events::rand_event::RandEvent;
 use crate::events::rotate_event::RotateEvent;
 use crate::events::row_events::delete_rows_event::DeleteRowsEvent;
 use crate::events::row_events::update_rows_event::UpdateRowsEvent;
 use crate::events::row_events::write_rows_event::WriteRowsEvent;
 use crate::events::rows_query_event::RowsQueryEvent;
 use crate::events::table_map_event::TableMapEvent;
+use crate::events::transaction_payload_event::TransactionPayloadEvent;
+use crate::events::unknown_event::UnknownEvent;
 use crate::events::uservar_event::UserVarEvent;
 use crate::events::xid_event::XidEvent;
+use crate::providers::mariadb::events::binlog_checkpoint_event::BinlogCheckpointEvent;
 use crate::providers::mariadb::events::gtid_event::GtidEvent as MariaDbGtidEvent;
 use crate::providers::mariadb::events::gtid_list_event::GtidListEvent;
+use crate::providers::mariadb::events::start_encryption_event::StartEncryptionEvent;
 use crate::providers::mysql::events::gtid_event::GtidEvent as MySqlGtidEvent;
+use crate::providers::mysql::events::gtid_tagged_event::GtidTaggedEvent;
 use crate::providers::mysql::events::prev_gtids_event::PreviousGtidsEvent;
+use crate::providers::mysql::events::transaction_context_event::TransactionContextEvent;
+use crate::providers::mysql::events::view_change_event::ViewChangeEvent;
+use crate::providers::mysql::events::xa_prepare_event::XaPrepareEvent;
+use std::any::Any;
 
 /// Represents a binlog event.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BinlogEvent {
-    UnknownEvent,
+    UnknownEvent(UnknownEvent),
     DeleteRowsEvent(DeleteRowsEvent),
     UpdateRowsEvent(UpdateRowsEvent),
     WriteRowsEvent(WriteRowsEvent),
@@ -31,9 +46,32 @@ pub enum BinlogEvent {
     RowsQueryEvent(RowsQueryEvent),
     HeartbeatEvent(HeartbeatEvent),
     FormatDescriptionEvent(FormatDescriptionEvent),
+    StopEvent,
+    RandEvent(RandEvent),
+    IncidentEvent(IncidentEvent),
+    BeginLoadQueryEvent(BeginLoadQueryEvent),
+    ExecuteLoadQueryEvent(ExecuteLoadQueryEvent),
+    IgnorableEvent(IgnorableEvent),
     // Provider specific events
     MySqlGtidEvent(MySqlGtidEvent),
+    MySqlAnonymousGtidEvent(MySqlGtidEvent),
+    MySqlGtidTaggedEvent(GtidTaggedEvent),
     MySqlPrevGtidsEvent(PreviousGtidsEvent),
+    MySqlXaPrepareEvent(XaPrepareEvent),
+    MySqlViewChangeEvent(ViewChangeEvent),
+    MySqlTransactionContextEvent(TransactionContextEvent),
+    TransactionPayloadEvent(TransactionPayloadEvent),
     MariaDbGtidEvent(MariaDbGtidEvent),
     MariaDbGtidListEvent(GtidListEvent),
+    MariaDbBinlogCheckpointEvent(BinlogCheckpointEvent),
+    MariaDbStartEncryptionEvent(StartEncryptionEvent),
+    // Events returned by custom parsers
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            serialize_with = "crate::serialization::opaque::serialize",
+            skip_deserializing
+        )
+    )]
+    CustomEvent(Box<dyn Any + Send>),
 }
@@ -3,10 +3,14 @@ use std::io::Cursor;
 
 use crate::errors::Error;
 
+/// Event can be skipped by replicas that don't recognize it.
+pub const LOG_EVENT_IGNORABLE_F: u16 = 0x80;
+
 /// Binlog event header version 4. Header size is 19 bytes.
 /// See <a href="https://mariadb.com/kb/en/library/2-binlog-event-header/">MariaDB docs</a>
 /// See <a href="https://dev.mysql.com/doc/internals/en/binlog-version.html">MySQL docs</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EventHeader {
     /// Provides creation time in seconds from Unix.
     pub timestamp: u32,
@@ -40,4 +44,9 @@ impl EventHeader {
             event_flags: cursor.read_u16::<LittleEndian>()?,
         })
     }
+
+    /// Returns true if the event can be safely skipped by replicas that cannot parse it.
+    pub fn is_ignorable(&self) -> bool {
+        self.event_flags & LOG_EVENT_IGNORABLE_F != 0
+    }
 }
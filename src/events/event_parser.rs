@@ -1,33 +1,52 @@
 use crate::constants::checksum_type::ChecksumType;
 use crate::errors::Error;
+use crate::events::begin_load_query_event::BeginLoadQueryEvent;
 use crate::events::binlog_event::BinlogEvent;
 use crate::events::event_header::EventHeader;
 use crate::events::event_type::EventType;
+use crate::events::execute_load_query_event::ExecuteLoadQueryEvent;
 use crate::events::format_description_event::FormatDescriptionEvent;
 use crate::events::heartbeat_event::HeartbeatEvent;
+use crate::events::ignorable_event::IgnorableEvent;
+use crate::events::incident_event::IncidentEvent;
 use crate::events::intvar_event::IntVarEvent;
 use crate::events::query_event::QueryEvent;
+use crate::events::rand_event::RandEvent;
 use crate::events::rotate_event::RotateEvent;
 use crate::events::row_events::delete_rows_event::DeleteRowsEvent;
 use crate::events::row_events::update_rows_event::UpdateRowsEvent;
 use crate::events::row_events::write_rows_event::WriteRowsEvent;
 use crate::events::rows_query_event::RowsQueryEvent;
 use crate::events::table_map_event::TableMapEvent;
+use crate::events::transaction_payload_event::TransactionPayloadEvent;
+use crate::events::unknown_event::UnknownEvent;
 use crate::events::uservar_event::UserVarEvent;
 use crate::events::xid_event::XidEvent;
+use crate::providers::mariadb::events::binlog_checkpoint_event::BinlogCheckpointEvent;
 use crate::providers::mariadb::events::gtid_event::GtidEvent as MariaDbGtidEvent;
 use crate::providers::mariadb::events::gtid_list_event::GtidListEvent;
+use crate::providers::mariadb::events::start_encryption_event::StartEncryptionEvent;
 use crate::providers::mysql::events::gtid_event::GtidEvent as MySqlGtidEvent;
+use crate::providers::mysql::events::gtid_tagged_event::GtidTaggedEvent;
 use crate::providers::mysql::events::prev_gtids_event::PreviousGtidsEvent;
+use crate::providers::mysql::events::transaction_context_event::TransactionContextEvent;
+use crate::providers::mysql::events::view_change_event::ViewChangeEvent;
+use crate::providers::mysql::events::xa_prepare_event::XaPrepareEvent;
 use std::collections::HashMap;
 use std::io::Cursor;
 
+/// User-provided parser of an event payload without checksum.
+pub type CustomParser = Box<dyn Fn(&EventHeader, &[u8]) -> Result<BinlogEvent, Error> + Send>;
+
 pub struct EventParser {
     /// Gets checksum algorithm type used in a binlog file.
     pub checksum_type: ChecksumType,
 
     /// Gets TableMapEvent cache required in row events.
     table_map: HashMap<u64, TableMapEvent>,
+
+    /// Gets user-provided parsers by event type code.
+    custom_parsers: HashMap<u8, CustomParser>,
 }
 
 impl EventParser {
@@ -35,7 +54,26 @@ impl EventParser {
         Self {
             checksum_type: ChecksumType::None,
             table_map: HashMap::new(),
+            custom_parsers: HashMap::new(),
+        }
+    }
+
+    /// Registers parser for the event type code.
+    /// Custom parsers take precedence over built-in ones.
+    /// FormatDescriptionEvent and TableMapEvent parsers cannot be replaced
+    /// because checksum type and table definitions for row events are taken from them.
+    /// With the serde feature their events are serialized as null without the value.
+    pub fn register_parser(&mut self, event_type: u8, parser: CustomParser) -> Result<(), Error> {
+        if event_type == EventType::FormatDescriptionEvent as u8
+            || event_type == EventType::TableMapEvent as u8
+        {
+            return Err(Error::String(format!(
+                "Parser of event type {} cannot be replaced",
+                event_type
+            )));
         }
+        self.custom_parsers.insert(event_type, parser);
+        Ok(())
     }
 
     pub fn parse_event(
@@ -44,11 +82,40 @@ impl EventParser {
         slice: &[u8],
     ) -> Result<BinlogEvent, Error> {
         // Consider verifying checksum
-        let mut cursor = match self.checksum_type {
-            ChecksumType::None => Cursor::new(slice),
-            ChecksumType::Crc32 => Cursor::new(&slice[0..slice.len() - 4]),
+        let payload = match self.checksum_type {
+            ChecksumType::None => slice,
+            ChecksumType::Crc32 => &slice[0..slice.len() - 4],
+        };
+
+        if let Some(parser) = self.custom_parsers.get(&header.event_type) {
+            return parser(header, payload);
+        }
+
+        let binlog_event = match self.parse_payload(header, payload) {
+            // Replicas are allowed to skip ignorable events they cannot parse
+            Err(_) if header.is_ignorable() => {
+                BinlogEvent::UnknownEvent(UnknownEvent::new(header.event_type, payload))
+            }
+            result => result?,
         };
 
+        if let BinlogEvent::FormatDescriptionEvent(x) = &binlog_event {
+            self.checksum_type = x.checksum_type;
+        }
+
+        if let BinlogEvent::TableMapEvent(x) = &binlog_event {
+            self.table_map.insert(x.table_id, x.clone()); //todo: optimize
+        }
+
+        Ok(binlog_event)
+    }
+
+    fn parse_payload(
+        &mut self,
+        header: &EventHeader,
+        payload: &[u8],
+    ) -> Result<BinlogEvent, Error> {
+        let mut cursor = Cursor::new(payload);
         let binlog_event: BinlogEvent = match EventType::from_code(header.event_type) {
             EventType::FormatDescriptionEvent => BinlogEvent::FormatDescriptionEvent(
                 FormatDescriptionEvent::parse(&mut cursor, &header)?,
@@ -64,6 +131,20 @@ impl EventParser {
             EventType::UserVarEvent => BinlogEvent::UserVarEvent(UserVarEvent::parse(&mut cursor)?),
             EventType::QueryEvent => BinlogEvent::QueryEvent(QueryEvent::parse(&mut cursor)?),
             EventType::XidEvent => BinlogEvent::XidEvent(XidEvent::parse(&mut cursor)?),
+            EventType::StopEvent => BinlogEvent::StopEvent,
+            EventType::RandEvent => BinlogEvent::RandEvent(RandEvent::parse(&mut cursor)?),
+            EventType::IncidentEvent => {
+                BinlogEvent::IncidentEvent(IncidentEvent::parse(&mut cursor)?)
+            }
+            EventType::BeginLoadQueryEvent => {
+                BinlogEvent::BeginLoadQueryEvent(BeginLoadQueryEvent::parse(&mut cursor)?)
+            }
+            EventType::ExecuteLoadQueryEvent => {
+                BinlogEvent::ExecuteLoadQueryEvent(ExecuteLoadQueryEvent::parse(&mut cursor)?)
+            }
+            EventType::IgnorableEvent => {
+                BinlogEvent::IgnorableEvent(IgnorableEvent::parse(&mut cursor)?)
+            }
             // Rows events used in MariaDB and MySQL from 5.1.15 to 5.6.
             EventType::WriteRowsEventV1 => {
                 BinlogEvent::WriteRowsEvent(WriteRowsEvent::parse(&mut cursor, &self.table_map, 1)?)
@@ -94,9 +175,30 @@ impl EventParser {
             EventType::MySqlGtidEvent => {
                 BinlogEvent::MySqlGtidEvent(MySqlGtidEvent::parse(&mut cursor)?)
             }
+            EventType::MySqlAnonymousGtidEvent => {
+                BinlogEvent::MySqlAnonymousGtidEvent(MySqlGtidEvent::parse(&mut cursor)?)
+            }
             EventType::MySqlPreviousGtidsEvent => {
                 BinlogEvent::MySqlPrevGtidsEvent(PreviousGtidsEvent::parse(&mut cursor)?)
             }
+            EventType::MySqlTransactionPayloadEvent => BinlogEvent::TransactionPayloadEvent(
+                TransactionPayloadEvent::parse(&mut cursor, self)?,
+            ),
+            EventType::MySqlGtidTaggedEvent => {
+                BinlogEvent::MySqlGtidTaggedEvent(GtidTaggedEvent::parse(&mut cursor)?)
+            }
+            EventType::MySqlXaPrepare => {
+                BinlogEvent::MySqlXaPrepareEvent(XaPrepareEvent::parse(&mut cursor)?)
+            }
+            EventType::MySqlViewChangeEvent => {
+                BinlogEvent::MySqlViewChangeEvent(ViewChangeEvent::parse(&mut cursor)?)
+            }
+            EventType::MySqlTransactionContextEvent => BinlogEvent::MySqlTransactionContextEvent(
+                TransactionContextEvent::parse(&mut cursor)?,
+            ),
+            EventType::MySqlHeartbeatEventV2 => {
+                BinlogEvent::HeartbeatEvent(HeartbeatEvent::parse_v2(&mut cursor)?)
+            }
             // MariaDB specific events
             EventType::MariaDbGtidEvent => {
                 BinlogEvent::MariaDbGtidEvent(MariaDbGtidEvent::parse(&mut cursor, &header)?)
@@ -104,20 +206,105 @@ impl EventParser {
             EventType::MariaDbGtidListEvent => {
                 BinlogEvent::MariaDbGtidListEvent(GtidListEvent::parse(&mut cursor)?)
             }
+            EventType::MariaDbBinlogCheckpointEvent => BinlogEvent::MariaDbBinlogCheckpointEvent(
+                BinlogCheckpointEvent::parse(&mut cursor)?,
+            ),
             EventType::MariaDbAnnotateRowsEvent => {
                 BinlogEvent::RowsQueryEvent(RowsQueryEvent::parse_mariadb(&mut cursor)?)
             }
-            _ => BinlogEvent::UnknownEvent,
+            EventType::MariaDbStartEncryptionEvent => {
+                BinlogEvent::MariaDbStartEncryptionEvent(StartEncryptionEvent::parse(&mut cursor)?)
+            }
+            // MariaDB compressed events
+            EventType::MariaDbQueryCompressedEvent => {
+                BinlogEvent::QueryEvent(QueryEvent::parse_compressed(&mut cursor)?)
+            }
+            EventType::MariaDbWriteRowsCompressedEventV1 => BinlogEvent::WriteRowsEvent(
+                WriteRowsEvent::parse_compressed(&mut cursor, &self.table_map, 1)?,
+            ),
+            EventType::MariaDbUpdateRowsCompressedEventV1 => BinlogEvent::UpdateRowsEvent(
+                UpdateRowsEvent::parse_compressed(&mut cursor, &self.table_map, 1)?,
+            ),
+            EventType::MariaDbDeleteRowsCompressedEventV1 => BinlogEvent::DeleteRowsEvent(
+                DeleteRowsEvent::parse_compressed(&mut cursor, &self.table_map, 1)?,
+            ),
+            EventType::MariaDbWriteRowsCompressedEventV2 => BinlogEvent::WriteRowsEvent(
+                WriteRowsEvent::parse_compressed(&mut cursor, &self.table_map, 2)?,
+            ),
+            EventType::MariaDbUpdateRowsCompressedEventV2 => BinlogEvent::UpdateRowsEvent(
+                UpdateRowsEvent::parse_compressed(&mut cursor, &self.table_map, 2)?,
+            ),
+            EventType::MariaDbDeleteRowsCompressedEventV2 => BinlogEvent::DeleteRowsEvent(
+                DeleteRowsEvent::parse_compressed(&mut cursor, &self.table_map, 2)?,
+            ),
+            _ => BinlogEvent::UnknownEvent(UnknownEvent::new(header.event_type, payload)),
         };
+        Ok(binlog_event)
+    }
+}
 
-        if let BinlogEvent::FormatDescriptionEvent(x) = &binlog_event {
-            self.checksum_type = x.checksum_type;
+#[cfg(test)]
+mod tests {
+    use super::EventParser;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::event_header::EventHeader;
+
+    fn create_header(event_type: u8, event_flags: u16) -> EventHeader {
+        let mut header = [0u8; 19];
+        header[4] = event_type;
+        header[17..19].copy_from_slice(&event_flags.to_le_bytes());
+        EventHeader::parse(&header).unwrap()
+    }
+
+    #[test]
+    fn parse_unknown_event_keeps_payload() {
+        let header = create_header(200, 0);
+        match EventParser::new().parse_event(&header, &[1, 2, 3]).unwrap() {
+            BinlogEvent::UnknownEvent(x) => {
+                assert_eq!(200, x.event_type);
+                assert_eq!(vec![1, 2, 3], x.payload);
+            }
+            x => panic!("Unexpected event {:?}", x),
         }
+    }
 
-        if let BinlogEvent::TableMapEvent(x) = &binlog_event {
-            self.table_map.insert(x.table_id, x.clone()); //todo: optimize
+    #[test]
+    fn parse_event_with_custom_parser() {
+        let mut parser = EventParser::new();
+        parser
+            .register_parser(
+                200,
+                Box::new(|_, payload| Ok(BinlogEvent::CustomEvent(Box::new(payload.len())))),
+            )
+            .unwrap();
+
+        let header = create_header(200, 0);
+        match parser.parse_event(&header, &[1, 2, 3]).unwrap() {
+            BinlogEvent::CustomEvent(x) => assert_eq!(Some(&3usize), x.downcast_ref()),
+            x => panic!("Unexpected event {:?}", x),
         }
+    }
 
-        Ok(binlog_event)
+    #[test]
+    fn reject_custom_parser_of_structural_events() {
+        let mut parser = EventParser::new();
+        for event_type in [15, 19] {
+            let result =
+                parser.register_parser(event_type, Box::new(|_, _| Ok(BinlogEvent::StopEvent)));
+            assert!(result.is_err());
+        }
+    }
+
+    #[test]
+    fn skip_ignorable_event_parse_error() {
+        // Truncated IntVarEvent
+        let payload = [1, 2, 3];
+        let mut parser = EventParser::new();
+        assert!(parser.parse_event(&create_header(5, 0), &payload).is_err());
+
+        let event = parser
+            .parse_event(&create_header(5, 0x80), &payload)
+            .unwrap();
+        assert!(matches!(event, BinlogEvent::UnknownEvent(_)));
     }
 }
@@ -29,6 +29,12 @@ pub enum EventType {
     /// Identifies <see cref="XidEvent"/>.
     XidEvent = 16,
 
+    /// Identifies BeginLoadQueryEvent.
+    BeginLoadQueryEvent = 17,
+
+    /// Identifies ExecuteLoadQueryEvent.
+    ExecuteLoadQueryEvent = 18,
+
     /// Identifies <see cref="TableMapEvent"/>.
     TableMapEvent = 19,
 
@@ -42,9 +48,15 @@ pub enum EventType {
     /// Identifies <see cref="DeleteRowsEvent"/> in MariaDB and MySQL from 5.1.15 to 5.6.
     DeleteRowsEventV1 = 25,
 
+    /// Identifies IncidentEvent.
+    IncidentEvent = 26,
+
     /// Identifies <see cref="HeartbeatEvent"/>.
     HeartbeatEvent = 27,
 
+    /// Identifies IgnorableEvent.
+    IgnorableEvent = 28,
+
     /// MySQL specific events
     /// Identifies <see cref="RowsQueryEvent"/> in MySQL from 5.6 to 8.0.
     MySqlRowsQueryEvent = 29,
@@ -61,12 +73,30 @@ pub enum EventType {
     /// Identifies <see cref="GtidEvent"/> in MySQL from 5.6 to 8.0.
     MySqlGtidEvent = 33,
 
+    /// Identifies <see cref="GtidEvent"/> without GTID when gtid_mode is OFF in MySQL 5.7+.
+    MySqlAnonymousGtidEvent = 34,
+
     /// Identifies <see cref="PreviousGtidsEvent"/> in MySQL from 5.6 to 8.0.
     MySqlPreviousGtidsEvent = 35,
 
+    /// Identifies TransactionContextEvent in MySQL 5.7.6+.
+    MySqlTransactionContextEvent = 36,
+
+    /// Identifies ViewChangeEvent in MySQL 5.7.6+.
+    MySqlViewChangeEvent = 37,
+
     /// Identifies <see cref="XaPrepareEvent"/> in MySQL from 5.6 to 8.0.
     MySqlXaPrepare = 38,
 
+    /// Identifies <see cref="TransactionPayloadEvent"/> in MySQL 8.0.20+.
+    MySqlTransactionPayloadEvent = 40,
+
+    /// Identifies <see cref="HeartbeatEvent"/> with 64-bit position in MySQL 8.0.26+.
+    MySqlHeartbeatEventV2 = 41,
+
+    /// Identifies GtidTaggedEvent in MySQL 8.3+.
+    MySqlGtidTaggedEvent = 42,
+
     /// MariaDB specific events
     /// Identifies <see cref="RowsQueryEvent"/> in MariaDB.
     MariaDbAnnotateRowsEvent = 160,
@@ -82,6 +112,28 @@ pub enum EventType {
 
     /// Identifies encryption start event in MariaDB.
     MariaDbStartEncryptionEvent = 164,
+
+    /// Compressed events
+    /// Identifies compressed <see cref="QueryEvent"/> in MariaDB.
+    MariaDbQueryCompressedEvent = 165,
+
+    /// Identifies compressed <see cref="WriteRowsEvent"/> V1 in MariaDB.
+    MariaDbWriteRowsCompressedEventV1 = 166,
+
+    /// Identifies compressed <see cref="UpdateRowsEvent"/> V1 in MariaDB.
+    MariaDbUpdateRowsCompressedEventV1 = 167,
+
+    /// Identifies compressed <see cref="DeleteRowsEvent"/> V1 in MariaDB.
+    MariaDbDeleteRowsCompressedEventV1 = 168,
+
+    /// Identifies compressed <see cref="WriteRowsEvent"/> V2 in MariaDB.
+    MariaDbWriteRowsCompressedEventV2 = 169,
+
+    /// Identifies compressed <see cref="UpdateRowsEvent"/> V2 in MariaDB.
+    MariaDbUpdateRowsCompressedEventV2 = 170,
+
+    /// Identifies compressed <see cref="DeleteRowsEvent"/> V2 in MariaDB.
+    MariaDbDeleteRowsCompressedEventV2 = 171,
 }
 
 impl EventType {
@@ -95,23 +147,40 @@ impl EventType {
             14 => EventType::UserVarEvent,
             15 => EventType::FormatDescriptionEvent,
             16 => EventType::XidEvent,
+            17 => EventType::BeginLoadQueryEvent,
+            18 => EventType::ExecuteLoadQueryEvent,
             19 => EventType::TableMapEvent,
             23 => EventType::WriteRowsEventV1,
             24 => EventType::UpdateRowsEventV1,
             25 => EventType::DeleteRowsEventV1,
+            26 => EventType::IncidentEvent,
             27 => EventType::HeartbeatEvent,
+            28 => EventType::IgnorableEvent,
             29 => EventType::MySqlRowsQueryEvent,
             30 => EventType::MySqlWriteRowsEventV2,
             31 => EventType::MySqlUpdateRowsEventV2,
             32 => EventType::MySqlDeleteRowsEventV2,
             33 => EventType::MySqlGtidEvent,
+            34 => EventType::MySqlAnonymousGtidEvent,
             35 => EventType::MySqlPreviousGtidsEvent,
+            36 => EventType::MySqlTransactionContextEvent,
+            37 => EventType::MySqlViewChangeEvent,
             38 => EventType::MySqlXaPrepare,
+            40 => EventType::MySqlTransactionPayloadEvent,
+            41 => EventType::MySqlHeartbeatEventV2,
+            42 => EventType::MySqlGtidTaggedEvent,
             160 => EventType::MariaDbAnnotateRowsEvent,
             161 => EventType::MariaDbBinlogCheckpointEvent,
             162 => EventType::MariaDbGtidEvent,
             163 => EventType::MariaDbGtidListEvent,
             164 => EventType::MariaDbStartEncryptionEvent,
+            165 => EventType::MariaDbQueryCompressedEvent,
+            166 => EventType::MariaDbWriteRowsCompressedEventV1,
+            167 => EventType::MariaDbUpdateRowsCompressedEventV1,
+            168 => EventType::MariaDbDeleteRowsCompressedEventV1,
+            169 => EventType::MariaDbWriteRowsCompressedEventV2,
+            170 => EventType::MariaDbUpdateRowsCompressedEventV2,
+            171 => EventType::MariaDbDeleteRowsCompressedEventV2,
             _ => EventType::Unknown,
         }
     }
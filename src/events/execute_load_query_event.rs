@@ -0,0 +1,82 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+use crate::{errors::Error, events::query_event::QueryEvent};
+
+const QUERY_HEADER_SIZE: usize = 13;
+
+/// Executes LOAD DATA INFILE statement for the file sent in BeginLoadQueryEvent.
+/// <a href="https://mariadb.com/kb/en/execute_load_query_event/">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExecuteLoadQueryEvent {
+    /// Gets id of the loaded file.
+    pub file_id: u32,
+
+    /// Gets start position of the file name in the statement.
+    pub start_position: u32,
+
+    /// Gets end position of the file name in the statement.
+    pub end_position: u32,
+
+    /// Gets how duplicates are handled.
+    /// 0x00 - LOAD_DUP_ERROR.
+    /// 0x01 - LOAD_DUP_IGNORE.
+    /// 0x02 - LOAD_DUP_REPLACE.
+    pub dup_handling: u8,
+
+    /// Gets the LOAD DATA INFILE statement.
+    pub query: QueryEvent,
+}
+
+impl ExecuteLoadQueryEvent {
+    /// Supports all versions of MariaDB and MySQL.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        // Load fields are inserted between QueryEvent post-header and its status variables.
+        let mut payload = vec![0; QUERY_HEADER_SIZE];
+        cursor.read_exact(&mut payload)?;
+
+        let file_id = cursor.read_u32::<LittleEndian>()?;
+        let start_position = cursor.read_u32::<LittleEndian>()?;
+        let end_position = cursor.read_u32::<LittleEndian>()?;
+        let dup_handling = cursor.read_u8()?;
+
+        cursor.read_to_end(&mut payload)?;
+        let query = QueryEvent::parse(&mut Cursor::new(payload.as_slice()))?;
+
+        Ok(Self {
+            file_id,
+            start_position,
+            end_position,
+            dup_handling,
+            query,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExecuteLoadQueryEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_execute_load_query_event() {
+        let sql = "LOAD DATA INFILE '/tmp/SQL_LOAD-1-2-3.data' INTO TABLE t";
+        let mut payload = vec![7, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0];
+        payload.extend_from_slice(&3u32.to_le_bytes());
+        payload.extend_from_slice(&17u32.to_le_bytes());
+        payload.extend_from_slice(&42u32.to_le_bytes());
+        payload.push(2);
+        payload.extend_from_slice(b"test\0");
+        payload.extend_from_slice(sql.as_bytes());
+
+        let event = ExecuteLoadQueryEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!(3, event.file_id);
+        assert_eq!(17, event.start_position);
+        assert_eq!(42, event.end_position);
+        assert_eq!(2, event.dup_handling);
+        assert_eq!(7, event.query.thread_id);
+        assert_eq!("test", event.query.database_name);
+        assert_eq!(sql, event.query.sql_statement);
+    }
+}
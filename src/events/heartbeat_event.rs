@@ -1,13 +1,25 @@
 use std::io::{Cursor, Read};
 
-use crate::errors::Error;
+use crate::{
+    errors::Error,
+    extensions::{read_len_enc_num, read_string},
+};
+
+/// Heartbeat v2 field types.
+const HEADER_END_MARK: usize = 0;
+const LOG_FILENAME_FIELD: usize = 1;
+const LOG_POSITION_FIELD: usize = 2;
 
 /// The event is sent from master to the client for keep alive feature.
 /// <a href="https://mariadb.com/kb/en/library/heartbeat_log_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct HeartbeatEvent {
     /// Gets current master binlog filename
     pub binlog_filename: String,
+
+    /// Gets current master binlog position. Sent only in HEARTBEAT_LOG_EVENT_V2.
+    pub binlog_position: Option<u64>,
 }
 
 impl HeartbeatEvent {
@@ -16,6 +28,62 @@ impl HeartbeatEvent {
         let mut binlog_filename = String::new();
         cursor.read_to_string(&mut binlog_filename)?;
 
-        Ok(Self { binlog_filename })
+        Ok(Self {
+            binlog_filename,
+            binlog_position: None,
+        })
+    }
+
+    /// Supports MySQL 8.0.26+ that sends 64-bit binlog positions.
+    pub fn parse_v2(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let mut binlog_filename = String::new();
+        let mut binlog_position = None;
+
+        // Payload is a list of type-length-value fields ending with the end mark.
+        while (cursor.position() as usize) < cursor.get_ref().len() {
+            let field_type = read_len_enc_num(cursor)?;
+            if field_type == HEADER_END_MARK {
+                break;
+            }
+
+            let field_length = read_len_enc_num(cursor)?;
+            match field_type {
+                LOG_FILENAME_FIELD => binlog_filename = read_string(cursor, field_length)?,
+                LOG_POSITION_FIELD => binlog_position = Some(read_len_enc_num(cursor)? as u64),
+                _ => cursor.set_position(cursor.position() + field_length as u64),
+            }
+        }
+
+        Ok(Self {
+            binlog_filename,
+            binlog_position,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeartbeatEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_heartbeat_event() {
+        let event = HeartbeatEvent::parse(&mut Cursor::new(&b"mysql-bin.000001"[..])).unwrap();
+        assert_eq!("mysql-bin.000001", event.binlog_filename);
+        assert_eq!(None, event.binlog_position);
+    }
+
+    #[test]
+    fn parse_heartbeat_v2_event() {
+        let mut payload = vec![1, 16];
+        payload.extend_from_slice(b"mysql-bin.000001");
+        payload.extend_from_slice(&[2, 9, 0xfe]);
+        payload.extend_from_slice(&(u32::MAX as u64 + 1).to_le_bytes());
+        // Unknown fields are skipped
+        payload.extend_from_slice(&[7, 2, 1, 1, 0]);
+
+        let event = HeartbeatEvent::parse_v2(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!("mysql-bin.000001", event.binlog_filename);
+        assert_eq!(Some(u32::MAX as u64 + 1), event.binlog_position);
     }
 }
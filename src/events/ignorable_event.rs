@@ -0,0 +1,23 @@
+use std::io::{Cursor, Read};
+
+use crate::errors::Error;
+
+/// Event that can be safely ignored by replicas that don't recognize it.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1Ignorable__event.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IgnorableEvent {
+    /// Gets raw event data.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub payload: Vec<u8>,
+}
+
+impl IgnorableEvent {
+    /// Supports MySQL 5.6+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let mut payload = Vec::new();
+        cursor.read_to_end(&mut payload)?;
+
+        Ok(Self { payload })
+    }
+}
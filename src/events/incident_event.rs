@@ -0,0 +1,49 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use crate::{errors::Error, extensions::read_string};
+
+/// Notifies that something out of the ordinary happened on the master.
+/// The replica is expected to stop as some events may be lost.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1Incident__event.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IncidentEvent {
+    /// Gets type.
+    /// 0x00 - No incident.
+    /// 0x01 - Events may be lost on the master.
+    pub incident_type: u16,
+
+    /// Gets description of the incident.
+    pub message: String,
+}
+
+impl IncidentEvent {
+    /// Supports all versions of MariaDB and MySQL.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let incident_type = cursor.read_u16::<LittleEndian>()?;
+        let message_length = cursor.read_u8()?;
+        let message = read_string(cursor, message_length as usize)?;
+
+        Ok(Self {
+            incident_type,
+            message,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IncidentEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_incident_event() {
+        let mut payload = vec![1, 0, 10];
+        payload.extend_from_slice(b"LOST_EVENT");
+
+        let event = IncidentEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!(1, event.incident_type);
+        assert_eq!("LOST_EVENT", event.message);
+    }
+}
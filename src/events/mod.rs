@@ -1,15 +1,23 @@
+pub mod begin_load_query_event;
 pub mod binlog_event;
 pub mod event_header;
 pub mod event_parser;
 pub mod event_type;
+pub mod execute_load_query_event;
 pub mod format_description_event;
 pub mod heartbeat_event;
+pub mod ignorable_event;
+pub mod incident_event;
 pub mod intvar_event;
 pub mod query_event;
+pub mod rand_event;
 pub mod rotate_event;
 pub mod rows_query_event;
 pub mod table_map_event;
+pub mod transaction_payload_event;
+pub mod unknown_event;
 pub mod uservar_event;
+pub mod xid;
 pub mod xid_event;
 
 pub mod row_events;
@@ -1,3 +1,4 @@
+use crate::providers::mariadb::compression::uncompress_event_data;
 use crate::{errors::Error, extensions::read_string};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::{Cursor, Read, Seek, SeekFrom};
@@ -5,6 +6,7 @@ use std::io::{Cursor, Read, Seek, SeekFrom};
 /// Represents sql statement in binary log.
 /// <a href="https://mariadb.com/kb/en/library/query_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QueryEvent {
     /// Gets id of the thread that issued the statement.
     pub thread_id: u32,
@@ -16,6 +18,7 @@ pub struct QueryEvent {
     pub error_code: u16,
 
     /// Gets status variables.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
     pub status_variables: Vec<u8>,
 
     /// Gets the default database name.
@@ -28,6 +31,15 @@ pub struct QueryEvent {
 impl QueryEvent {
     /// Supports all versions of MariaDB and MySQL.
     pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        QueryEvent::parse_event(cursor, false)
+    }
+
+    /// Supports MariaDB 10.2.3+ QUERY_COMPRESSED_EVENT where the statement is compressed.
+    pub fn parse_compressed(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        QueryEvent::parse_event(cursor, true)
+    }
+
+    fn parse_event(cursor: &mut Cursor<&[u8]>, compressed: bool) -> Result<Self, Error> {
         let thread_id = cursor.read_u32::<LittleEndian>()?;
         let duration = cursor.read_u32::<LittleEndian>()?;
 
@@ -44,8 +56,13 @@ impl QueryEvent {
         let database_name = read_string(cursor, database_name_length as usize)?;
         cursor.seek(SeekFrom::Current(1))?;
 
-        let mut sql_statement = String::new();
-        cursor.read_to_string(&mut sql_statement)?;
+        let sql_statement = if compressed {
+            String::from_utf8(uncompress_event_data(cursor)?)?
+        } else {
+            let mut sql_statement = String::new();
+            cursor.read_to_string(&mut sql_statement)?;
+            sql_statement
+        };
 
         Ok(Self {
             thread_id,
@@ -57,3 +74,41 @@ impl QueryEvent {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::QueryEvent;
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    const HEADER: [u8; 13] = [7, 0, 0, 0, 0, 0, 0, 0, 4, 0, 0, 0, 0];
+
+    #[test]
+    fn parse_query_event() {
+        let mut payload = HEADER.to_vec();
+        payload.extend_from_slice(b"test\0DROP TABLE t");
+        let mut cursor = Cursor::new(payload.as_slice());
+
+        let event = QueryEvent::parse(&mut cursor).unwrap();
+        assert_eq!(7, event.thread_id);
+        assert_eq!("test", event.database_name);
+        assert_eq!("DROP TABLE t", event.sql_statement);
+    }
+
+    #[test]
+    fn parse_compressed_query_event() {
+        let sql = b"DROP TABLE t";
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(sql).unwrap();
+
+        let mut payload = HEADER.to_vec();
+        payload.extend_from_slice(b"test\0");
+        payload.extend_from_slice(&[0x81, sql.len() as u8]);
+        payload.extend(encoder.finish().unwrap());
+        let mut cursor = Cursor::new(payload.as_slice());
+
+        let event = QueryEvent::parse_compressed(&mut cursor).unwrap();
+        assert_eq!("test", event.database_name);
+        assert_eq!("DROP TABLE t", event.sql_statement);
+    }
+}
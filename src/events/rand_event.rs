@@ -0,0 +1,42 @@
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+use crate::errors::Error;
+
+/// Generated when RAND() function is used in a statement.
+/// <a href="https://mariadb.com/kb/en/rand_event/">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RandEvent {
+    /// Gets the first seed of the random number generator.
+    pub seed1: u64,
+
+    /// Gets the second seed of the random number generator.
+    pub seed2: u64,
+}
+
+impl RandEvent {
+    /// Supports all versions of MariaDB and MySQL.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let seed1 = cursor.read_u64::<LittleEndian>()?;
+        let seed2 = cursor.read_u64::<LittleEndian>()?;
+
+        Ok(Self { seed1, seed2 })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RandEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_rand_event() {
+        let mut payload = 1u64.to_le_bytes().to_vec();
+        payload.extend_from_slice(&u64::MAX.to_le_bytes());
+
+        let event = RandEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!(1, event.seed1);
+        assert_eq!(u64::MAX, event.seed2);
+    }
+}
@@ -7,6 +7,7 @@ use crate::errors::Error;
 /// Fake version is also returned when replication is started.
 /// <a href="https://mariadb.com/kb/en/library/rotate_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RotateEvent {
     /// Gets next binlog filename
     pub binlog_filename: String,
@@ -0,0 +1,153 @@
+use crate::errors::Error;
+use crate::events::row_events::mysql_value::{Date, DateTime, Time};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, TimeDelta, TimeZone, Utc};
+
+impl TryFrom<Date> for NaiveDate {
+    type Error = Error;
+
+    /// Fails for zero and partial dates.
+    fn try_from(value: Date) -> Result<Self, Self::Error> {
+        value.check_complete()?;
+        NaiveDate::from_ymd_opt(value.year as i32, value.month as u32, value.day as u32)
+            .ok_or_else(|| Error::String(format!("Invalid date {}", value)))
+    }
+}
+
+impl TryFrom<Time> for NaiveTime {
+    type Error = Error;
+
+    /// Fails for TIME values outside of 00:00:00 - 23:59:59 range.
+    /// Use TimeDelta conversion for time intervals.
+    fn try_from(value: Time) -> Result<Self, Self::Error> {
+        let hour = u32::try_from(value.hour).ok();
+        hour.and_then(|hour| {
+            NaiveTime::from_hms_milli_opt(
+                hour,
+                value.minute as u32,
+                value.second as u32,
+                value.millis,
+            )
+        })
+        .ok_or_else(|| Error::String(format!("Time {} is not a time of day", value)))
+    }
+}
+
+impl From<Time> for TimeDelta {
+    fn from(value: Time) -> Self {
+        let seconds = value.hour.unsigned_abs() as i64 * 3600
+            + value.minute as i64 * 60
+            + value.second as i64;
+        let millis = seconds * 1000 + value.millis as i64;
+        TimeDelta::milliseconds(if value.hour < 0 { -millis } else { millis })
+    }
+}
+
+impl TryFrom<DateTime> for NaiveDateTime {
+    type Error = Error;
+
+    /// Fails for zero and partial dates.
+    fn try_from(value: DateTime) -> Result<Self, Self::Error> {
+        let date = NaiveDate::try_from(value.date())?;
+        let time = NaiveTime::from_hms_milli_opt(
+            value.hour as u32,
+            value.minute as u32,
+            value.second as u32,
+            value.millis,
+        )
+        .ok_or_else(|| Error::String(format!("Invalid datetime {}", value)))?;
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+impl DateTime {
+    /// DATETIME values don't store time zone.
+    /// Interprets the value in the zone the server writes DATETIME values in.
+    /// The earlier instant is returned for ambiguous values at DST transitions.
+    pub fn to_chrono_in<Tz: TimeZone>(&self, zone: &Tz) -> Result<chrono::DateTime<Tz>, Error> {
+        let value = NaiveDateTime::try_from(*self)?;
+        zone.from_local_datetime(&value)
+            .earliest()
+            .ok_or_else(|| Error::String(format!("Datetime {} doesn't exist in the zone", self)))
+    }
+}
+
+/// Converts TIMESTAMP value which is always stored in UTC.
+pub fn timestamp_to_chrono(millis: u64) -> Result<chrono::DateTime<Utc>, Error> {
+    Utc.timestamp_millis_opt(millis as i64)
+        .single()
+        .ok_or_else(|| Error::String(format!("Invalid timestamp {}", millis)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timestamp_to_chrono;
+    use crate::events::row_events::mysql_value::{Date, DateTime, Time};
+    use chrono::{FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+
+    fn create_date_time(year: u16, month: u8, day: u8) -> DateTime {
+        DateTime {
+            year,
+            month,
+            day,
+            hour: 10,
+            minute: 20,
+            second: 30,
+            millis: 400,
+        }
+    }
+
+    #[test]
+    fn convert_dates() {
+        let date = Date {
+            year: 2022,
+            month: 2,
+            day: 28,
+        };
+        assert_eq!(
+            NaiveDate::from_ymd_opt(2022, 2, 28),
+            NaiveDate::try_from(date).ok()
+        );
+
+        let zero = Date {
+            year: 0,
+            month: 0,
+            day: 0,
+        };
+        let partial = Date {
+            year: 2022,
+            month: 2,
+            day: 0,
+        };
+        assert!(NaiveDate::try_from(zero).is_err());
+        assert!(NaiveDate::try_from(partial).is_err());
+        assert!(NaiveDateTime::try_from(create_date_time(0, 0, 0)).is_err());
+    }
+
+    #[test]
+    fn convert_times() {
+        let time = Time {
+            hour: 25,
+            minute: 1,
+            second: 2,
+            millis: 3,
+        };
+        assert!(NaiveTime::try_from(time).is_err());
+        assert_eq!(TimeDelta::milliseconds(90_062_003), TimeDelta::from(time));
+    }
+
+    #[test]
+    fn convert_date_time_in_zone() {
+        let zone = FixedOffset::east_opt(3 * 3600).unwrap();
+        let value = create_date_time(2022, 2, 28).to_chrono_in(&zone).unwrap();
+
+        assert_eq!("2022-02-28T10:20:30.400+03:00", value.to_rfc3339());
+        assert_eq!(
+            "2022-02-28T07:20:30.400+00:00",
+            value.naive_utc().and_utc().to_rfc3339()
+        );
+        assert_eq!(
+            "2022-02-28T07:20:30.400+00:00",
+            timestamp_to_chrono(1_646_032_830_400).unwrap().to_rfc3339()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::errors::Error;
+use crate::events::row_events::mysql_value::Decimal;
 use byteorder::{BigEndian, ReadBytesExt};
 use std::io::{Cursor, Read};
 
@@ -7,7 +8,7 @@ use std::io::{Cursor, Read};
 const DIGITS_PER_INT: u8 = 9;
 const COMPRESSED_BYTES: [u8; 10] = [0, 1, 1, 2, 2, 3, 3, 4, 4, 4];
 
-pub fn parse_decimal(cursor: &mut Cursor<&[u8]>, metadata: u16) -> Result<String, Error> {
+pub fn parse_decimal(cursor: &mut Cursor<&[u8]>, metadata: u16) -> Result<Decimal, Error> {
     let precision = metadata & 0xFF;
     let scale = (metadata >> 8) as u8;
     let integral = (precision - scale as u16) as u8;
@@ -33,7 +34,6 @@ pub fn parse_decimal(cursor: &mut Cursor<&[u8]>, metadata: u16) -> Result<String
     value[0] ^= 0x80;
 
     if negative {
-        result += "-";
         for i in 0..value.len() {
             value[i] ^= 0xFF;
         }
@@ -65,9 +65,8 @@ pub fn parse_decimal(cursor: &mut Cursor<&[u8]>, metadata: u16) -> Result<String
     if !started {
         result += "0";
     }
-    if scale > 0 {
-        result += ".";
-    }
+    let integral_part = result;
+    let mut result = String::new();
 
     size = COMPRESSED_BYTES[compressed_fractional as usize];
     for _i in 0..uncompressed_fractional {
@@ -79,15 +78,126 @@ pub fn parse_decimal(cursor: &mut Cursor<&[u8]>, metadata: u16) -> Result<String
         let precision = compressed_fractional as usize;
         result += &format!("{val:0prec$}", prec = precision, val = value)
     }
-    Ok(result)
+    Ok(Decimal {
+        negative,
+        integral: integral_part,
+        fractional: result,
+        precision: precision as u8,
+        scale,
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::events::row_events::decimal::parse_decimal;
+    use crate::events::row_events::decimal::{parse_decimal, COMPRESSED_BYTES, DIGITS_PER_INT};
     use byteorder::{LittleEndian, ReadBytesExt};
     use std::io::Cursor;
 
+    /// Encodes decimal the same way as decimal2bin in MySQL.
+    fn encode_decimal(value: &str, precision: u8, scale: u8) -> Vec<u8> {
+        let negative = value.starts_with('-');
+        let value = value.trim_start_matches('-');
+        let (integral, fractional) = value.split_once('.').unwrap_or((value, ""));
+        let integral = format!(
+            "{:0>width$}",
+            integral,
+            width = (precision - scale) as usize
+        );
+        let fractional = format!("{:0<width$}", fractional, width = scale as usize);
+
+        let mut result = Vec::new();
+        let mut write_group = |digits: &str| {
+            let size = match digits.len() as u8 {
+                DIGITS_PER_INT => 4,
+                length => COMPRESSED_BYTES[length as usize] as usize,
+            };
+            let number: u32 = digits.parse().unwrap();
+            result.extend_from_slice(&number.to_be_bytes()[4 - size..]);
+        };
+
+        let leading = integral.len() % DIGITS_PER_INT as usize;
+        if leading > 0 {
+            write_group(&integral[..leading]);
+        }
+        for i in (leading..integral.len()).step_by(DIGITS_PER_INT as usize) {
+            write_group(&integral[i..i + DIGITS_PER_INT as usize]);
+        }
+        for i in (0..fractional.len()).step_by(DIGITS_PER_INT as usize) {
+            write_group(&fractional[i..fractional.len().min(i + DIGITS_PER_INT as usize)]);
+        }
+
+        result[0] ^= 0x80;
+        if negative {
+            result.iter_mut().for_each(|x| *x ^= 0xFF);
+        }
+        result
+    }
+
+    fn parse(value: &str, precision: u8, scale: u8) -> String {
+        let payload = encode_decimal(value, precision, scale);
+        let metadata = precision as u16 | (scale as u16) << 8;
+        let mut cursor = Cursor::new(payload.as_slice());
+        parse_decimal(&mut cursor, metadata).unwrap().to_string()
+    }
+
+    #[test]
+    fn parse_decimal_65_30_edge_cases() {
+        let max = format!("{}.{}", "9".repeat(35), "9".repeat(30));
+        let min_fraction = format!("0.{}1", "0".repeat(29));
+        let zero = format!("0.{}", "0".repeat(30));
+
+        assert_eq!(max, parse(&max, 65, 30));
+        assert_eq!(format!("-{}", max), parse(&format!("-{}", max), 65, 30));
+        assert_eq!(min_fraction, parse(&min_fraction, 65, 30));
+        assert_eq!(
+            format!("-{}", min_fraction),
+            parse(&format!("-{}", min_fraction), 65, 30)
+        );
+        assert_eq!(zero, parse("0", 65, 30));
+
+        let value = format!("1{}.5", "0".repeat(34));
+        let expected = format!("{}{}", value, "0".repeat(29));
+        assert_eq!(expected, parse(&value, 65, 30));
+    }
+
+    #[test]
+    fn parse_decimal_keeps_precision_and_scale() {
+        let payload = encode_decimal("-12.5", 65, 30);
+        let mut cursor = Cursor::new(payload.as_slice());
+        let decimal = parse_decimal(&mut cursor, 65 | 30 << 8).unwrap();
+
+        assert!(decimal.negative);
+        assert_eq!("12", decimal.integral);
+        assert_eq!(30, decimal.fractional.len());
+        assert_eq!(65, decimal.precision);
+        assert_eq!(30, decimal.scale);
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn convert_to_rust_decimal() {
+        let payload = encode_decimal("-12345.678", 10, 4);
+        let decimal = parse_decimal(&mut Cursor::new(payload.as_slice()), 10 | 4 << 8).unwrap();
+        let value = rust_decimal::Decimal::try_from(&decimal).unwrap();
+        assert_eq!("-12345.6780", value.to_string());
+
+        // DECIMAL(65,30) doesn't fit in 96-bit mantissa
+        let max = format!("{}.{}", "9".repeat(35), "9".repeat(30));
+        let payload = encode_decimal(&max, 65, 30);
+        let decimal = parse_decimal(&mut Cursor::new(payload.as_slice()), 65 | 30 << 8).unwrap();
+        assert!(rust_decimal::Decimal::try_from(&decimal).is_err());
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn convert_to_bigdecimal() {
+        let max = format!("-{}.{}", "9".repeat(35), "9".repeat(30));
+        let payload = encode_decimal(&max, 65, 30);
+        let decimal = parse_decimal(&mut Cursor::new(payload.as_slice()), 65 | 30 << 8).unwrap();
+        let value = bigdecimal::BigDecimal::try_from(&decimal).unwrap();
+        assert_eq!(max, value.to_string());
+    }
+
     #[test]
     fn parse_positive_number() {
         // decimal(65,10), column = '1234567890112233445566778899001112223334445556667778889.9900011112'
@@ -100,7 +210,10 @@ mod tests {
 
         let expected =
             String::from("1234567890112233445566778899001112223334445556667778889.9900011112");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 
     #[test]
@@ -115,7 +228,10 @@ mod tests {
 
         let expected =
             String::from("-1234567890112233445566778899001112223334445556667778889.9900011112");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 
     #[test]
@@ -129,7 +245,10 @@ mod tests {
         let metadata = cursor.read_u16::<LittleEndian>().unwrap();
 
         let expected = String::from("7778889.9900011112");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 
     #[test]
@@ -143,7 +262,10 @@ mod tests {
         let metadata = cursor.read_u16::<LittleEndian>().unwrap();
 
         let expected = String::from("0.9900011112");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 
     #[test]
@@ -158,7 +280,10 @@ mod tests {
         let metadata = cursor.read_u16::<LittleEndian>().unwrap();
 
         let expected = String::from("34445556667778889.123456789006700");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 
     #[test]
@@ -172,6 +297,9 @@ mod tests {
         let metadata = cursor.read_u16::<LittleEndian>().unwrap();
 
         let expected = String::from("34445556667778889");
-        assert_eq!(expected, parse_decimal(&mut cursor, metadata).unwrap());
+        assert_eq!(
+            expected,
+            parse_decimal(&mut cursor, metadata).unwrap().to_string()
+        );
     }
 }
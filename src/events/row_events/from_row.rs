@@ -0,0 +1,100 @@
+use crate::errors::{ConversionError, Error};
+use crate::events::row_events::from_value::FromMySqlValue;
+use crate::events::row_events::row_data::RowData;
+use crate::events::table_map_event::TableMapEvent;
+
+#[cfg(feature = "derive")]
+pub use mysql_cdc_derive::FromRow;
+
+/// Maps a row of a row event to a user struct.
+/// Can be implemented with `#[derive(FromRow)]` when `derive` feature is enabled.
+pub trait FromRow: Sized {
+    fn from_row(row: &RowData, table: &TableMapEvent) -> Result<Self, Error>;
+}
+
+/// Gets value of the column and converts it to the Rust type.
+/// The column is found by name if the server sends column names (binlog_row_metadata=FULL).
+/// Otherwise the column is found by ordinal.
+pub fn get_column<T: FromMySqlValue>(
+    row: &RowData,
+    table: &TableMapEvent,
+    name: &str,
+    ordinal: usize,
+) -> Result<T, Error> {
+    let column_names = table
+        .table_metadata
+        .as_ref()
+        .and_then(|x| x.column_names.as_ref());
+
+    let index = match column_names {
+        Some(names) => names.iter().position(|x| x == name),
+        None => Some(ordinal),
+    };
+
+    let conversion_error = |message: String| {
+        Error::ConversionError(ConversionError {
+            column: name.to_string(),
+            message,
+        })
+    };
+
+    let index = match index {
+        Some(x) if x < row.cells.len() => x,
+        _ => return Err(conversion_error("Column not found".to_string())),
+    };
+
+    // Numeric columns are signed by default
+    let unsigned = table.is_unsigned(index).unwrap_or(false);
+    T::from_mysql_value(row.cells[index].as_ref(), unsigned).map_err(conversion_error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::get_column;
+    use crate::errors::Error;
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::events::row_events::row_data::RowData;
+    use crate::events::table_map_event::TableMapEvent;
+    use crate::test_fixtures::{self, ID, NAME};
+
+    fn create_table(column_names: Option<Vec<String>>) -> TableMapEvent {
+        let mut table = test_fixtures::create_table(&[ID, NAME]);
+        let metadata = table.table_metadata.as_mut().unwrap();
+        metadata.signedness = Some(vec![true]);
+        metadata.column_names = column_names;
+        table
+    }
+
+    #[test]
+    fn get_column_by_name_and_ordinal() {
+        let row = RowData::new(vec![Some(MySqlValue::Int(u32::MAX)), None]);
+
+        let table = create_table(Some(vec!["id".to_string(), "name".to_string()]));
+        assert_eq!(u32::MAX, get_column::<u32>(&row, &table, "id", 5).unwrap());
+        assert_eq!(
+            None,
+            get_column::<Option<String>>(&row, &table, "name", 0).unwrap()
+        );
+
+        let table = create_table(None);
+        assert_eq!(
+            u32::MAX,
+            get_column::<u32>(&row, &table, "other", 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn conversion_error_names_column() {
+        let row = RowData::new(vec![Some(MySqlValue::Int(u32::MAX)), None]);
+        let table = create_table(Some(vec!["id".to_string(), "name".to_string()]));
+
+        match get_column::<i32>(&row, &table, "id", 0) {
+            Err(Error::ConversionError(x)) => assert_eq!("id", x.column),
+            x => panic!("Unexpected result {:?}", x),
+        }
+        match get_column::<String>(&row, &table, "name", 1) {
+            Err(Error::ConversionError(x)) => assert_eq!("Unexpected NULL value", x.message),
+            x => panic!("Unexpected result {:?}", x),
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use crate::events::row_events::mysql_value::{Date, DateTime, Decimal, MySqlValue, Time};
+
+/// Converts a cell of a row event to a Rust type.
+/// Integer cells are stored as raw bits, so signedness of the column is required to decode them.
+/// Errors are returned as messages, FromRow adds the column name.
+pub trait FromMySqlValue: Sized {
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String>;
+}
+
+/// Reads integer value of numeric, YEAR, ENUM and SET columns.
+fn read_integer(value: &MySqlValue, unsigned: bool) -> Option<i128> {
+    let value = match (value, unsigned) {
+        (MySqlValue::TinyInt(x), true) => *x as i128,
+        (MySqlValue::TinyInt(x), false) => *x as i8 as i128,
+        (MySqlValue::SmallInt(x), true) => *x as i128,
+        (MySqlValue::SmallInt(x), false) => *x as i16 as i128,
+        (MySqlValue::MediumInt(x), true) => *x as i128,
+        // Extend sign of 24-bit value
+        (MySqlValue::MediumInt(x), false) => ((*x << 8) as i32 >> 8) as i128,
+        (MySqlValue::Int(x), true) => *x as i128,
+        (MySqlValue::Int(x), false) => *x as i32 as i128,
+        (MySqlValue::BigInt(x), true) => *x as i128,
+        (MySqlValue::BigInt(x), false) => *x as i64 as i128,
+        (MySqlValue::Year(x), _) => *x as i128,
+        (MySqlValue::Enum(x), _) => *x as i128,
+        (MySqlValue::Set(x), _) => *x as i128,
+        _ => return None,
+    };
+    Some(value)
+}
+
+fn unexpected_value<T>(value: &MySqlValue, type_name: &str) -> Result<T, String> {
+    Err(format!("Cannot convert {:?} to {}", value, type_name))
+}
+
+fn not_null(value: Option<&MySqlValue>) -> Result<&MySqlValue, String> {
+    value.ok_or_else(|| "Unexpected NULL value".to_string())
+}
+
+macro_rules! impl_integer {
+    ($($type:ty),*) => {
+        $(
+            impl FromMySqlValue for $type {
+                fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+                    let value = not_null(value)?;
+                    let number = match read_integer(value, unsigned) {
+                        Some(x) => x,
+                        None => return unexpected_value(value, stringify!($type)),
+                    };
+                    <$type>::try_from(number)
+                        .map_err(|_| format!("Value {} is out of {} range", number, stringify!($type)))
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl FromMySqlValue for f64 {
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+        let value = not_null(value)?;
+        match value {
+            MySqlValue::Float(x) => Ok(*x as f64),
+            MySqlValue::Double(x) => Ok(*x),
+            _ => match read_integer(value, unsigned) {
+                Some(x) => Ok(x as f64),
+                None => unexpected_value(value, "f64"),
+            },
+        }
+    }
+}
+
+impl FromMySqlValue for f32 {
+    fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+        let value = not_null(value)?;
+        match value {
+            MySqlValue::Float(x) => Ok(*x),
+            _ => unexpected_value(value, "f32"),
+        }
+    }
+}
+
+impl FromMySqlValue for bool {
+    /// BOOLEAN columns are stored as TINYINT(1).
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+        let value = not_null(value)?;
+        match value {
+            MySqlValue::Bit(x) if x.len() == 1 => Ok(x[0]),
+            _ => match read_integer(value, unsigned) {
+                Some(x) => Ok(x != 0),
+                None => unexpected_value(value, "bool"),
+            },
+        }
+    }
+}
+
+impl FromMySqlValue for String {
+    fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+        let value = not_null(value)?;
+        match value {
+            MySqlValue::String(x) => Ok(x.clone()),
+            MySqlValue::Decimal(x) => Ok(x.to_string()),
+            MySqlValue::Blob(x) => String::from_utf8(x.clone()).map_err(|e| e.to_string()),
+            _ => unexpected_value(value, "String"),
+        }
+    }
+}
+
+impl FromMySqlValue for Vec<u8> {
+    fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+        let value = not_null(value)?;
+        match value {
+            MySqlValue::Blob(x) => Ok(x.clone()),
+            MySqlValue::String(x) => Ok(x.as_bytes().to_vec()),
+            _ => unexpected_value(value, "Vec<u8>"),
+        }
+    }
+}
+
+impl<T: FromMySqlValue> FromMySqlValue for Option<T> {
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+        match value {
+            Some(_) => Ok(Some(T::from_mysql_value(value, unsigned)?)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl FromMySqlValue for MySqlValue {
+    fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+        Ok(not_null(value)?.clone())
+    }
+}
+
+macro_rules! impl_variant {
+    ($type:ty, $variant:ident) => {
+        impl FromMySqlValue for $type {
+            fn from_mysql_value(
+                value: Option<&MySqlValue>,
+                _unsigned: bool,
+            ) -> Result<Self, String> {
+                match not_null(value)? {
+                    MySqlValue::$variant(x) => Ok(x.clone()),
+                    value => unexpected_value(value, stringify!($type)),
+                }
+            }
+        }
+    };
+}
+
+impl_variant!(Decimal, Decimal);
+impl_variant!(Date, Date);
+impl_variant!(Time, Time);
+impl_variant!(DateTime, DateTime);
+
+#[cfg(feature = "rust_decimal")]
+impl FromMySqlValue for rust_decimal::Decimal {
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+        let value = Decimal::from_mysql_value(value, unsigned)?;
+        rust_decimal::Decimal::try_from(&value).map_err(|e| format!("{:?}", e))
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl FromMySqlValue for bigdecimal::BigDecimal {
+    fn from_mysql_value(value: Option<&MySqlValue>, unsigned: bool) -> Result<Self, String> {
+        let value = Decimal::from_mysql_value(value, unsigned)?;
+        bigdecimal::BigDecimal::try_from(&value).map_err(|e| format!("{:?}", e))
+    }
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_values {
+    use super::{not_null, unexpected_value, FromMySqlValue};
+    use crate::events::row_events::chrono_types::timestamp_to_chrono;
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+    macro_rules! impl_chrono {
+        ($type:ty, $variant:ident) => {
+            impl FromMySqlValue for $type {
+                fn from_mysql_value(
+                    value: Option<&MySqlValue>,
+                    _unsigned: bool,
+                ) -> Result<Self, String> {
+                    match not_null(value)? {
+                        MySqlValue::$variant(x) => {
+                            <$type>::try_from(*x).map_err(|e| format!("{:?}", e))
+                        }
+                        value => unexpected_value(value, stringify!($type)),
+                    }
+                }
+            }
+        };
+    }
+
+    impl_chrono!(NaiveDate, Date);
+    impl_chrono!(NaiveTime, Time);
+    impl_chrono!(NaiveDateTime, DateTime);
+
+    impl FromMySqlValue for chrono::DateTime<Utc> {
+        fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+            match not_null(value)? {
+                MySqlValue::Timestamp(x) => timestamp_to_chrono(*x).map_err(|e| format!("{:?}", e)),
+                value => unexpected_value(value, "DateTime<Utc>"),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_values {
+    use super::{not_null, unexpected_value, FromMySqlValue};
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::events::row_events::time_types::timestamp_to_time;
+    use time::{OffsetDateTime, PrimitiveDateTime};
+
+    macro_rules! impl_time {
+        ($type:ty, $variant:ident) => {
+            impl FromMySqlValue for $type {
+                fn from_mysql_value(
+                    value: Option<&MySqlValue>,
+                    _unsigned: bool,
+                ) -> Result<Self, String> {
+                    match not_null(value)? {
+                        MySqlValue::$variant(x) => {
+                            <$type>::try_from(*x).map_err(|e| format!("{:?}", e))
+                        }
+                        value => unexpected_value(value, stringify!($type)),
+                    }
+                }
+            }
+        };
+    }
+
+    impl_time!(time::Date, Date);
+    impl_time!(time::Time, Time);
+    impl_time!(PrimitiveDateTime, DateTime);
+
+    impl FromMySqlValue for OffsetDateTime {
+        fn from_mysql_value(value: Option<&MySqlValue>, _unsigned: bool) -> Result<Self, String> {
+            match not_null(value)? {
+                MySqlValue::Timestamp(x) => timestamp_to_time(*x).map_err(|e| format!("{:?}", e)),
+                value => unexpected_value(value, "OffsetDateTime"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FromMySqlValue;
+    use crate::events::row_events::mysql_value::MySqlValue;
+
+    #[test]
+    fn convert_integers_by_signedness() {
+        let value = MySqlValue::TinyInt(0xFF);
+        assert_eq!(Ok(-1), i8::from_mysql_value(Some(&value), false));
+        assert_eq!(Ok(255), u8::from_mysql_value(Some(&value), true));
+        assert!(i8::from_mysql_value(Some(&value), true).is_err());
+
+        let value = MySqlValue::MediumInt(0xFFFFFE);
+        assert_eq!(Ok(-2), i32::from_mysql_value(Some(&value), false));
+        assert_eq!(Ok(0xFFFFFE), i64::from_mysql_value(Some(&value), true));
+
+        let value = MySqlValue::BigInt(u64::MAX);
+        assert_eq!(Ok(u64::MAX), u64::from_mysql_value(Some(&value), true));
+        assert!(i64::from_mysql_value(Some(&value), true).is_err());
+        assert!(u64::from_mysql_value(Some(&value), false).is_err());
+    }
+
+    #[test]
+    fn convert_nulls() {
+        assert_eq!(Ok(None), Option::<i32>::from_mysql_value(None, false));
+        assert!(i32::from_mysql_value(None, false).is_err());
+
+        let value = MySqlValue::String("text".to_string());
+        assert_eq!(
+            Ok(Some("text".to_string())),
+            Option::<String>::from_mysql_value(Some(&value), false)
+        );
+        assert!(i32::from_mysql_value(Some(&value), false).is_err());
+    }
+}
@@ -0,0 +1,288 @@
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::row_events::decimal::parse_decimal;
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use std::fmt::Write;
+use std::io::Cursor;
+
+/// Value types of MySQL binary JSON format.
+/// <a href="https://github.com/mysql/mysql-server/blob/8.0/sql-common/json_binary.h">See more</a>
+const SMALL_OBJECT: u8 = 0x00;
+const LARGE_OBJECT: u8 = 0x01;
+const SMALL_ARRAY: u8 = 0x02;
+const LARGE_ARRAY: u8 = 0x03;
+const LITERAL: u8 = 0x04;
+const INT16: u8 = 0x05;
+const UINT16: u8 = 0x06;
+const INT32: u8 = 0x07;
+const UINT32: u8 = 0x08;
+const INT64: u8 = 0x09;
+const UINT64: u8 = 0x0A;
+const DOUBLE: u8 = 0x0B;
+const STRING: u8 = 0x0C;
+const OPAQUE: u8 = 0x0F;
+
+const LITERAL_NULL: u8 = 0x00;
+const LITERAL_TRUE: u8 = 0x01;
+const LITERAL_FALSE: u8 = 0x02;
+
+/// Converts value of JSON column in MySQL binary format to JSON text.
+/// Keys are written in the order they are stored by the server, without whitespace like Debezium.
+pub fn decode_json(data: &[u8]) -> Result<String, Error> {
+    let mut result = String::new();
+    match data.split_first() {
+        Some((value_type, value)) => write_value(*value_type, value, &mut result)?,
+        // Empty value is written by the server for JSON null
+        None => result.push_str("null"),
+    }
+    Ok(result)
+}
+
+fn write_value(value_type: u8, data: &[u8], out: &mut String) -> Result<(), Error> {
+    match value_type {
+        SMALL_OBJECT => write_container(data, false, true, out)?,
+        LARGE_OBJECT => write_container(data, true, true, out)?,
+        SMALL_ARRAY => write_container(data, false, false, out)?,
+        LARGE_ARRAY => write_container(data, true, false, out)?,
+        LITERAL => write_literal(read_uint(data, 0, 1)? as u8, out)?,
+        INT16 => write!(out, "{}", read_uint(data, 0, 2)? as u16 as i16).unwrap(),
+        UINT16 => write!(out, "{}", read_uint(data, 0, 2)? as u16).unwrap(),
+        INT32 => write!(out, "{}", read_uint(data, 0, 4)? as u32 as i32).unwrap(),
+        UINT32 => write!(out, "{}", read_uint(data, 0, 4)? as u32).unwrap(),
+        INT64 => write!(out, "{}", read_uint(data, 0, 8)? as i64).unwrap(),
+        UINT64 => write!(out, "{}", read_uint(data, 0, 8)?).unwrap(),
+        DOUBLE => write!(out, "{:?}", f64::from_bits(read_uint(data, 0, 8)?)).unwrap(),
+        STRING => {
+            let (length, offset) = read_variable_length(data, 0)?;
+            let value = read_slice(data, offset, length)?;
+            write_string(&String::from_utf8_lossy(value), out);
+        }
+        OPAQUE => {
+            let column_type = read_uint(data, 0, 1)? as u8;
+            let (length, offset) = read_variable_length(data, 1)?;
+            write_opaque(column_type, read_slice(data, offset, length)?, out)?;
+        }
+        _ => return Err(invalid_json(&format!("value type {}", value_type))),
+    }
+    Ok(())
+}
+
+/// Objects and arrays have the same layout except keys.
+fn write_container(data: &[u8], large: bool, object: bool, out: &mut String) -> Result<(), Error> {
+    let offset_size = if large { 4 } else { 2 };
+    let count = read_uint(data, 0, offset_size)? as usize;
+    let key_entry_size = offset_size + 2;
+    let value_entry_size = 1 + offset_size;
+
+    let mut position = 2 * offset_size;
+    let mut keys = Vec::new();
+    if object {
+        for _i in 0..count {
+            let key_offset = read_uint(data, position, offset_size)? as usize;
+            let key_length = read_uint(data, position + offset_size, 2)? as usize;
+            keys.push(read_slice(data, key_offset, key_length)?);
+            position += key_entry_size;
+        }
+    }
+
+    out.push(if object { '{' } else { '[' });
+    for i in 0..count {
+        if i > 0 {
+            out.push(',');
+        }
+        if let Some(key) = keys.get(i) {
+            write_string(&String::from_utf8_lossy(key), out);
+            out.push(':');
+        }
+
+        let value_type = read_uint(data, position, 1)? as u8;
+        let entry = read_uint(data, position + 1, offset_size)?;
+        match value_type {
+            // Small values are inlined in the value entry
+            LITERAL => write_literal(entry as u8, out)?,
+            INT16 => write!(out, "{}", entry as u16 as i16).unwrap(),
+            UINT16 => write!(out, "{}", entry as u16).unwrap(),
+            INT32 if large => write!(out, "{}", entry as u32 as i32).unwrap(),
+            UINT32 if large => write!(out, "{}", entry as u32).unwrap(),
+            _ => {
+                let value = data
+                    .get(entry as usize..)
+                    .ok_or_else(|| invalid_json("value offset"))?;
+                write_value(value_type, value, out)?;
+            }
+        }
+        position += value_entry_size;
+    }
+    out.push(if object { '}' } else { ']' });
+    Ok(())
+}
+
+fn write_literal(value: u8, out: &mut String) -> Result<(), Error> {
+    match value {
+        LITERAL_NULL => out.push_str("null"),
+        LITERAL_TRUE => out.push_str("true"),
+        LITERAL_FALSE => out.push_str("false"),
+        _ => return Err(invalid_json(&format!("literal {}", value))),
+    }
+    Ok(())
+}
+
+/// Opaque values keep MySQL types that don't exist in JSON.
+fn write_opaque(column_type: u8, data: &[u8], out: &mut String) -> Result<(), Error> {
+    match ColumnType::from_code(column_type) {
+        Ok(ColumnType::NewDecimal) => {
+            let precision = read_uint(data, 0, 1)? as u16;
+            let scale = read_uint(data, 1, 1)? as u16;
+            let mut cursor = Cursor::new(&data[2..]);
+            let value = parse_decimal(&mut cursor, (scale << 8) | precision)?;
+            write!(out, "{}", value).unwrap();
+        }
+        Ok(ColumnType::Date) => {
+            let (year, month, day, _, _, _, _) = unpack_datetime(read_uint(data, 0, 8)? as i64);
+            write!(out, "\"{:04}-{:02}-{:02}\"", year, month, day).unwrap();
+        }
+        Ok(ColumnType::DateTime | ColumnType::TimeStamp) => {
+            let (year, month, day, hour, minute, second, micros) =
+                unpack_datetime(read_uint(data, 0, 8)? as i64);
+            write!(
+                out,
+                "\"{:04}-{:02}-{:02} {:02}:{:02}:{:02}.{:06}\"",
+                year, month, day, hour, minute, second, micros
+            )
+            .unwrap();
+        }
+        Ok(ColumnType::Time) => {
+            let packed = read_uint(data, 0, 8)? as i64;
+            let sign = if packed < 0 { "-" } else { "" };
+            let value = packed.unsigned_abs();
+            let hms = value >> 24;
+            write!(
+                out,
+                "\"{}{:02}:{:02}:{:02}.{:06}\"",
+                sign,
+                (hms >> 12) % (1 << 10),
+                (hms >> 6) % (1 << 6),
+                hms % (1 << 6),
+                value % (1 << 24)
+            )
+            .unwrap();
+        }
+        _ => {
+            let value = STANDARD.encode(data);
+            write!(out, "\"base64:type{}:{}\"", column_type, value).unwrap();
+        }
+    }
+    Ok(())
+}
+
+/// Unpacks DATETIME stored as integer in MySQL server.
+fn unpack_datetime(packed: i64) -> (u64, u64, u64, u64, u64, u64, u64) {
+    let value = packed.unsigned_abs();
+    let micros = value % (1 << 24);
+    let ymdhms = value >> 24;
+    let ymd = ymdhms >> 17;
+    let year_month = ymd >> 5;
+    let hms = ymdhms % (1 << 17);
+    (
+        year_month / 13,
+        year_month % 13,
+        ymd % (1 << 5),
+        hms >> 12,
+        (hms >> 6) % (1 << 6),
+        hms % (1 << 6),
+        micros,
+    )
+}
+
+fn write_string(value: &str, out: &mut String) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0C}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+/// Reads little-endian unsigned integer of the size.
+fn read_uint(data: &[u8], offset: usize, size: usize) -> Result<u64, Error> {
+    let bytes = read_slice(data, offset, size)?;
+    let mut result = 0u64;
+    for (i, byte) in bytes.iter().enumerate() {
+        result |= (*byte as u64) << (8 * i);
+    }
+    Ok(result)
+}
+
+/// Reads length stored in 7 bits per byte. Returns the length and offset of the data.
+fn read_variable_length(data: &[u8], mut offset: usize) -> Result<(usize, usize), Error> {
+    let mut length = 0usize;
+    for i in 0..5 {
+        let byte = read_uint(data, offset, 1)? as usize;
+        offset += 1;
+        length |= (byte & 0x7F) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok((length, offset));
+        }
+    }
+    Err(invalid_json("variable length"))
+}
+
+fn read_slice(data: &[u8], offset: usize, length: usize) -> Result<&[u8], Error> {
+    data.get(offset..offset + length)
+        .ok_or_else(|| invalid_json("offset"))
+}
+
+fn invalid_json(message: &str) -> Error {
+    Error::String(format!("Invalid binary JSON {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::decode_json;
+
+    #[test]
+    fn decode_scalars() {
+        assert_eq!("null", decode_json(&[]).unwrap());
+        assert_eq!("true", decode_json(&[0x04, 0x01]).unwrap());
+        assert_eq!("-2", decode_json(&[0x05, 0xFE, 0xFF]).unwrap());
+        assert_eq!(
+            "1.5",
+            decode_json(&[0x0B, 0, 0, 0, 0, 0, 0, 0xF8, 0x3F]).unwrap()
+        );
+        assert_eq!(
+            "\"a\\\"b\"",
+            decode_json(&[0x0C, 0x03, b'a', b'"', b'b']).unwrap()
+        );
+    }
+
+    #[test]
+    fn decode_small_object() {
+        // {"a": 1, "b": [true, "x"]}
+        let data = [
+            0x00, // small object
+            0x02, 0x00, // element count
+            0x1C, 0x00, // size
+            0x12, 0x00, 0x01, 0x00, // key "a"
+            0x13, 0x00, 0x01, 0x00, // key "b"
+            0x05, 0x01, 0x00, // inlined int16 1
+            0x02, 0x14, 0x00, // small array at offset 20
+            b'a', b'b', // keys
+            0x02, 0x00, // array element count
+            0x0C, 0x00, // array size
+            0x04, 0x01, 0x00, // inlined true
+            0x0C, 0x0A, 0x00, // string at offset 10 of the array
+            0x01, b'x',
+        ];
+        assert_eq!(r#"{"a":1,"b":[true,"x"]}"#, decode_json(&data).unwrap());
+    }
+}
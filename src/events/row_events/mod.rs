@@ -1,10 +1,24 @@
 pub mod delete_rows_event;
+pub mod from_row;
+pub mod from_value;
 pub mod mysql_value;
 pub mod row_data;
 pub mod update_rows_event;
 pub mod write_rows_event;
 
-mod actual_string_type;
+#[cfg(feature = "chrono")]
+pub mod chrono_types;
+#[cfg(feature = "time")]
+pub mod time_types;
+
+pub(crate) mod actual_string_type;
 mod col_parser;
-mod decimal;
+pub(crate) mod decimal;
+#[cfg(any(
+    feature = "debezium",
+    feature = "avro",
+    feature = "arrow",
+    feature = "sql"
+))]
+pub(crate) mod json_binary;
 mod row_parser;
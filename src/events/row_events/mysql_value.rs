@@ -1,11 +1,215 @@
-#[derive(Debug)]
+use crate::errors::Error;
+use std::fmt;
+
+/// Exact DECIMAL value keeping precision and scale of the column.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Decimal {
+    /// Gets whether the value is negative.
+    pub negative: bool,
+
+    /// Gets integral digits without leading zeros. Zero is written as "0".
+    pub integral: String,
+
+    /// Gets fractional digits. The number of digits equals the scale.
+    pub fractional: String,
+
+    /// Gets precision, the maximum number of digits.
+    pub precision: u8,
+
+    /// Gets scale, the number of digits after the decimal point.
+    pub scale: u8,
+}
+
+impl Decimal {
+    /// Parses decimal from string representation like -123.45.
+    /// Precision and scale are derived from the number of digits.
+    pub fn parse(value: &str) -> Result<Self, Error> {
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(x) => (true, x),
+            None => (false, value),
+        };
+        let (integral, fractional) = digits.split_once('.').unwrap_or((digits, ""));
+
+        let is_digits = |x: &str| x.chars().all(|c| c.is_ascii_digit());
+        if integral.is_empty() || !is_digits(integral) || !is_digits(fractional) {
+            return Err(Error::String(format!("Invalid decimal format {}", value)));
+        }
+
+        let integral = match integral.trim_start_matches('0') {
+            "" => "0",
+            x => x,
+        };
+        let integral_digits = if integral == "0" { 0 } else { integral.len() };
+        let precision = (integral_digits + fractional.len()).max(1);
+        if precision > 65 || fractional.len() > 30 {
+            return Err(Error::String(format!("Decimal {} is out of range", value)));
+        }
+
+        Ok(Self {
+            negative,
+            integral: integral.to_string(),
+            fractional: fractional.to_string(),
+            precision: precision as u8,
+            scale: fractional.len() as u8,
+        })
+    }
+
+    /// Returns unscaled value as big-endian two's complement.
+    /// The format is used by Avro, Parquet and Kafka Connect decimals.
+    pub fn to_unscaled_bytes(&self) -> Vec<u8> {
+        let mut magnitude = vec![0u8];
+        for digit in self.integral.bytes().chain(self.fractional.bytes()) {
+            let mut carry = (digit - b'0') as u32;
+            for byte in magnitude.iter_mut().rev() {
+                let result = *byte as u32 * 10 + carry;
+                *byte = result as u8;
+                carry = result >> 8;
+            }
+            if carry > 0 {
+                magnitude.insert(0, carry as u8);
+            }
+        }
+
+        // Keep one leading zero byte for the sign bit
+        while magnitude.len() > 1 && magnitude[0] == 0 && magnitude[1] & 0x80 == 0 {
+            magnitude.remove(0);
+        }
+        if magnitude[0] & 0x80 != 0 {
+            magnitude.insert(0, 0);
+        }
+
+        let is_zero = magnitude.iter().all(|x| *x == 0);
+        if !self.negative || is_zero {
+            return magnitude;
+        }
+
+        let mut carry = true;
+        for byte in magnitude.iter_mut().rev() {
+            let (result, overflow) = (!*byte).overflowing_add(carry as u8);
+            *byte = result;
+            carry = overflow;
+        }
+        while magnitude.len() > 1 && magnitude[0] == 0xFF && magnitude[1] & 0x80 != 0 {
+            magnitude.remove(0);
+        }
+        magnitude
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Returns string representation of the decimal in MySQL Server.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-")?;
+        }
+        write!(f, "{}", self.integral)?;
+        if !self.fractional.is_empty() {
+            write!(f, ".{}", self.fractional)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl TryFrom<&Decimal> for rust_decimal::Decimal {
+    type Error = Error;
+
+    /// Fails if the value doesn't fit in 96-bit mantissa or scale is greater than 28.
+    fn try_from(value: &Decimal) -> Result<Self, Self::Error> {
+        rust_decimal::Decimal::from_str_exact(&value.to_string())
+            .map_err(|e| Error::String(format!("Cannot convert decimal {}: {}", value, e)))
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl TryFrom<&Decimal> for bigdecimal::BigDecimal {
+    type Error = Error;
+
+    fn try_from(value: &Decimal) -> Result<Self, Self::Error> {
+        use std::str::FromStr;
+        bigdecimal::BigDecimal::from_str(&value.to_string())
+            .map_err(|e| Error::String(format!("Cannot convert decimal {}: {}", value, e)))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Date {
     pub year: u16,
     pub month: u8,
     pub day: u8,
 }
 
-#[derive(Debug)]
+impl Date {
+    /// Returns true for 0000-00-00 value allowed in MySQL without NO_ZERO_DATE mode.
+    pub fn is_zero(&self) -> bool {
+        self.year == 0 && self.month == 0 && self.day == 0
+    }
+
+    /// Returns true for dates like 2020-00-00 allowed without NO_ZERO_IN_DATE mode.
+    pub fn is_partial(&self) -> bool {
+        !self.is_zero() && (self.month == 0 || self.day == 0)
+    }
+
+    /// Returns error for zero and partial dates that cannot be represented by date libraries.
+    pub fn check_complete(&self) -> Result<(), Error> {
+        if self.is_zero() {
+            return Err(Error::String(format!(
+                "Zero date {} is not supported",
+                self
+            )));
+        }
+        if self.is_partial() {
+            return Err(Error::String(format!(
+                "Partial date {} is not supported",
+                self
+            )));
+        }
+        Ok(())
+    }
+
+    /// Returns number of days since 1970-01-01.
+    /// <a href="http://howardhinnant.github.io/date_algorithms.html#days_from_civil">See more</a>
+    pub fn to_epoch_days(&self) -> i64 {
+        let year = self.year as i64 - if self.month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year =
+            (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146097 + day_of_era - 719468
+    }
+
+    /// Creates date from number of days since 1970-01-01.
+    /// <a href="http://howardhinnant.github.io/date_algorithms.html#civil_from_days">See more</a>
+    pub fn from_epoch_days(days: i64) -> Self {
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u8;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u8;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Self {
+            year: year as u16,
+            month,
+            day,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Time {
     pub hour: i16, // Signed value from -838 to 838
     pub minute: u8,
@@ -13,7 +217,26 @@ pub struct Time {
     pub millis: u32,
 }
 
-#[derive(Debug)]
+impl Time {
+    /// Returns the time as microseconds. Can be negative or exceed 24 hours.
+    pub fn to_micros(&self) -> i64 {
+        let seconds = (self.hour as i64 * 60 + self.minute as i64) * 60 + self.second as i64;
+        seconds * 1_000_000 + self.millis as i64 * 1000
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03}",
+            self.hour, self.minute, self.second, self.millis
+        )
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DateTime {
     pub year: u16,
     pub month: u8,
@@ -24,7 +247,59 @@ pub struct DateTime {
     pub millis: u32,
 }
 
-#[derive(Debug)]
+impl DateTime {
+    /// Returns date part of the value.
+    pub fn date(&self) -> Date {
+        Date {
+            year: self.year,
+            month: self.month,
+            day: self.day,
+        }
+    }
+
+    /// Returns microseconds since 1970-01-01 00:00:00 treating the value as UTC.
+    pub fn to_epoch_micros(&self) -> i64 {
+        let seconds = self.date().to_epoch_days() * 86400
+            + self.hour as i64 * 3600
+            + self.minute as i64 * 60
+            + self.second as i64;
+        seconds * 1_000_000 + self.millis as i64 * 1000
+    }
+
+    /// Creates value from microseconds since 1970-01-01 00:00:00 UTC.
+    /// Fractional part is truncated to milliseconds.
+    pub fn from_epoch_micros(micros: i64) -> Self {
+        let seconds = micros.div_euclid(1_000_000);
+        let date = Date::from_epoch_days(seconds.div_euclid(86400));
+        let time = seconds.rem_euclid(86400);
+        Self {
+            year: date.year,
+            month: date.month,
+            day: date.day,
+            hour: (time / 3600) as u8,
+            minute: (time % 3600 / 60) as u8,
+            second: (time % 60) as u8,
+            millis: (micros.rem_euclid(1_000_000) / 1000) as u32,
+        }
+    }
+}
+
+impl fmt::Display for DateTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {:02}:{:02}:{:02}.{:03}",
+            self.date(),
+            self.hour,
+            self.minute,
+            self.second,
+            self.millis
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum MySqlValue {
     TinyInt(u8),
     SmallInt(u16),
@@ -33,15 +308,54 @@ pub enum MySqlValue {
     BigInt(u64),
     Float(f32),
     Double(f64),
-    Decimal(String),
+    Decimal(Decimal),
     String(String),
     Bit(Vec<bool>),
     Enum(u32),
     Set(u64),
-    Blob(Vec<u8>),
+    Blob(#[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))] Vec<u8>),
     Year(u16),
     Date(Date),
     Time(Time),
     DateTime(DateTime),
     Timestamp(u64), // millis from unix time
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{Date, DateTime, Decimal};
+
+    #[test]
+    fn unscaled_bytes_match_java_big_integer() {
+        let bytes = |x: &str| Decimal::parse(x).unwrap().to_unscaled_bytes();
+        assert_eq!(vec![0x00], bytes("0.00"));
+        assert_eq!(vec![0x30, 0x39], bytes("123.45"));
+        assert_eq!(vec![0xCF, 0xC7], bytes("-123.45"));
+        assert_eq!(vec![0x00, 0x80], bytes("1.28"));
+        assert_eq!(vec![0x80], bytes("-1.28"));
+        assert_eq!(vec![0xFF, 0x7F], bytes("-1.29"));
+    }
+
+    #[test]
+    fn convert_to_epoch() {
+        let date = |year, month, day| Date { year, month, day };
+        assert_eq!(0, date(1970, 1, 1).to_epoch_days());
+        assert_eq!(-1, date(1969, 12, 31).to_epoch_days());
+        assert_eq!(19723, date(2024, 1, 1).to_epoch_days());
+        assert_eq!(date(1970, 1, 1), Date::from_epoch_days(0));
+        assert_eq!(date(1969, 12, 31), Date::from_epoch_days(-1));
+        assert_eq!(date(2024, 2, 29), Date::from_epoch_days(19782));
+
+        let value = DateTime {
+            year: 2024,
+            month: 1,
+            day: 1,
+            hour: 10,
+            minute: 20,
+            second: 30,
+            millis: 123,
+        };
+        assert_eq!(1704104430123000, value.to_epoch_micros());
+        assert_eq!(value, DateTime::from_epoch_micros(1704104430123456));
+    }
+}
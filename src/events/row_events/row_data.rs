@@ -2,6 +2,7 @@ use crate::events::row_events::mysql_value::MySqlValue;
 
 /// Represents an inserted or deleted row in row based replication.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RowData {
     /// Column values of the changed row.
     pub cells: Vec<Option<MySqlValue>>,
@@ -15,6 +16,7 @@ impl RowData {
 
 /// Represents an updated row in row based replication.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UpdateRowData {
     /// Row state before it was updated.
     pub before_update: RowData,
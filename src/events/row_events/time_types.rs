@@ -0,0 +1,115 @@
+use crate::errors::Error;
+use crate::events::row_events::mysql_value::{Date, DateTime, Time};
+use time::{Duration, Month, OffsetDateTime, PrimitiveDateTime, UtcOffset};
+
+impl TryFrom<Date> for time::Date {
+    type Error = Error;
+
+    /// Fails for zero and partial dates.
+    fn try_from(value: Date) -> Result<Self, Self::Error> {
+        value.check_complete()?;
+        Month::try_from(value.month)
+            .and_then(|month| time::Date::from_calendar_date(value.year as i32, month, value.day))
+            .map_err(|_| Error::String(format!("Invalid date {}", value)))
+    }
+}
+
+impl TryFrom<Time> for time::Time {
+    type Error = Error;
+
+    /// Fails for TIME values outside of 00:00:00 - 23:59:59 range.
+    /// Use Duration conversion for time intervals.
+    fn try_from(value: Time) -> Result<Self, Self::Error> {
+        let hour = u8::try_from(value.hour)
+            .map_err(|_| Error::String(format!("Time {} is not a time of day", value)))?;
+        time::Time::from_hms_milli(hour, value.minute, value.second, value.millis as u16)
+            .map_err(|_| Error::String(format!("Time {} is not a time of day", value)))
+    }
+}
+
+impl From<Time> for Duration {
+    fn from(value: Time) -> Self {
+        let seconds = value.hour.unsigned_abs() as i64 * 3600
+            + value.minute as i64 * 60
+            + value.second as i64;
+        let millis = seconds * 1000 + value.millis as i64;
+        Duration::milliseconds(if value.hour < 0 { -millis } else { millis })
+    }
+}
+
+impl TryFrom<DateTime> for PrimitiveDateTime {
+    type Error = Error;
+
+    /// Fails for zero and partial dates.
+    fn try_from(value: DateTime) -> Result<Self, Self::Error> {
+        let date = time::Date::try_from(value.date())?;
+        let time =
+            time::Time::from_hms_milli(value.hour, value.minute, value.second, value.millis as u16)
+                .map_err(|_| Error::String(format!("Invalid datetime {}", value)))?;
+        Ok(PrimitiveDateTime::new(date, time))
+    }
+}
+
+impl DateTime {
+    /// DATETIME values don't store time zone.
+    /// Interprets the value with the offset the server writes DATETIME values in.
+    pub fn to_time_in(&self, offset: UtcOffset) -> Result<OffsetDateTime, Error> {
+        Ok(PrimitiveDateTime::try_from(*self)?.assume_offset(offset))
+    }
+}
+
+/// Converts TIMESTAMP value which is always stored in UTC.
+pub fn timestamp_to_time(millis: u64) -> Result<OffsetDateTime, Error> {
+    OffsetDateTime::from_unix_timestamp_nanos(millis as i128 * 1_000_000)
+        .map_err(|_| Error::String(format!("Invalid timestamp {}", millis)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::timestamp_to_time;
+    use crate::events::row_events::mysql_value::{Date, DateTime, Time};
+    use time::{Duration, UtcOffset};
+
+    #[test]
+    fn convert_dates() {
+        let date = Date {
+            year: 2022,
+            month: 2,
+            day: 29,
+        };
+        assert!(time::Date::try_from(date).is_err());
+
+        let date = Date {
+            year: 0,
+            month: 0,
+            day: 0,
+        };
+        assert!(time::Date::try_from(date).is_err());
+    }
+
+    #[test]
+    fn convert_date_time_with_offset() {
+        let date_time = DateTime {
+            year: 2022,
+            month: 2,
+            day: 28,
+            hour: 10,
+            minute: 20,
+            second: 30,
+            millis: 400,
+        };
+        let offset = UtcOffset::from_hms(3, 0, 0).unwrap();
+        let value = date_time.to_time_in(offset).unwrap();
+
+        assert_eq!(timestamp_to_time(1_646_032_830_400).unwrap(), value);
+
+        let time = Time {
+            hour: 100,
+            minute: 0,
+            second: 0,
+            millis: 0,
+        };
+        assert!(time::Time::try_from(time).is_err());
+        assert_eq!(Duration::hours(100), Duration::from(time));
+    }
+}
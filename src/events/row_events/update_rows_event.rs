@@ -3,6 +3,7 @@ use crate::events::row_events::row_data::UpdateRowData;
 use crate::events::row_events::row_parser::{parse_head, parse_update_row_data_list};
 use crate::events::table_map_event::TableMapEvent;
 use crate::extensions::read_bitmap_little_endian;
+use crate::providers::mariadb::compression::uncompress_event_data;
 use std::collections::HashMap;
 use std::io::Cursor;
 
@@ -10,6 +11,7 @@ use std::io::Cursor;
 /// Includes versions before and after update.
 /// <a href="https://mariadb.com/kb/en/library/rows_event_v1/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UpdateRowsEvent {
     /// Gets id of the table where rows were updated
     pub table_id: u64,
@@ -36,17 +38,46 @@ impl UpdateRowsEvent {
         cursor: &mut Cursor<&[u8]>,
         table_map: &HashMap<u64, TableMapEvent>,
         row_event_version: u8,
+    ) -> Result<Self, Error> {
+        Self::parse_event(cursor, table_map, row_event_version, false)
+    }
+
+    /// Supports MariaDB 10.2.3+ compressed row events (V1 and V2 row events).
+    pub fn parse_compressed(
+        cursor: &mut Cursor<&[u8]>,
+        table_map: &HashMap<u64, TableMapEvent>,
+        row_event_version: u8,
+    ) -> Result<Self, Error> {
+        Self::parse_event(cursor, table_map, row_event_version, true)
+    }
+
+    fn parse_event(
+        cursor: &mut Cursor<&[u8]>,
+        table_map: &HashMap<u64, TableMapEvent>,
+        row_event_version: u8,
+        compressed: bool,
     ) -> Result<Self, Error> {
         let (table_id, flags, columns_number) = parse_head(cursor, row_event_version)?;
         let columns_before_update = read_bitmap_little_endian(cursor, columns_number)?;
         let columns_after_update = read_bitmap_little_endian(cursor, columns_number)?;
-        let rows = parse_update_row_data_list(
-            cursor,
-            table_map,
-            table_id,
-            &columns_before_update,
-            &columns_after_update,
-        )?;
+        let rows = if compressed {
+            let rows_data = uncompress_event_data(cursor)?;
+            parse_update_row_data_list(
+                &mut Cursor::new(rows_data.as_slice()),
+                table_map,
+                table_id,
+                &columns_before_update,
+                &columns_after_update,
+            )?
+        } else {
+            parse_update_row_data_list(
+                cursor,
+                table_map,
+                table_id,
+                &columns_before_update,
+                &columns_after_update,
+            )?
+        };
         Ok(Self {
             table_id,
             flags,
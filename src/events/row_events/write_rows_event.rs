@@ -3,12 +3,14 @@ use crate::events::row_events::row_data::RowData;
 use crate::events::row_events::row_parser::{parse_head, parse_row_data_list};
 use crate::events::table_map_event::TableMapEvent;
 use crate::extensions::read_bitmap_little_endian;
+use crate::providers::mariadb::compression::uncompress_event_data;
 use std::collections::HashMap;
 use std::io::Cursor;
 
 /// Represents one or many inserted rows in row based replication.
 /// <a href="https://mariadb.com/kb/en/library/rows_event_v1/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WriteRowsEvent {
     /// Gets id of the table where rows were inserted
     pub table_id: u64,
@@ -32,10 +34,34 @@ impl WriteRowsEvent {
         cursor: &mut Cursor<&[u8]>,
         table_map: &HashMap<u64, TableMapEvent>,
         row_event_version: u8,
+    ) -> Result<Self, Error> {
+        Self::parse_event(cursor, table_map, row_event_version, false)
+    }
+
+    /// Supports MariaDB 10.2.3+ compressed row events (V1 and V2 row events).
+    pub fn parse_compressed(
+        cursor: &mut Cursor<&[u8]>,
+        table_map: &HashMap<u64, TableMapEvent>,
+        row_event_version: u8,
+    ) -> Result<Self, Error> {
+        Self::parse_event(cursor, table_map, row_event_version, true)
+    }
+
+    fn parse_event(
+        cursor: &mut Cursor<&[u8]>,
+        table_map: &HashMap<u64, TableMapEvent>,
+        row_event_version: u8,
+        compressed: bool,
     ) -> Result<Self, Error> {
         let (table_id, flags, columns_number) = parse_head(cursor, row_event_version)?;
         let columns_present = read_bitmap_little_endian(cursor, columns_number)?;
-        let rows = parse_row_data_list(cursor, table_map, table_id, &columns_present)?;
+        let rows = if compressed {
+            let rows_data = uncompress_event_data(cursor)?;
+            let mut cursor = Cursor::new(rows_data.as_slice());
+            parse_row_data_list(&mut cursor, table_map, table_id, &columns_present)?
+        } else {
+            parse_row_data_list(cursor, table_map, table_id, &columns_present)?
+        };
         Ok(Self {
             table_id,
             flags,
@@ -6,6 +6,7 @@ use crate::errors::Error;
 /// See <a href="https://dev.mysql.com/doc/internals/en/rows-query-event.html">MySQL docs</a>
 /// See <a href="https://mariadb.com/kb/en/annotate_rows_event/">MariaDB docs</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RowsQueryEvent {
     /// Gets SQL statement
     pub query: String,
@@ -1,13 +1,25 @@
 use crate::constants::column_type::ColumnType;
 use crate::errors::Error;
+use crate::events::row_events::actual_string_type::get_actual_string_type;
 use crate::extensions::{read_bitmap_little_endian, read_len_enc_num, read_string};
-use crate::metadata::table_metadata::TableMetadata;
+use crate::metadata::table_metadata::{
+    is_character_column, is_enum_or_set_column, is_numeric_column, TableMetadata,
+};
 use byteorder::{BigEndian, LittleEndian, ReadBytesExt};
 use std::io::{Cursor, Read, Seek, SeekFrom};
 
+#[cfg(any(
+    feature = "debezium",
+    feature = "avro",
+    feature = "arrow",
+    feature = "sql"
+))]
+const BINARY_COLLATION: u32 = 63;
+
 /// The event has table defition for row events.
 /// <a href="https://mariadb.com/kb/en/library/table_map_event/">See more</a>
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableMapEvent {
     /// Gets id of the changed table
     pub table_id: u64,
@@ -31,6 +43,38 @@ pub struct TableMapEvent {
     pub table_metadata: Option<TableMetadata>,
 }
 
+/// Position of the column among columns of the same kind.
+/// Table metadata lists signedness, charsets and ENUM/SET values only for columns of the kind.
+#[derive(Clone, Copy, Debug, Default)]
+pub(crate) struct ColumnOrdinals {
+    numeric: usize,
+    character: usize,
+    enum_or_set: usize,
+    enums: usize,
+    sets: usize,
+}
+
+impl ColumnOrdinals {
+    /// Counts the column of the type and actual type.
+    fn add(&mut self, column_type: u8, actual_type: u8) {
+        if is_numeric_column(column_type) {
+            self.numeric += 1;
+        }
+        if is_character_column(actual_type) {
+            self.character += 1;
+        }
+        if is_enum_or_set_column(actual_type) {
+            self.enum_or_set += 1;
+        }
+        if actual_type == ColumnType::Enum as u8 {
+            self.enums += 1;
+        }
+        if actual_type == ColumnType::Set as u8 {
+            self.sets += 1;
+        }
+    }
+}
+
 impl TableMapEvent {
     /// Supports all versions of MariaDB and MySQL 5.0+.
     pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
@@ -75,6 +119,172 @@ impl TableMapEvent {
         })
     }
 
+    /// Returns true if the numeric column is UNSIGNED.
+    /// Returns None for other columns or if the server didn't send signedness metadata.
+    pub fn is_unsigned(&self, column_index: usize) -> Option<bool> {
+        self.is_unsigned_at(column_index, &self.ordinals(column_index))
+    }
+
+    pub(crate) fn is_unsigned_at(
+        &self,
+        column_index: usize,
+        ordinals: &ColumnOrdinals,
+    ) -> Option<bool> {
+        let signedness = self.table_metadata.as_ref()?.signedness.as_ref()?;
+        if !is_numeric_column(*self.column_types.get(column_index)?) {
+            return None;
+        }
+        signedness.get(ordinals.numeric).copied()
+    }
+
+    /// Returns real column type and metadata.
+    /// ENUM, SET and long CHAR columns are sent as STRING column type.
+    pub fn actual_column_type(&self, column_index: usize) -> (u8, u16) {
+        let mut column_type = self.column_types[column_index];
+        let mut metadata = self.column_metadata[column_index];
+        if column_type == ColumnType::String as u8 {
+            get_actual_string_type(&mut column_type, &mut metadata);
+        }
+        (column_type, metadata)
+    }
+
+    /// Returns collation id of the character column.
+    /// Binary columns like BLOB, BINARY have binary collation 63.
+    /// Returns None for other columns or if the server didn't send charset metadata.
+    pub fn column_collation(&self, column_index: usize) -> Option<u32> {
+        self.column_collation_at(column_index, &self.ordinals(column_index))
+    }
+
+    pub(crate) fn column_collation_at(
+        &self,
+        column_index: usize,
+        ordinals: &ColumnOrdinals,
+    ) -> Option<u32> {
+        let metadata = self.table_metadata.as_ref()?;
+        let (column_type, _) = self.actual_column_type(column_index);
+        let index = if is_character_column(column_type) {
+            ordinals.character
+        } else if is_enum_or_set_column(column_type) {
+            ordinals.enum_or_set
+        } else {
+            return None;
+        };
+
+        let (default_charset, column_charsets) = match is_character_column(column_type) {
+            true => (&metadata.default_charset, &metadata.column_charsets),
+            false => (
+                &metadata.enum_and_set_default_charset,
+                &metadata.enum_and_set_column_charsets,
+            ),
+        };
+        if let Some(charset) = default_charset {
+            let collation = charset
+                .charset_collations
+                .iter()
+                .find(|(column, _)| *column as usize == index)
+                .map(|(_, collation)| *collation);
+            return Some(collation.unwrap_or(charset.default_charset_collation));
+        }
+        column_charsets.as_ref()?.get(index).copied()
+    }
+
+    /// Returns string values of ENUM column.
+    pub fn enum_values(&self, column_index: usize) -> Option<&Vec<String>> {
+        self.enum_values_at(column_index, &self.ordinals(column_index))
+    }
+
+    pub(crate) fn enum_values_at(
+        &self,
+        column_index: usize,
+        ordinals: &ColumnOrdinals,
+    ) -> Option<&Vec<String>> {
+        if self.actual_column_type(column_index).0 != ColumnType::Enum as u8 {
+            return None;
+        }
+        let values = self.table_metadata.as_ref()?.enum_string_values.as_ref()?;
+        values.get(ordinals.enums)
+    }
+
+    /// Returns string values of SET column.
+    pub fn set_values(&self, column_index: usize) -> Option<&Vec<String>> {
+        self.set_values_at(column_index, &self.ordinals(column_index))
+    }
+
+    pub(crate) fn set_values_at(
+        &self,
+        column_index: usize,
+        ordinals: &ColumnOrdinals,
+    ) -> Option<&Vec<String>> {
+        if self.actual_column_type(column_index).0 != ColumnType::Set as u8 {
+            return None;
+        }
+        let values = self.table_metadata.as_ref()?.set_string_values.as_ref()?;
+        values.get(ordinals.sets)
+    }
+
+    /// Returns true if the character or blob column has binary collation.
+    /// Character columns without charset metadata are treated as binary to keep the data.
+    #[cfg(any(feature = "avro", feature = "arrow", feature = "sql"))]
+    pub(crate) fn is_binary_column(&self, column_index: usize) -> bool {
+        self.is_binary_column_at(column_index, &self.ordinals(column_index))
+    }
+
+    #[cfg(any(
+        feature = "debezium",
+        feature = "avro",
+        feature = "arrow",
+        feature = "sql"
+    ))]
+    pub(crate) fn is_binary_column_at(
+        &self,
+        column_index: usize,
+        ordinals: &ColumnOrdinals,
+    ) -> bool {
+        match self.column_collation_at(column_index, ordinals) {
+            Some(collation) => collation == BINARY_COLLATION,
+            None => !matches!(
+                ColumnType::from_code(self.actual_column_type(column_index).0),
+                Ok(ColumnType::String | ColumnType::VarChar | ColumnType::VarString)
+            ),
+        }
+    }
+
+    /// Returns string value of ENUM column by 1-based index.
+    /// Zero index is used for invalid empty value. Index is returned if values are unknown.
+    #[cfg(any(feature = "avro", feature = "arrow", feature = "sql"))]
+    pub(crate) fn enum_string(&self, column_index: usize, index: u32) -> String {
+        enum_string(self.enum_values(column_index), index)
+    }
+
+    /// Returns comma separated values of SET column bitmap.
+    /// Bitmap is returned if values are unknown.
+    #[cfg(any(feature = "avro", feature = "arrow", feature = "sql"))]
+    pub(crate) fn set_string(&self, column_index: usize, bitmap: u64) -> String {
+        set_string(self.set_values(column_index), bitmap)
+    }
+
+    /// Returns ordinals of all columns computed in one pass.
+    #[cfg(feature = "debezium")]
+    pub(crate) fn column_ordinals(&self) -> Vec<ColumnOrdinals> {
+        let mut ordinals = ColumnOrdinals::default();
+        (0..self.column_types.len())
+            .map(|x| {
+                let result = ordinals;
+                ordinals.add(self.column_types[x], self.actual_column_type(x).0);
+                result
+            })
+            .collect()
+    }
+
+    /// Counts preceding columns of each kind.
+    fn ordinals(&self, column_index: usize) -> ColumnOrdinals {
+        let mut ordinals = ColumnOrdinals::default();
+        for x in 0..column_index.min(self.column_types.len()) {
+            ordinals.add(self.column_types[x], self.actual_column_type(x).0);
+        }
+        ordinals
+    }
+
     fn parse_metadata(
         cursor: &mut Cursor<&[u8]>,
         column_types: &Vec<u8>,
@@ -112,3 +322,43 @@ impl TableMapEvent {
         Ok(metadata)
     }
 }
+
+/// Returns string value of ENUM by 1-based index.
+/// Zero index is used for invalid empty value. Index is returned if values are unknown.
+#[cfg(any(
+    feature = "debezium",
+    feature = "avro",
+    feature = "arrow",
+    feature = "sql"
+))]
+pub(crate) fn enum_string(values: Option<&Vec<String>>, index: u32) -> String {
+    match values {
+        Some(_) if index == 0 => String::new(),
+        Some(values) => match values.get(index as usize - 1) {
+            Some(x) => x.clone(),
+            None => index.to_string(),
+        },
+        None => index.to_string(),
+    }
+}
+
+/// Returns comma separated values of SET bitmap.
+/// Bitmap is returned if values are unknown.
+#[cfg(any(
+    feature = "debezium",
+    feature = "avro",
+    feature = "arrow",
+    feature = "sql"
+))]
+pub(crate) fn set_string(values: Option<&Vec<String>>, bitmap: u64) -> String {
+    match values {
+        Some(values) => values
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| bitmap & (1 << i) != 0)
+            .map(|(_, x)| x.as_str())
+            .collect::<Vec<&str>>()
+            .join(","),
+        None => bitmap.to_string(),
+    }
+}
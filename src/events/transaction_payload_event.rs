@@ -0,0 +1,284 @@
+use crate::constants::checksum_type::ChecksumType;
+use crate::constants::EVENT_HEADER_SIZE;
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::event_header::EventHeader;
+use crate::events::event_parser::EventParser;
+use crate::extensions::read_len_enc_num;
+use std::borrow::Cow;
+use std::io::Cursor;
+
+/// Payload header field types.
+/// See <a href="https://github.com/mysql/mysql-server/blob/8.0/libbinlogevents/include/compression/base.h">MySQL sources</a>
+const HEADER_END_MARK: usize = 0;
+const PAYLOAD_SIZE_FIELD: usize = 1;
+const COMPRESSION_TYPE_FIELD: usize = 2;
+const UNCOMPRESSED_SIZE_FIELD: usize = 3;
+
+/// Compression algorithm of the transaction payload.
+#[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PayloadCompressionType {
+    /// ZSTD compression.
+    Zstd = 0,
+
+    /// Payload is not compressed.
+    None = 255,
+}
+
+impl PayloadCompressionType {
+    pub fn from_code(code: usize) -> Result<Self, Error> {
+        match code {
+            0 => Ok(PayloadCompressionType::Zstd),
+            255 => Ok(PayloadCompressionType::None),
+            _ => Err(Error::String(format!(
+                "Transaction payload compression type {} is not supported",
+                code
+            ))),
+        }
+    }
+}
+
+/// Wraps all events of a transaction when binlog_transaction_compression is enabled.
+/// Inner events don't have checksums, the outer event is checksummed instead.
+/// <a href="https://dev.mysql.com/doc/refman/8.0/en/binary-log-transaction-compression.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionPayloadEvent {
+    /// Gets compression algorithm of the payload.
+    pub compression_type: PayloadCompressionType,
+
+    /// Gets size of the payload as it is stored in the binlog.
+    pub payload_size: usize,
+
+    /// Gets size of the payload after decompression.
+    pub uncompressed_size: usize,
+
+    /// Gets events of the transaction in the order they were written.
+    pub events: Vec<(EventHeader, BinlogEvent)>,
+}
+
+impl TransactionPayloadEvent {
+    /// Supports MySQL 8.0.20+.
+    /// Inner events are parsed by the passed parser so that it keeps track of TableMapEvents.
+    pub fn parse(cursor: &mut Cursor<&[u8]>, parser: &mut EventParser) -> Result<Self, Error> {
+        let mut payload_size = 0;
+        let mut uncompressed_size = 0;
+        let mut compression_type = PayloadCompressionType::None;
+
+        // Header is a list of type-length-value fields ending with the end mark.
+        loop {
+            let field_type = read_len_enc_num(cursor)?;
+            if field_type == HEADER_END_MARK {
+                break;
+            }
+
+            let field_length = read_len_enc_num(cursor)?;
+            match field_type {
+                PAYLOAD_SIZE_FIELD => payload_size = read_len_enc_num(cursor)?,
+                COMPRESSION_TYPE_FIELD => {
+                    compression_type = PayloadCompressionType::from_code(read_len_enc_num(cursor)?)?
+                }
+                UNCOMPRESSED_SIZE_FIELD => uncompressed_size = read_len_enc_num(cursor)?,
+                // Fields added in newer versions can be skipped
+                _ => cursor.set_position(cursor.position() + field_length as u64),
+            }
+        }
+
+        // Payload size is checked before use because it comes from the event
+        let data: &[u8] = cursor.get_ref();
+        let position = cursor.position() as usize;
+        if payload_size > data.len().saturating_sub(position) {
+            return Err(Error::String(format!(
+                "Transaction payload size {} exceeds the event size",
+                payload_size
+            )));
+        }
+        let payload = &data[position..position + payload_size];
+        cursor.set_position((position + payload_size) as u64);
+
+        let payload = match compression_type {
+            PayloadCompressionType::Zstd => Cow::Owned(zstd::stream::decode_all(payload)?),
+            PayloadCompressionType::None => Cow::Borrowed(payload),
+        };
+
+        // Inner events are written without checksums.
+        let checksum_type = parser.checksum_type;
+        parser.checksum_type = ChecksumType::None;
+        let events = parse_events(&payload, parser);
+        parser.checksum_type = checksum_type;
+
+        Ok(Self {
+            compression_type,
+            payload_size,
+            uncompressed_size,
+            events: events?,
+        })
+    }
+
+    /// Returns inner events positioned relative to the outer event.
+    /// All inner events except the last one point to the start of the outer event,
+    /// so replication resumed from them re-reads the whole payload.
+    /// The last inner event points to the event following the payload.
+    pub fn into_events(self, header: &EventHeader) -> Vec<(EventHeader, BinlogEvent)> {
+        let start_position = header
+            .next_event_position
+            .saturating_sub(header.event_length);
+        let last_index = self.events.len().saturating_sub(1);
+
+        let mut events = self.events;
+        for (index, (inner_header, _)) in events.iter_mut().enumerate() {
+            inner_header.next_event_position = match header.next_event_position {
+                0 => 0,
+                _ if index == last_index => header.next_event_position,
+                _ => start_position,
+            };
+        }
+        events
+    }
+}
+
+fn parse_events(
+    payload: &[u8],
+    parser: &mut EventParser,
+) -> Result<Vec<(EventHeader, BinlogEvent)>, Error> {
+    let mut events = Vec::new();
+    let mut offset = 0;
+    while offset < payload.len() {
+        let header = EventHeader::parse(&payload[offset..])?;
+        let event_end = offset + header.event_length as usize;
+        if header.event_length < EVENT_HEADER_SIZE as u32 || event_end > payload.len() {
+            return Err(Error::String(
+                "Transaction payload contains a truncated event".to_string(),
+            ));
+        }
+
+        let event = parser.parse_event(&header, &payload[offset + EVENT_HEADER_SIZE..event_end])?;
+        events.push((header, event));
+        offset = event_end;
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PayloadCompressionType, TransactionPayloadEvent};
+    use crate::constants::checksum_type::ChecksumType;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::event_header::EventHeader;
+    use crate::events::event_parser::EventParser;
+    use byteorder::{LittleEndian, WriteBytesExt};
+
+    fn create_event(event_type: u8, body: &[u8]) -> Vec<u8> {
+        let mut event = Vec::new();
+        event.write_u32::<LittleEndian>(1_600_000_000).unwrap();
+        event.write_u8(event_type).unwrap();
+        event.write_u32::<LittleEndian>(1).unwrap();
+        event
+            .write_u32::<LittleEndian>(19 + body.len() as u32)
+            .unwrap();
+        event.write_u32::<LittleEndian>(0).unwrap();
+        event.write_u16::<LittleEndian>(0).unwrap();
+        event.extend_from_slice(body);
+        event
+    }
+
+    fn create_payload() -> Vec<u8> {
+        // QueryEvent with "BEGIN" statement followed by XidEvent
+        let mut query = vec![1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
+        query.extend_from_slice(b"\0BEGIN");
+
+        let mut inner = create_event(2, &query);
+        inner.extend(create_event(16, &[42, 0, 0, 0, 0, 0, 0, 0]));
+        let compressed = zstd::stream::encode_all(inner.as_slice(), 3).unwrap();
+
+        let mut payload = vec![
+            2,
+            1,
+            0,
+            3,
+            1,
+            inner.len() as u8,
+            1,
+            1,
+            compressed.len() as u8,
+            0,
+        ];
+        payload.extend(compressed);
+        payload
+    }
+
+    #[test]
+    fn parse_compressed_transaction() {
+        let payload = create_payload();
+        let mut body = payload.clone();
+        body.extend_from_slice(&[0, 0, 0, 0]); // outer event checksum
+
+        let mut event = vec![0u8; 19];
+        event[4] = 40;
+        event[9..13].copy_from_slice(&(19 + body.len() as u32).to_le_bytes());
+        event[13..17].copy_from_slice(&1000u32.to_le_bytes());
+        let header = EventHeader::parse(&event).unwrap();
+
+        let mut parser = EventParser::new();
+        parser.checksum_type = ChecksumType::Crc32;
+        let event = match parser.parse_event(&header, &body).unwrap() {
+            BinlogEvent::TransactionPayloadEvent(x) => x,
+            x => panic!("Unexpected event {:?}", x),
+        };
+
+        assert_eq!(PayloadCompressionType::Zstd, event.compression_type);
+        assert_eq!(2, event.events.len());
+        assert!(matches!(parser.checksum_type, ChecksumType::Crc32));
+
+        let events = event.into_events(&header);
+        match &events[0] {
+            (header, BinlogEvent::QueryEvent(x)) => {
+                assert_eq!("BEGIN", x.sql_statement);
+                assert_eq!(1000 - 19 - body.len() as u32, header.next_event_position);
+            }
+            x => panic!("Unexpected event {:?}", x),
+        }
+        match &events[1] {
+            (header, BinlogEvent::XidEvent(x)) => {
+                assert_eq!(42, x.xid);
+                assert_eq!(1000, header.next_event_position);
+            }
+            x => panic!("Unexpected event {:?}", x),
+        }
+    }
+
+    #[test]
+    fn skip_unknown_header_fields() {
+        let mut payload = create_payload();
+        // Unknown field of 2 bytes before the end mark
+        payload.splice(9..9, [9, 2, 5, 5]);
+
+        let mut cursor = std::io::Cursor::new(payload.as_slice());
+        let event = TransactionPayloadEvent::parse(&mut cursor, &mut EventParser::new()).unwrap();
+        assert_eq!(PayloadCompressionType::Zstd, event.compression_type);
+        assert_eq!(2, event.events.len());
+    }
+
+    #[test]
+    fn parse_oversized_payload_fails() {
+        // Payload size field of u64::MAX
+        let mut payload = vec![1, 9, 0xFE];
+        payload.extend_from_slice(&u64::MAX.to_le_bytes());
+        payload.extend_from_slice(&[0, 1, 2, 3]);
+
+        let mut cursor = std::io::Cursor::new(payload.as_slice());
+        let result = TransactionPayloadEvent::parse(&mut cursor, &mut EventParser::new());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn parse_truncated_payload_fails() {
+        let mut payload = create_payload();
+        payload.truncate(payload.len() - 2);
+
+        let mut cursor = std::io::Cursor::new(payload.as_slice());
+        let result = TransactionPayloadEvent::parse(&mut cursor, &mut EventParser::new());
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,21 @@
+/// Event of a type the parser doesn't support.
+/// Raw payload is kept so the event can be logged or forwarded.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct UnknownEvent {
+    /// Gets event type code.
+    pub event_type: u8,
+
+    /// Gets event payload without header and checksum.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub payload: Vec<u8>,
+}
+
+impl UnknownEvent {
+    pub fn new(event_type: u8, payload: &[u8]) -> Self {
+        Self {
+            event_type,
+            payload: payload.to_vec(),
+        }
+    }
+}
@@ -1,11 +1,26 @@
 use byteorder::{LittleEndian, ReadBytesExt};
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
+use crate::events::row_events::decimal::parse_decimal;
+use crate::events::row_events::mysql_value::Decimal;
 use crate::{errors::Error, extensions::read_string};
 
+/// Variable types. See Item_result in MySQL sources.
+const STRING_RESULT: u8 = 0;
+const REAL_RESULT: u8 = 1;
+const INT_RESULT: u8 = 2;
+const DECIMAL_RESULT: u8 = 4;
+
+/// Value of INT_RESULT variable is unsigned.
+const UNSIGNED_FLAG: u8 = 1;
+
+/// Collation of binary strings.
+const BINARY_COLLATION: u32 = 63;
+
 /// A USER_VAR_EVENT is written every time a statement uses a user defined variable.
 /// <a href="https://mariadb.com/kb/en/user_var_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UserVarEvent {
     /// User variable name
     pub name: String,
@@ -16,6 +31,7 @@ pub struct UserVarEvent {
 
 /// User variable value
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VariableValue {
     /// Variable type
     pub var_type: u8,
@@ -24,12 +40,35 @@ pub struct VariableValue {
     pub collation: u32,
 
     /// User variable value
-    pub value: String,
+    pub value: UserVarValue,
 
     /// flags
     pub flags: u8,
 }
 
+/// User variable value decoded according to its type
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum UserVarValue {
+    /// STRING_RESULT with a text collation
+    String(String),
+
+    /// STRING_RESULT with binary collation or not valid UTF-8
+    Bytes(#[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))] Vec<u8>),
+
+    /// REAL_RESULT
+    Real(f64),
+
+    /// Signed INT_RESULT
+    Int(i64),
+
+    /// INT_RESULT with unsigned flag
+    UnsignedInt(u64),
+
+    /// DECIMAL_RESULT
+    Decimal(Decimal),
+}
+
 impl UserVarEvent {
     /// Supports all versions of MariaDB and MySQL.
     pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
@@ -45,9 +84,11 @@ impl UserVarEvent {
         let collation = cursor.read_u32::<LittleEndian>()?;
 
         let value_len = cursor.read_u32::<LittleEndian>()?;
-        let value = read_string(cursor, value_len as usize)?;
+        let mut value = vec![0; value_len as usize];
+        cursor.read_exact(&mut value)?;
 
         let flags = cursor.read_u8()?;
+        let value = parse_value(value, var_type, collation, flags)?;
 
         Ok(Self {
             name,
@@ -61,11 +102,59 @@ impl UserVarEvent {
     }
 }
 
+fn parse_value(
+    value: Vec<u8>,
+    var_type: u8,
+    collation: u32,
+    flags: u8,
+) -> Result<UserVarValue, Error> {
+    let mut cursor = Cursor::new(value.as_slice());
+    let value = match var_type {
+        STRING_RESULT if collation == BINARY_COLLATION => UserVarValue::Bytes(value),
+        STRING_RESULT => match String::from_utf8(value) {
+            Ok(value) => UserVarValue::String(value),
+            Err(error) => UserVarValue::Bytes(error.into_bytes()),
+        },
+        REAL_RESULT => UserVarValue::Real(cursor.read_f64::<LittleEndian>()?),
+        INT_RESULT if flags & UNSIGNED_FLAG != 0 => {
+            UserVarValue::UnsignedInt(cursor.read_u64::<LittleEndian>()?)
+        }
+        INT_RESULT => UserVarValue::Int(cursor.read_i64::<LittleEndian>()?),
+        DECIMAL_RESULT => {
+            // Binary decimal is preceded by precision and scale
+            let metadata = cursor.read_u16::<LittleEndian>()?;
+            UserVarValue::Decimal(parse_decimal(&mut cursor, metadata)?)
+        }
+        _ => {
+            return Err(Error::String(format!(
+                "User variable type {} is not supported",
+                var_type
+            )))
+        }
+    };
+    Ok(value)
+}
+
 #[cfg(test)]
 mod tests {
     use std::io::Cursor;
 
-    use crate::events::uservar_event::UserVarEvent;
+    use crate::events::uservar_event::{UserVarEvent, UserVarValue};
+
+    fn parse_value(var_type: u8, value: &[u8], flags: u8) -> UserVarValue {
+        let mut payload: Vec<u8> = vec![0x01, 0x00, 0x00, 0x00, 0x78, 0x00, var_type];
+        payload.extend_from_slice(&63u32.to_le_bytes());
+        payload.extend_from_slice(&(value.len() as u32).to_le_bytes());
+        payload.extend_from_slice(value);
+        payload.push(flags);
+
+        let mut cursor = Cursor::new(payload.as_slice());
+        UserVarEvent::parse(&mut cursor)
+            .unwrap()
+            .value
+            .unwrap()
+            .value
+    }
 
     #[test]
     fn parse_user_var_event() {
@@ -82,6 +171,27 @@ mod tests {
         let variable = event.value.unwrap();
         assert_eq!(0, variable.var_type);
         assert_eq!(33, variable.collation);
-        assert_eq!(String::from("bar"), variable.value);
+        assert_eq!(UserVarValue::String(String::from("bar")), variable.value);
+    }
+
+    #[test]
+    fn parse_typed_values() {
+        let value = parse_value(2, &(-5i64).to_le_bytes(), 0);
+        assert_eq!(UserVarValue::Int(-5), value);
+
+        let value = parse_value(2, &u64::MAX.to_le_bytes(), 1);
+        assert_eq!(UserVarValue::UnsignedInt(u64::MAX), value);
+
+        let value = parse_value(1, &1.5f64.to_le_bytes(), 0);
+        assert_eq!(UserVarValue::Real(1.5), value);
+
+        let value = parse_value(0, &[0xff, 0x00], 0);
+        assert_eq!(UserVarValue::Bytes(vec![0xff, 0x00]), value);
+
+        // decimal(5,2) 123.45
+        match parse_value(4, &[5, 2, 0x80, 0x7b, 0x2d], 0) {
+            UserVarValue::Decimal(x) => assert_eq!("123.45", x.to_string()),
+            x => panic!("Unexpected value {:?}", x),
+        }
     }
 }
@@ -0,0 +1,55 @@
+use crate::errors::Error;
+use std::fmt;
+use std::io::{Cursor, Read};
+
+/// Represents identifier of an XA transaction.
+/// <a href="https://dev.mysql.com/doc/refman/8.0/en/xa-statements.html">See more</a>
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Xid {
+    /// Gets format identifier.
+    pub format_id: i32,
+
+    /// Gets global transaction identifier.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub gtrid: Vec<u8>,
+
+    /// Gets branch qualifier.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub bqual: Vec<u8>,
+}
+
+impl Xid {
+    /// Reads gtrid and bqual that are stored one after another.
+    pub fn parse(
+        cursor: &mut Cursor<&[u8]>,
+        format_id: i32,
+        gtrid_length: usize,
+        bqual_length: usize,
+    ) -> Result<Self, Error> {
+        let mut gtrid = vec![0; gtrid_length];
+        cursor.read_exact(&mut gtrid)?;
+
+        let mut bqual = vec![0; bqual_length];
+        cursor.read_exact(&mut bqual)?;
+
+        Ok(Self {
+            format_id,
+            gtrid,
+            bqual,
+        })
+    }
+}
+
+impl fmt::Display for Xid {
+    /// Formats the xid like it is used in XA statements.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "X'{}',X'{}',{}",
+            hex::encode(&self.gtrid),
+            hex::encode(&self.bqual),
+            self.format_id
+        )
+    }
+}
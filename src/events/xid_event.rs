@@ -6,6 +6,7 @@ use crate::errors::Error;
 /// Represents a transaction commit event.
 /// <a href="https://mariadb.com/kb/en/library/xid_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct XidEvent {
     /// Gets the XID transaction number
     pub xid: u64,
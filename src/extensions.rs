@@ -1,26 +1,57 @@
-use crate::constants::auth_plugin_names::AuthPlugin;
 use crate::constants::NULL_TERMINATOR;
 use crate::errors::Error;
 use crate::responses::error_packet::ErrorPacket;
 use crate::responses::response_type::ResponseType;
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use ed25519_dalek::hazmat::{raw_sign, ExpandedSecretKey};
+use ed25519_dalek::VerifyingKey;
+use openssl::rsa::{Padding, Rsa};
 use sha1::Sha1;
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 use std::io::{self, BufRead, Cursor, Read, Write};
 
-pub fn encrypt_password(password: &String, scramble: &String, auth_plugin: &AuthPlugin) -> Vec<u8> {
-    match auth_plugin {
-        AuthPlugin::MySqlNativePassword => {
-            let password_hash = sha1(password.as_bytes());
-            let concat_hash = [scramble.as_bytes().to_vec(), sha1(&password_hash)].concat();
-            xor(&password_hash, &sha1(&concat_hash))
-        }
-        AuthPlugin::CachingSha2Password => {
-            let password_hash = sha256(password.as_bytes());
-            let concat_hash = [scramble.as_bytes().to_vec(), sha256(&password_hash)].concat();
-            xor(&password_hash, &sha256(&concat_hash))
-        }
-    }
+/// Scrambles the password for mysql_native_password plugin.
+pub fn scramble_sha1(password: &str, scramble: &[u8]) -> Vec<u8> {
+    let password_hash = sha1(password.as_bytes());
+    let concat_hash = [scramble.to_vec(), sha1(&password_hash)].concat();
+    xor(&password_hash, &sha1(&concat_hash))
+}
+
+/// Scrambles the password for caching_sha2_password plugin.
+pub fn scramble_sha256(password: &str, scramble: &[u8]) -> Vec<u8> {
+    let password_hash = sha256(password.as_bytes());
+    let concat_hash = [scramble.to_vec(), sha256(&password_hash)].concat();
+    xor(&password_hash, &sha256(&concat_hash))
+}
+
+/// Encrypts null-terminated password xored with the scramble using RSA public key of the server.
+pub fn rsa_encrypt_password(
+    password: &str,
+    scramble: &[u8],
+    public_key: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let mut password = password.as_bytes().to_vec();
+    password.push(NULL_TERMINATOR);
+    let encrypted_password = xor(&password, scramble);
+
+    let rsa = Rsa::public_key_from_pem(public_key)?;
+    let mut encrypted_body = vec![0u8; rsa.size() as usize];
+    rsa.public_encrypt(
+        &encrypted_password,
+        &mut encrypted_body,
+        Padding::PKCS1_OAEP,
+    )?;
+    Ok(encrypted_body)
+}
+
+/// Signs the message with Ed25519 key of MariaDB ed25519 plugin.
+/// Unlike standard Ed25519 the secret key is SHA-512 hash of the password of any length.
+pub fn ed25519_sign(password: &str, message: &[u8]) -> Vec<u8> {
+    let secret_key = ExpandedSecretKey::from_bytes(&Sha512::digest(password.as_bytes()).into());
+    let public_key = VerifyingKey::from(&secret_key);
+    raw_sign::<Sha512>(&secret_key, message, &public_key)
+        .to_bytes()
+        .to_vec()
 }
 
 pub fn sha1(value: &[u8]) -> Vec<u8> {
@@ -0,0 +1,185 @@
+use crate::binlog_client::BinlogClient;
+use crate::constants::database_provider::DatabaseProvider;
+use crate::errors::Error;
+use crate::packet_channel::PacketChannel;
+use crate::providers::mariadb::gtid::gtid_list::GtidList;
+use crate::providers::mysql::gtid::gtid_set::GtidSet;
+use crate::server_address::ServerAddress;
+use crate::starting_strategy::StartingStrategy;
+
+impl BinlogClient {
+    /// Gets address of the server the client is attached to.
+    /// Changes when the client fails over to another server.
+    pub fn current_server(&self) -> ServerAddress {
+        self.options.servers().swap_remove(self.server)
+    }
+
+    /// Connects to the current server or the following ones in order.
+    /// Servers that haven't executed the committed GTIDs are skipped.
+    pub(crate) fn connect_with_failover(
+        &mut self,
+    ) -> Result<(PacketChannel, DatabaseProvider), Error> {
+        let servers = self.options.servers().len();
+        // Binlog file positions are specific to the server
+        let count = match self.options.binlog.starting_strategy {
+            StartingStrategy::FromGtid => servers,
+            _ => 1,
+        };
+
+        let mut last_error = None;
+        for i in 0..count {
+            let server = (self.server + i) % servers;
+            let result = self.connect_to(server).and_then(|(mut channel, provider)| {
+                if !self.options.failover_servers.is_empty() {
+                    self.verify_gtid_position(&mut channel, &provider, server)?;
+                }
+                Ok((channel, provider))
+            });
+            match result {
+                Ok(x) => {
+                    self.server = server;
+                    return Ok(x);
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        Err(last_error.unwrap())
+    }
+
+    /// Checks that the server has executed the transactions committed by the client.
+    /// Otherwise the server is behind and replication would skip the missing transactions.
+    fn verify_gtid_position(
+        &self,
+        channel: &mut PacketChannel,
+        provider: &DatabaseProvider,
+        server: usize,
+    ) -> Result<(), Error> {
+        let missing = match provider {
+            DatabaseProvider::MySQL => match &self.options.binlog.gtid_set {
+                Some(committed) => {
+                    let executed = query_value(channel, "SELECT @@gtid_executed")?;
+                    let executed = GtidSet::parse(&executed)?;
+                    (!committed.is_subset_of(&executed)).then(|| committed.to_string())
+                }
+                None => None,
+            },
+            DatabaseProvider::MariaDB => match &self.options.binlog.gtid_list {
+                Some(committed) => {
+                    let executed = query_value(channel, "SELECT @@gtid_binlog_pos")?;
+                    let executed = GtidList::parse(&executed)?;
+                    (!committed.is_subset_of(&executed)).then(|| committed.to_string())
+                }
+                None => None,
+            },
+        };
+        match missing {
+            Some(gtids) => Err(Error::String(format!(
+                "Server {} hasn't executed committed GTIDs {}",
+                self.options.servers()[server],
+                gtids
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn query_value(channel: &mut PacketChannel, sql: &str) -> Result<String, Error> {
+    let rows = channel.query(sql)?.rows;
+    match rows.first() {
+        Some(row) => Ok(row.get_at::<String>(0)?.unwrap_or_default()),
+        None => Ok(String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::binlog_options::BinlogOptions;
+    use crate::providers::mariadb::gtid::gtid_list::GtidList;
+    use crate::providers::mysql::gtid::gtid_set::GtidSet;
+    use crate::replica_options::ReplicaOptions;
+    use crate::server_address::ServerAddress;
+    use crate::test_server::{Response, TestServer};
+    use std::net::TcpListener;
+
+    const GTID_SET: &str = "24bc7850-2c16-11e6-a073-0242ac110002:1-15";
+
+    fn create_options(port: u16, failover_servers: &[&TestServer]) -> ReplicaOptions {
+        ReplicaOptions {
+            hostname: "127.0.0.1".to_string(),
+            port,
+            failover_servers: failover_servers
+                .iter()
+                .map(|x| ServerAddress::new("127.0.0.1".to_string(), x.port))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn gtid_server(version: &'static str, executed: &'static str) -> TestServer {
+        TestServer::start(version, move |_| {
+            Response::rows(&["@@gtid_executed"], &[&[Some(executed)]])
+        })
+    }
+
+    fn closed_port() -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        listener.local_addr().unwrap().port()
+    }
+
+    #[test]
+    fn fail_over_to_server_with_committed_gtids() {
+        let stale = gtid_server("8.0.36", "24bc7850-2c16-11e6-a073-0242ac110002:1-10");
+        let replica = gtid_server("8.0.36", "24bc7850-2c16-11e6-a073-0242ac110002:1-20");
+
+        let primary = closed_port();
+        let mut options = create_options(primary, &[&stale, &replica]);
+        options.binlog = BinlogOptions::from_mysql_gtid(GtidSet::parse(GTID_SET).unwrap());
+        let mut client = BinlogClient::new(options);
+        let address = ServerAddress::new("127.0.0.1".to_string(), primary);
+        assert_eq!(address, client.current_server());
+
+        let (channel, _) = client.connect_with_failover().unwrap();
+        drop(channel);
+        assert_eq!(replica.port, client.current_server().port);
+
+        assert_eq!(vec!["SELECT @@gtid_executed"], stale.join());
+        assert_eq!(vec!["SELECT @@gtid_executed"], replica.join());
+    }
+
+    #[test]
+    fn fail_over_with_tagged_gtids() {
+        let stale = gtid_server("8.4.0", "24bc7850-2c16-11e6-a073-0242ac110002:1-20:tag:1-2");
+        let replica = gtid_server("8.4.0", "24bc7850-2c16-11e6-a073-0242ac110002:1-20:tag:1-5");
+
+        let mut options = create_options(closed_port(), &[&stale, &replica]);
+        let committed = "24bc7850-2c16-11e6-a073-0242ac110002:1-15:tag:1-3";
+        options.binlog = BinlogOptions::from_mysql_gtid(GtidSet::parse(committed).unwrap());
+        let mut client = BinlogClient::new(options);
+
+        let (channel, _) = client.connect_with_failover().unwrap();
+        drop(channel);
+        assert_eq!(replica.port, client.current_server().port);
+
+        assert_eq!(vec!["SELECT @@gtid_executed"], stale.join());
+        assert_eq!(vec!["SELECT @@gtid_executed"], replica.join());
+    }
+
+    #[test]
+    fn fail_over_requires_gtid_positioning() {
+        let replica = gtid_server("10.11.6-MariaDB", "0-1-300");
+
+        let mut options = create_options(closed_port(), &[&replica]);
+        options.binlog = BinlogOptions::from_position("mariadb-bin.000002".to_string(), 4);
+        let mut client = BinlogClient::new(options);
+        assert!(client.connect_with_failover().is_err());
+        assert_eq!(0, client.server);
+
+        client.options.binlog =
+            BinlogOptions::from_mariadb_gtid(GtidList::parse("0-1-270").unwrap());
+        let (channel, _) = client.connect_with_failover().unwrap();
+        drop(channel);
+        assert_eq!(replica.port, client.current_server().port);
+        assert_eq!(vec!["SELECT @@gtid_binlog_pos"], replica.join());
+    }
+}
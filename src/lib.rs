@@ -3,7 +3,7 @@
 //!
 //! ## Limitations
 //! Please note the lib currently has the following limitations:
-//! - Supports only standard auth plugins `mysql_native_password` and `caching_sha2_password`.
+//! - Supports auth plugins `mysql_native_password`, `caching_sha2_password`, `sha256_password`, `mysql_clear_password` and MariaDB `client_ed25519`, `parsec`. Kerberos and GSSAPI plugins are not supported.
 //! - **Currently, the library doesn't support SSL encryption.**
 //! - **Doesn't handle split packets (16MB and more).**
 //!
@@ -97,15 +97,30 @@
 //! }
 //! ```
 
+#[cfg(feature = "arrow")]
+pub mod arrow;
+#[cfg(feature = "avro")]
+pub mod avro;
 pub mod binlog_client;
 pub mod binlog_events;
 pub mod binlog_options;
 pub mod binlog_reader;
+pub mod compression_mode;
+#[cfg(feature = "debezium")]
+pub mod debezium;
 pub mod errors;
 pub mod events;
 pub mod metadata;
 pub mod providers;
 pub mod replica_options;
+pub mod result_set;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod server_address;
+pub mod session;
+pub mod snapshot;
+#[cfg(feature = "sql")]
+pub mod sql;
 pub mod ssl_mode;
 pub mod starting_strategy;
 
@@ -114,5 +129,14 @@ mod configure;
 mod connect;
 mod constants;
 mod extensions;
+mod failover;
 mod packet_channel;
+mod packet_compression;
+mod query;
 mod responses;
+#[cfg(test)]
+#[allow(dead_code)] // Helpers are used by tests of optional features
+mod test_fixtures;
+#[cfg(test)]
+mod test_server;
+mod transport;
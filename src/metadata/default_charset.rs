@@ -1,5 +1,6 @@
 /// Represents charsets of character columns.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DefaultCharset {
     /// Gets the most used charset collation.
     pub default_charset_collation: u32,
@@ -8,7 +8,8 @@ use std::io::{self, Cursor, Read};
 
 /// Contains metadata for table columns.
 /// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1Table__map__event.html">See more</a>
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableMetadata {
     /// Gets signedness of numeric colums.
     pub signedness: Option<Vec<bool>>,
@@ -203,17 +204,47 @@ fn read_bitmap_reverted(
 fn get_numeric_column_count(column_types: &[u8]) -> Result<usize, Error> {
     let mut count = 0;
     for i in 0..column_types.len() {
-        match ColumnType::from_code(column_types[i])? {
-            ColumnType::Tiny => count += 1,
-            ColumnType::Short => count += 1,
-            ColumnType::Int24 => count += 1,
-            ColumnType::Long => count += 1,
-            ColumnType::LongLong => count += 1,
-            ColumnType::Float => count += 1,
-            ColumnType::Double => count += 1,
-            ColumnType::NewDecimal => count += 1,
-            _ => (),
+        ColumnType::from_code(column_types[i])?;
+        if is_numeric_column(column_types[i]) {
+            count += 1;
         }
     }
     Ok(count)
 }
+
+/// Returns true for columns that have signedness metadata.
+pub(crate) fn is_numeric_column(column_type: u8) -> bool {
+    matches!(
+        ColumnType::from_code(column_type),
+        Ok(ColumnType::Tiny
+            | ColumnType::Short
+            | ColumnType::Int24
+            | ColumnType::Long
+            | ColumnType::LongLong
+            | ColumnType::Float
+            | ColumnType::Double
+            | ColumnType::NewDecimal)
+    )
+}
+
+/// Returns true for columns that have charset metadata.
+pub(crate) fn is_character_column(column_type: u8) -> bool {
+    matches!(
+        ColumnType::from_code(column_type),
+        Ok(ColumnType::String
+            | ColumnType::VarString
+            | ColumnType::VarChar
+            | ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob)
+    )
+}
+
+/// Returns true for columns that have ENUM and SET charset metadata.
+pub(crate) fn is_enum_or_set_column(column_type: u8) -> bool {
+    matches!(
+        ColumnType::from_code(column_type),
+        Ok(ColumnType::Enum | ColumnType::Set)
+    )
+}
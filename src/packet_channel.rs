@@ -1,45 +1,93 @@
 use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
 use std::io::{self, Read, Write};
-use std::net::TcpStream;
 
-use crate::constants::{PACKET_HEADER_SIZE, TIMEOUT_LATENCY_DELTA};
+use crate::compression_mode::CompressionMode;
+use crate::constants::{PACKET_HEADER_SIZE, TIMEOUT_LATENCY_DELTA, TIMEOUT_MESSAGE};
+use crate::errors::Error;
+use crate::packet_compression::PacketCompression;
 use crate::replica_options::ReplicaOptions;
+use crate::transport::Transport;
 
 pub struct PacketChannel {
-    stream: TcpStream,
+    stream: Transport,
+
+    /// Result sets end with OK packet instead of EOF packets when CLIENT_DEPRECATE_EOF is negotiated.
+    pub deprecate_eof: bool,
+
+    /// Version of the server reported in the initial handshake.
+    pub server_version: String,
+
+    /// Set after authentication when compression is negotiated.
+    compression: Option<PacketCompression>,
 }
 
 impl PacketChannel {
-    pub fn new(options: &ReplicaOptions) -> Result<Self, io::Error> {
-        let address: String = format!("{}:{}", options.hostname, options.port.to_string());
-        let stream = TcpStream::connect(address)?;
-        let read_timeout = options.heartbeat_interval + TIMEOUT_LATENCY_DELTA;
+    pub fn new(options: &ReplicaOptions, server: usize) -> Result<Self, Error> {
+        let stream = Transport::connect(options, server).map_err(socket_error)?;
+        let read_timeout = options
+            .read_timeout
+            .unwrap_or(options.heartbeat_interval + TIMEOUT_LATENCY_DELTA);
         stream.set_read_timeout(Some(read_timeout))?;
-        Ok(Self { stream })
+        stream.set_write_timeout(options.write_timeout)?;
+        Ok(Self {
+            stream,
+            deprecate_eof: false,
+            server_version: String::new(),
+            compression: None,
+        })
     }
 
-    pub fn read_packet(&mut self) -> Result<(Vec<u8>, u8), io::Error> {
+    pub fn read_packet(&mut self) -> Result<(Vec<u8>, u8), Error> {
         let mut header_buffer = [0; PACKET_HEADER_SIZE];
 
-        self.stream.read_exact(&mut header_buffer)?;
+        self.read_exact(&mut header_buffer).map_err(socket_error)?;
         let packet_size = (&header_buffer[0..3]).read_u24::<LittleEndian>()?;
         let seq_num = header_buffer[3];
 
         let mut packet: Vec<u8> = vec![0; packet_size as usize];
-        self.stream.read_exact(&mut packet)?;
+        self.read_exact(&mut packet).map_err(socket_error)?;
 
         Ok((packet, seq_num))
     }
 
-    pub fn write_packet(&mut self, packet: &[u8], seq_num: u8) -> Result<(), io::Error> {
-        let packet_len = packet.len() as u32;
-        self.stream.write_u24::<LittleEndian>(packet_len)?;
-        self.stream.write_u8(seq_num)?;
-        self.stream.write(packet)?;
-        Ok(())
+    pub fn write_packet(&mut self, packet: &[u8], seq_num: u8) -> Result<(), Error> {
+        let mut buffer = Vec::with_capacity(PACKET_HEADER_SIZE + packet.len());
+        buffer.write_u24::<LittleEndian>(packet.len() as u32)?;
+        buffer.write_u8(seq_num)?;
+        buffer.extend_from_slice(packet);
+
+        let result = match &mut self.compression {
+            Some(compression) => compression.write(&mut self.stream, &buffer, seq_num == 0),
+            None => self.stream.write_all(&buffer),
+        };
+        result.map_err(socket_error)
+    }
+
+    /// Following packets are sent and received in compressed frames.
+    pub fn enable_compression(&mut self, mode: CompressionMode, level: u32) {
+        self.compression = Some(PacketCompression::new(mode, level));
     }
 
     pub fn upgrade_to_ssl(&mut self) {
         unimplemented!();
     }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), io::Error> {
+        match &mut self.compression {
+            Some(compression) => compression.read_exact(&mut self.stream, buf),
+            None => self.stream.read_exact(buf),
+        }
+    }
+}
+
+/// Maps socket timeouts to Timeout error.
+/// Timeouts are reported as WouldBlock on Unix and TimedOut on Windows and by connect.
+fn socket_error(error: io::Error) -> Error {
+    match error.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => match error.get_ref() {
+            Some(message) => Error::Timeout(message.to_string()),
+            None => Error::Timeout(TIMEOUT_MESSAGE.to_string()),
+        },
+        _ => Error::IoError(error),
+    }
 }
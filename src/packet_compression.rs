@@ -0,0 +1,189 @@
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::io::{self, Read, Write};
+
+use crate::compression_mode::CompressionMode;
+
+const COMPRESSED_HEADER_SIZE: usize = 7;
+
+/// Shorter packets are sent uncompressed like in MySQL client.
+const MIN_COMPRESS_LENGTH: usize = 50;
+
+/// Compressed packet framing. Each frame carries one or more packets or their parts.
+/// <a href="https://mariadb.com/kb/en/0-packet/#compressed-packet">See more</a>
+pub struct PacketCompression {
+    mode: CompressionMode,
+    level: u32,
+    seq_num: u8,
+
+    /// Uncompressed bytes of the frames that are not read yet.
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl PacketCompression {
+    pub fn new(mode: CompressionMode, level: u32) -> Self {
+        Self {
+            mode,
+            level,
+            seq_num: 0,
+            buffer: Vec::new(),
+            position: 0,
+        }
+    }
+
+    /// Reads uncompressed bytes filling the buffer. Frames are read from the stream as needed.
+    pub fn read_exact(&mut self, stream: &mut impl Read, buf: &mut [u8]) -> io::Result<()> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            if self.position == self.buffer.len() {
+                self.read_frame(stream)?;
+            }
+            let length = (buf.len() - filled).min(self.buffer.len() - self.position);
+            buf[filled..filled + length]
+                .copy_from_slice(&self.buffer[self.position..self.position + length]);
+            self.position += length;
+            filled += length;
+        }
+        Ok(())
+    }
+
+    /// Writes the packets with header in one frame.
+    /// Sequence number of frames starts over with each command.
+    pub fn write(
+        &mut self,
+        stream: &mut impl Write,
+        packets: &[u8],
+        new_command: bool,
+    ) -> io::Result<()> {
+        if new_command {
+            self.seq_num = 0;
+        }
+
+        let compressed = match packets.len() < MIN_COMPRESS_LENGTH {
+            true => None,
+            false => Some(self.compress(packets)?).filter(|x| x.len() < packets.len()),
+        };
+        let mut frame = Vec::with_capacity(COMPRESSED_HEADER_SIZE + packets.len());
+        match &compressed {
+            Some(payload) => {
+                frame.write_u24::<LittleEndian>(payload.len() as u32)?;
+                frame.write_u8(self.seq_num)?;
+                frame.write_u24::<LittleEndian>(packets.len() as u32)?;
+                frame.extend_from_slice(payload);
+            }
+            // Zero uncompressed length means the payload is sent as is
+            None => {
+                frame.write_u24::<LittleEndian>(packets.len() as u32)?;
+                frame.write_u8(self.seq_num)?;
+                frame.write_u24::<LittleEndian>(0)?;
+                frame.extend_from_slice(packets);
+            }
+        }
+        self.seq_num = self.seq_num.wrapping_add(1);
+        stream.write_all(&frame)
+    }
+
+    fn read_frame(&mut self, stream: &mut impl Read) -> io::Result<()> {
+        let mut header = [0; COMPRESSED_HEADER_SIZE];
+        stream.read_exact(&mut header)?;
+        let mut cursor = &header[..];
+        let payload_length = cursor.read_u24::<LittleEndian>()?;
+        let seq_num = cursor.read_u8()?;
+        let uncompressed_length = cursor.read_u24::<LittleEndian>()?;
+
+        let mut payload = vec![0; payload_length as usize];
+        stream.read_exact(&mut payload)?;
+
+        self.buffer = match uncompressed_length {
+            0 => payload,
+            _ => self.decompress(&payload, uncompressed_length as usize)?,
+        };
+        self.position = 0;
+        self.seq_num = seq_num.wrapping_add(1);
+        Ok(())
+    }
+
+    fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self.mode {
+            CompressionMode::Zlib => {
+                let mut encoder = ZlibEncoder::new(Vec::new(), Compression::new(self.level.min(9)));
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+            CompressionMode::Zstd => zstd::stream::encode_all(data, self.level as i32),
+            CompressionMode::Disabled => Ok(data.to_vec()),
+        }
+    }
+
+    fn decompress(&self, data: &[u8], length: usize) -> io::Result<Vec<u8>> {
+        let mut result = Vec::with_capacity(length);
+        match self.mode {
+            CompressionMode::Zlib => {
+                ZlibDecoder::new(data).read_to_end(&mut result)?;
+            }
+            CompressionMode::Zstd => {
+                zstd::stream::read::Decoder::new(data)?.read_to_end(&mut result)?;
+            }
+            CompressionMode::Disabled => result.extend_from_slice(data),
+        }
+        if result.len() != length {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Compressed packet has unexpected length",
+            ));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::compression_mode::CompressionMode;
+    use crate::constants::capability_flags;
+    use crate::replica_options::ReplicaOptions;
+    use crate::test_server::{Response, ServerConfig, TestServer};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn query_with_compression() {
+        let modes = [
+            (CompressionMode::Zlib, capability_flags::COMPRESS),
+            (
+                CompressionMode::Zstd,
+                capability_flags::ZSTD_COMPRESSION_ALGORITHM,
+            ),
+        ];
+        for (mode, flag) in modes {
+            let config = ServerConfig {
+                compression: true,
+                ..Default::default()
+            };
+            let server = TestServer::start_with(config, |_| {
+                let long = "x".repeat(1000);
+                Response::rows(&["note"], &[&[Some(&long)], &[Some("1")]])
+            });
+
+            let options = ReplicaOptions {
+                hostname: "127.0.0.1".to_string(),
+                port: server.port,
+                compression: mode,
+                ..Default::default()
+            };
+            let result_set = BinlogClient::new(options).query("SELECT note").unwrap();
+            assert_eq!(2, result_set.rows.len());
+            assert_eq!(
+                Some("x".repeat(1000)),
+                result_set.rows[0].get::<String>("note").unwrap()
+            );
+            assert_eq!(Some(1), result_set.rows[1].get::<u32>("note").unwrap());
+
+            let capabilities = server.client_capabilities.load(Ordering::SeqCst);
+            assert_ne!(0, capabilities & flag);
+            assert_eq!(vec!["SELECT note"], server.join());
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use crate::errors::Error;
+use byteorder::{BigEndian, ReadBytesExt};
+use flate2::read::ZlibDecoder;
+use std::io::{Cursor, Read};
+
+/// Decompresses data of MariaDB compressed events (log_bin_compress=ON).
+/// First byte stores the algorithm(zlib=0) and the number of bytes of uncompressed length.
+/// Uncompressed length is followed by zlib compressed data.
+/// <a href="https://mariadb.com/kb/en/compressed-binary-log/">See more</a>
+pub fn uncompress_event_data(cursor: &mut Cursor<&[u8]>) -> Result<Vec<u8>, Error> {
+    // 1 bit always set. 3 bits algorithm. 1 bit unused. 3 bits length size.
+    let header = cursor.read_u8()?;
+    if (header & 0xF0) != 0x80 {
+        return Err(Error::String(format!(
+            "Unsupported binlog compression header {}",
+            header
+        )));
+    }
+
+    let length_size = (header & 0x07) as usize;
+    if length_size == 0 || length_size > 4 {
+        return Err(Error::String(format!(
+            "Invalid binlog compression length size {}",
+            length_size
+        )));
+    }
+    let length = cursor.read_uint::<BigEndian>(length_size)? as usize;
+
+    let mut data = Vec::with_capacity(length);
+    ZlibDecoder::new(cursor).read_to_end(&mut data)?;
+
+    if data.len() != length {
+        return Err(Error::String(format!(
+            "Uncompressed event data length {} doesn't match expected {}",
+            data.len(),
+            length
+        )));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::uncompress_event_data;
+    use flate2::{write::ZlibEncoder, Compression};
+    use std::io::{Cursor, Write};
+
+    fn compress(data: &[u8]) -> Vec<u8> {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(data).unwrap();
+
+        let mut result = vec![0x82, (data.len() >> 8) as u8, data.len() as u8];
+        result.extend(encoder.finish().unwrap());
+        result
+    }
+
+    #[test]
+    fn uncompress_returns_original_data() {
+        let data = "INSERT INTO t VALUES (1)".repeat(20);
+        let compressed = compress(data.as_bytes());
+
+        let mut cursor = Cursor::new(compressed.as_slice());
+        let result = uncompress_event_data(&mut cursor).unwrap();
+        assert_eq!(data.as_bytes(), result.as_slice());
+    }
+
+    #[test]
+    fn uncompress_with_wrong_length_fails() {
+        let mut compressed = compress(b"COMMIT");
+        compressed[2] += 1;
+
+        let mut cursor = Cursor::new(compressed.as_slice());
+        assert!(uncompress_event_data(&mut cursor).is_err());
+    }
+
+    #[test]
+    fn uncompress_unknown_algorithm_fails() {
+        let mut compressed = compress(b"COMMIT");
+        compressed[0] = 0x92;
+
+        let mut cursor = Cursor::new(compressed.as_slice());
+        assert!(uncompress_event_data(&mut cursor).is_err());
+    }
+}
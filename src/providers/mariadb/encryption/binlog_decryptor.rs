@@ -0,0 +1,210 @@
+use crate::constants::{EVENT_HEADER_SIZE, EVENT_LENGTH_OFFSET};
+use crate::errors::Error;
+use crate::providers::mariadb::encryption::key_provider::{
+    EncryptionAlgorithm, KeyProvider, BINLOG_KEY_ID,
+};
+use crate::providers::mariadb::events::start_encryption_event::{
+    StartEncryptionEvent, NONCE_LENGTH,
+};
+use openssl::symm::{Cipher, Crypter, Mode};
+
+const BLOCK_SIZE: usize = 16;
+const ENCRYPTED_DATA_OFFSET: usize = 4;
+
+/// Decrypts events written after StartEncryptionEvent.
+/// See Log_event_writer::write_header in MariaDB sources.
+pub struct BinlogDecryptor {
+    key: Vec<u8>,
+    nonce: [u8; NONCE_LENGTH],
+    algorithm: EncryptionAlgorithm,
+}
+
+impl BinlogDecryptor {
+    pub fn new(event: &StartEncryptionEvent, provider: &dyn KeyProvider) -> Result<Self, Error> {
+        if event.scheme != 1 {
+            return Err(Error::String(format!(
+                "Binlog encryption scheme {} is not supported",
+                event.scheme
+            )));
+        }
+
+        Ok(Self {
+            key: provider.get_key(BINLOG_KEY_ID, event.key_version)?,
+            nonce: event.nonce,
+            algorithm: provider.algorithm(),
+        })
+    }
+
+    /// Decrypts the whole event in place. Position is the file offset of the event.
+    pub fn decrypt(&self, event: &mut [u8], position: u32) -> Result<(), Error> {
+        if event.len() < EVENT_HEADER_SIZE {
+            return Err(Error::String("Encrypted event is truncated".to_string()));
+        }
+
+        // Event length is stored unencrypted.
+        // Its encrypted bytes are moved to the place of the timestamp.
+        let event_length = event.len() as u32;
+        event.copy_within(0..4, EVENT_LENGTH_OFFSET);
+
+        let iv = self.create_iv(position);
+        let data = crypt(
+            self.algorithm,
+            Mode::Decrypt,
+            &self.key,
+            &iv,
+            &event[ENCRYPTED_DATA_OFFSET..],
+        )?;
+        event[ENCRYPTED_DATA_OFFSET..].copy_from_slice(&data);
+
+        // Timestamp was moved to the place of event length before encryption.
+        event.copy_within(EVENT_LENGTH_OFFSET..EVENT_LENGTH_OFFSET + 4, 0);
+        event[EVENT_LENGTH_OFFSET..EVENT_LENGTH_OFFSET + 4]
+            .copy_from_slice(&event_length.to_le_bytes());
+        Ok(())
+    }
+
+    fn create_iv(&self, position: u32) -> [u8; BLOCK_SIZE] {
+        let mut iv = [0u8; BLOCK_SIZE];
+        iv[..NONCE_LENGTH].copy_from_slice(&self.nonce);
+        iv[NONCE_LENGTH..].copy_from_slice(&position.to_le_bytes());
+        iv
+    }
+}
+
+/// Encrypts or decrypts data without padding like MyCTX_nopad in MariaDB.
+/// The incomplete last block of CBC mode is XORed with the encrypted IV.
+pub fn crypt(
+    algorithm: EncryptionAlgorithm,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    match algorithm {
+        EncryptionAlgorithm::AesCtr => run_cipher(get_cipher(algorithm, key)?, mode, key, iv, data),
+        EncryptionAlgorithm::AesCbc => {
+            let blocks_length = data.len() / BLOCK_SIZE * BLOCK_SIZE;
+            let cipher = get_cipher(algorithm, key)?;
+            let mut result = run_cipher(cipher, mode, key, iv, &data[..blocks_length])?;
+
+            if blocks_length < data.len() {
+                let ecb = get_ecb_cipher(key)?;
+                let mask = run_cipher(ecb, Mode::Encrypt, key, iv, iv)?;
+                let tail = &data[blocks_length..];
+                result.extend(tail.iter().zip(mask).map(|(byte, mask)| byte ^ mask));
+            }
+            Ok(result)
+        }
+    }
+}
+
+fn run_cipher(
+    cipher: Cipher,
+    mode: Mode,
+    key: &[u8],
+    iv: &[u8],
+    data: &[u8],
+) -> Result<Vec<u8>, Error> {
+    let iv = cipher.iv_len().map(|_| iv);
+    let mut crypter = Crypter::new(cipher, mode, key, iv)?;
+    crypter.pad(false);
+
+    let mut result = vec![0; data.len() + cipher.block_size()];
+    let mut count = crypter.update(data, &mut result)?;
+    count += crypter.finalize(&mut result[count..])?;
+    result.truncate(count);
+    Ok(result)
+}
+
+fn get_cipher(algorithm: EncryptionAlgorithm, key: &[u8]) -> Result<Cipher, Error> {
+    let cipher = match (algorithm, key.len()) {
+        (EncryptionAlgorithm::AesCbc, 16) => Cipher::aes_128_cbc(),
+        (EncryptionAlgorithm::AesCbc, 24) => Cipher::aes_192_cbc(),
+        (EncryptionAlgorithm::AesCbc, 32) => Cipher::aes_256_cbc(),
+        (EncryptionAlgorithm::AesCtr, 16) => Cipher::aes_128_ctr(),
+        (EncryptionAlgorithm::AesCtr, 24) => Cipher::aes_192_ctr(),
+        (EncryptionAlgorithm::AesCtr, 32) => Cipher::aes_256_ctr(),
+        (_, length) => return Err(invalid_key_length(length)),
+    };
+    Ok(cipher)
+}
+
+fn get_ecb_cipher(key: &[u8]) -> Result<Cipher, Error> {
+    match key.len() {
+        16 => Ok(Cipher::aes_128_ecb()),
+        24 => Ok(Cipher::aes_192_ecb()),
+        32 => Ok(Cipher::aes_256_ecb()),
+        length => Err(invalid_key_length(length)),
+    }
+}
+
+fn invalid_key_length(length: usize) -> Error {
+    Error::String(format!("Invalid encryption key length {}", length))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::{crypt, BinlogDecryptor};
+    use crate::constants::EVENT_LENGTH_OFFSET;
+    use crate::providers::mariadb::encryption::file_key_provider::FileKeyProvider;
+    use crate::providers::mariadb::encryption::key_provider::EncryptionAlgorithm;
+    use crate::providers::mariadb::events::start_encryption_event::StartEncryptionEvent;
+    use openssl::symm::Mode;
+
+    pub(crate) const KEY_FILE: &str =
+        "1;a7addd9adea9978fda19f21e6be987880e68ac92632ca052e5bb42b1a506939a";
+
+    /// Encrypts the event at the position the same way as Log_event_writer in MariaDB.
+    pub(crate) fn encrypt_event(
+        event: &[u8],
+        start_event: &StartEncryptionEvent,
+        algorithm: EncryptionAlgorithm,
+        position: u32,
+    ) -> Vec<u8> {
+        let provider = FileKeyProvider::new(KEY_FILE, algorithm).unwrap();
+        let iv = BinlogDecryptor::new(start_event, &provider)
+            .unwrap()
+            .create_iv(position);
+        let key = hex::decode(&KEY_FILE[2..]).unwrap();
+        let mut plain = event.to_vec();
+        plain.copy_within(0..4, EVENT_LENGTH_OFFSET);
+
+        let encrypted = crypt(algorithm, Mode::Encrypt, &key, &iv, &plain[4..]).unwrap();
+        let mut result = encrypted[5..9].to_vec();
+        result.extend_from_slice(&encrypted[0..5]);
+        result.extend_from_slice(&event[EVENT_LENGTH_OFFSET..EVENT_LENGTH_OFFSET + 4]);
+        result.extend_from_slice(&encrypted[9..]);
+        result
+    }
+
+    fn decrypt_event(algorithm: EncryptionAlgorithm, event_length: usize) {
+        let mut event: Vec<u8> = (0..event_length).map(|x| x as u8).collect();
+        event[EVENT_LENGTH_OFFSET..EVENT_LENGTH_OFFSET + 4]
+            .copy_from_slice(&(event_length as u32).to_le_bytes());
+
+        let start_event = StartEncryptionEvent {
+            scheme: 1,
+            key_version: 1,
+            nonce: [7; 12],
+        };
+        let provider = FileKeyProvider::new(KEY_FILE, algorithm).unwrap();
+        let decryptor = BinlogDecryptor::new(&start_event, &provider).unwrap();
+
+        let mut encrypted = encrypt_event(&event, &start_event, algorithm, 256);
+        assert_ne!(event, encrypted);
+
+        decryptor.decrypt(&mut encrypted, 256).unwrap();
+        assert_eq!(event, encrypted);
+    }
+
+    #[test]
+    fn decrypt_cbc_event() {
+        decrypt_event(EncryptionAlgorithm::AesCbc, 68);
+        decrypt_event(EncryptionAlgorithm::AesCbc, 75);
+    }
+
+    #[test]
+    fn decrypt_ctr_event() {
+        decrypt_event(EncryptionAlgorithm::AesCtr, 75);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::errors::Error;
+use crate::providers::mariadb::encryption::key_provider::{EncryptionAlgorithm, KeyProvider};
+use openssl::hash::MessageDigest;
+use openssl::pkcs5::bytes_to_key;
+use openssl::symm::{decrypt, Cipher};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Prefix of key files encrypted by `openssl enc`.
+const SALTED_PREFIX: &[u8] = b"Salted__";
+const SALT_LENGTH: usize = 8;
+
+/// Reads keys in the format of the file_key_management plugin.
+/// Each line contains a key id and a hex-encoded key separated by semicolon.
+/// <a href="https://mariadb.com/kb/en/file-key-management-encryption-plugin/">See more</a>
+#[derive(Debug)]
+pub struct FileKeyProvider {
+    keys: HashMap<u32, Vec<u8>>,
+    algorithm: EncryptionAlgorithm,
+}
+
+impl FileKeyProvider {
+    /// Parses contents of a plain key file.
+    pub fn new(content: &str, algorithm: EncryptionAlgorithm) -> Result<Self, Error> {
+        let mut keys = HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (key_id, key) = line
+                .split_once(';')
+                .ok_or_else(|| Error::String(format!("Invalid key file line: {}", line)))?;
+
+            let key_id: u32 = key_id.trim().parse()?;
+            let key = hex::decode(key.trim())?;
+
+            if key_id == 0 {
+                return Err(Error::String("Key id must be positive".to_string()));
+            }
+            if !matches!(key.len(), 16 | 24 | 32) {
+                return Err(Error::String(format!(
+                    "Key {} must be 16, 24 or 32 bytes long",
+                    key_id
+                )));
+            }
+            if keys.insert(key_id, key).is_some() {
+                return Err(Error::String(format!("Duplicate key id {}", key_id)));
+            }
+        }
+        Ok(Self { keys, algorithm })
+    }
+
+    /// Reads a plain key file.
+    pub fn from_file<P: AsRef<Path>>(
+        path: P,
+        algorithm: EncryptionAlgorithm,
+    ) -> Result<Self, Error> {
+        let content = std::fs::read_to_string(path)?;
+        Self::new(&content, algorithm)
+    }
+
+    /// Reads a key file encrypted with aes-256-cbc using the file_key_management_filekey password.
+    pub fn from_encrypted_file<P: AsRef<Path>>(
+        path: P,
+        file_key: &str,
+        algorithm: EncryptionAlgorithm,
+    ) -> Result<Self, Error> {
+        let content = std::fs::read(path)?;
+        let content = decrypt_key_file(&content, file_key)?;
+        Self::new(&String::from_utf8(content)?, algorithm)
+    }
+}
+
+impl KeyProvider for FileKeyProvider {
+    /// The plugin doesn't support key rotation so key version is ignored.
+    fn get_key(&self, key_id: u32, _key_version: u32) -> Result<Vec<u8>, Error> {
+        match self.keys.get(&key_id) {
+            Some(key) => Ok(key.clone()),
+            None => Err(Error::String(format!(
+                "Encryption key {} not found",
+                key_id
+            ))),
+        }
+    }
+
+    fn algorithm(&self) -> EncryptionAlgorithm {
+        self.algorithm
+    }
+}
+
+fn decrypt_key_file(content: &[u8], file_key: &str) -> Result<Vec<u8>, Error> {
+    if content.len() < SALTED_PREFIX.len() + SALT_LENGTH || !content.starts_with(SALTED_PREFIX) {
+        return Err(Error::String(
+            "Encrypted key file has invalid header".to_string(),
+        ));
+    }
+
+    let salt = &content[SALTED_PREFIX.len()..SALTED_PREFIX.len() + SALT_LENGTH];
+    let cipher = Cipher::aes_256_cbc();
+    let pair = bytes_to_key(
+        cipher,
+        MessageDigest::sha1(),
+        file_key.as_bytes(),
+        Some(salt),
+        1,
+    )?;
+
+    let data = &content[SALTED_PREFIX.len() + SALT_LENGTH..];
+    Ok(decrypt(cipher, &pair.key, pair.iv.as_deref(), data)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decrypt_key_file, FileKeyProvider, SALTED_PREFIX};
+    use crate::providers::mariadb::encryption::key_provider::{EncryptionAlgorithm, KeyProvider};
+    use openssl::hash::MessageDigest;
+    use openssl::pkcs5::bytes_to_key;
+    use openssl::symm::{encrypt, Cipher};
+
+    const KEY_FILE: &str = "# Binlog key\n\
+        1;a7addd9adea9978fda19f21e6be987880e68ac92632ca052e5bb42b1a506939a\n\
+        \n\
+        2;49c16acc2dffe616710c9ba9a10b94944a737de1beccb52dc1560abfdd67388b\n";
+
+    #[test]
+    fn parse_key_file() {
+        let provider = FileKeyProvider::new(KEY_FILE, EncryptionAlgorithm::AesCtr).unwrap();
+
+        assert_eq!(32, provider.get_key(1, 1).unwrap().len());
+        assert_eq!(0x49, provider.get_key(2, 1).unwrap()[0]);
+        assert!(provider.get_key(3, 1).is_err());
+        assert_eq!(EncryptionAlgorithm::AesCtr, provider.algorithm());
+    }
+
+    #[test]
+    fn parse_invalid_key_fails() {
+        assert!(FileKeyProvider::new("1;a7addd", EncryptionAlgorithm::AesCbc).is_err());
+        assert!(FileKeyProvider::new("1a7addd", EncryptionAlgorithm::AesCbc).is_err());
+    }
+
+    #[test]
+    fn decrypt_encrypted_key_file() {
+        let salt = [1, 2, 3, 4, 5, 6, 7, 8];
+        let cipher = Cipher::aes_256_cbc();
+        let pair = bytes_to_key(cipher, MessageDigest::sha1(), b"secret", Some(&salt), 1).unwrap();
+
+        let mut content = SALTED_PREFIX.to_vec();
+        content.extend_from_slice(&salt);
+        content
+            .extend(encrypt(cipher, &pair.key, pair.iv.as_deref(), KEY_FILE.as_bytes()).unwrap());
+
+        let decrypted = decrypt_key_file(&content, "secret").unwrap();
+        assert_eq!(KEY_FILE.as_bytes(), decrypted.as_slice());
+    }
+}
@@ -0,0 +1,28 @@
+use crate::errors::Error;
+
+/// Key id used by MariaDB to encrypt binary logs (ENCRYPTION_KEY_SYSTEM_DATA).
+pub const BINLOG_KEY_ID: u32 = 1;
+
+/// Block cipher mode used by the key management plugin.
+/// See file_key_management_encryption_algorithm server variable.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EncryptionAlgorithm {
+    /// AES in CBC mode. Default in the file_key_management plugin.
+    AesCbc,
+
+    /// AES in CTR mode.
+    AesCtr,
+}
+
+/// Provides encryption keys like MariaDB key management plugins.
+/// Providers are Send so that readers using them can be moved across threads.
+/// <a href="https://mariadb.com/kb/en/encryption-key-management/">See more</a>
+pub trait KeyProvider: Send {
+    /// Returns the key for the specified key id and key version.
+    fn get_key(&self, key_id: u32, key_version: u32) -> Result<Vec<u8>, Error>;
+
+    /// Returns the block cipher mode the keys are used with.
+    fn algorithm(&self) -> EncryptionAlgorithm {
+        EncryptionAlgorithm::AesCbc
+    }
+}
@@ -0,0 +1,4 @@
+pub mod file_key_provider;
+pub mod key_provider;
+
+pub(crate) mod binlog_decryptor;
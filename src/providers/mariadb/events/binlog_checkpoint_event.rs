@@ -0,0 +1,37 @@
+use crate::{errors::Error, extensions::read_string};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+/// Marks binlog file up to which all transactions are durably committed in storage engines.
+/// <a href="https://mariadb.com/kb/en/binlog_checkpoint_event/">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BinlogCheckpointEvent {
+    /// Gets the oldest binlog file needed for crash recovery.
+    pub binlog_filename: String,
+}
+
+impl BinlogCheckpointEvent {
+    /// Parses events in MariaDB 10.0+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let filename_length = cursor.read_u32::<LittleEndian>()?;
+        let binlog_filename = read_string(cursor, filename_length as usize)?;
+
+        Ok(Self { binlog_filename })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BinlogCheckpointEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_binlog_checkpoint_event() {
+        let mut payload = 18u32.to_le_bytes().to_vec();
+        payload.extend_from_slice(b"mariadb-bin.000002");
+
+        let event = BinlogCheckpointEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!("mariadb-bin.000002", event.binlog_filename);
+    }
+}
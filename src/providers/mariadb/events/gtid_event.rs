@@ -1,17 +1,42 @@
+use crate::events::xid::Xid;
 use crate::providers::mariadb::gtid::gtid::Gtid;
 use crate::{errors::Error, events::event_header::EventHeader};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::Cursor;
 
+/// Event group is a single statement that cannot be rolled back.
+pub const FL_STANDALONE: u8 = 1;
+/// Event contains commit id of the group commit.
+pub const FL_GROUP_COMMIT_ID: u8 = 2;
+/// Event group can be safely rolled back.
+pub const FL_TRANSACTIONAL: u8 = 4;
+/// Event group can be applied in parallel in optimistic mode.
+pub const FL_ALLOW_PARALLEL: u8 = 8;
+/// Transaction had to wait for a row lock on the primary.
+pub const FL_WAITED: u8 = 16;
+/// Event group contains DDL.
+pub const FL_DDL: u8 = 32;
+/// Event group is XA PREPARE of an XA transaction.
+pub const FL_PREPARED_XA: u8 = 64;
+/// Event group is XA COMMIT or XA ROLLBACK of an XA transaction.
+pub const FL_COMPLETED_XA: u8 = 128;
+
 /// Marks start of a new event group(transaction).
 /// <a href="https://mariadb.com/kb/en/gtid_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GtidEvent {
     /// Gets Global Transaction ID of the event group.
     pub gtid: Gtid,
 
     /// Gets flags.
     pub flags: u8,
+
+    /// Gets id of the group commit. Transactions with the same id can be applied in parallel.
+    pub commit_id: Option<u64>,
+
+    /// Gets XA transaction id in MariaDB 10.5+.
+    pub xid: Option<Xid>,
 }
 
 impl GtidEvent {
@@ -21,7 +46,103 @@ impl GtidEvent {
         let domain_id = cursor.read_u32::<LittleEndian>()?;
         let flags = cursor.read_u8()?;
 
+        let mut commit_id = None;
+        if flags & FL_GROUP_COMMIT_ID != 0 {
+            commit_id = Some(cursor.read_u64::<LittleEndian>()?);
+        }
+
+        let mut xid = None;
+        if flags & (FL_PREPARED_XA | FL_COMPLETED_XA) != 0 {
+            let format_id = cursor.read_i32::<LittleEndian>()?;
+            let gtrid_length = cursor.read_u8()? as usize;
+            let bqual_length = cursor.read_u8()? as usize;
+            xid = Some(Xid::parse(cursor, format_id, gtrid_length, bqual_length)?);
+        }
+
         let gtid = Gtid::new(domain_id, header.server_id, sequence);
-        Ok(Self { gtid, flags })
+        Ok(Self {
+            gtid,
+            flags,
+            commit_id,
+            xid,
+        })
+    }
+
+    /// Event group is a single statement without BEGIN/COMMIT and XID event.
+    pub fn is_standalone(&self) -> bool {
+        self.flags & FL_STANDALONE != 0
+    }
+
+    /// Event group modifies only transactional tables.
+    pub fn is_transactional(&self) -> bool {
+        self.flags & FL_TRANSACTIONAL != 0
+    }
+
+    /// Event group contains DDL statement.
+    pub fn is_ddl(&self) -> bool {
+        self.flags & FL_DDL != 0
+    }
+
+    /// Event group can be applied in parallel in optimistic mode.
+    pub fn allows_parallel(&self) -> bool {
+        self.flags & FL_ALLOW_PARALLEL != 0
+    }
+
+    /// Transaction had to wait for another one on the primary.
+    pub fn waited(&self) -> bool {
+        self.flags & FL_WAITED != 0
+    }
+
+    /// Event group is XA PREPARE.
+    pub fn is_prepared_xa(&self) -> bool {
+        self.flags & FL_PREPARED_XA != 0
+    }
+
+    /// Event group is XA COMMIT or XA ROLLBACK.
+    pub fn is_completed_xa(&self) -> bool {
+        self.flags & FL_COMPLETED_XA != 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GtidEvent;
+    use crate::events::event_header::EventHeader;
+    use std::io::Cursor;
+
+    fn parse(flags: u8, extra: &[u8]) -> GtidEvent {
+        let mut header = [0u8; 19];
+        header[5] = 1;
+        let header = EventHeader::parse(&header).unwrap();
+
+        let mut payload = 100u64.to_le_bytes().to_vec();
+        payload.extend_from_slice(&0u32.to_le_bytes());
+        payload.push(flags);
+        payload.extend_from_slice(extra);
+        GtidEvent::parse(&mut Cursor::new(&payload), &header).unwrap()
+    }
+
+    #[test]
+    fn parse_standalone_ddl() {
+        let event = parse(1 | 32, &[0; 6]);
+
+        assert_eq!("0-1-100", event.gtid.to_string());
+        assert!(event.is_standalone());
+        assert!(event.is_ddl());
+        assert!(!event.is_transactional());
+        assert_eq!(None, event.commit_id);
+    }
+
+    #[test]
+    fn parse_group_commit_xa() {
+        let mut extra = 55u64.to_le_bytes().to_vec();
+        extra.extend_from_slice(&1i32.to_le_bytes());
+        extra.extend_from_slice(&[2, 1, 0xab, 0xcd, 0xef]);
+        let event = parse(2 | 4 | 64, &extra);
+
+        assert_eq!(Some(55), event.commit_id);
+        assert!(event.is_transactional());
+        assert!(event.is_prepared_xa());
+        assert_eq!("X'abcd',X'ef',1", event.xid.unwrap().to_string());
     }
 }
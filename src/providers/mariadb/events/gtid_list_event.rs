@@ -6,6 +6,7 @@ use std::io::Cursor;
 /// Shows current replication state with list of last gtid for each replication domain.
 /// <a href="https://mariadb.com/kb/en/gtid_list_event/">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GtidListEvent {
     /// Gets a list of Gtid that represents current replication state
     pub gtid_list: GtidList,
@@ -1,2 +1,4 @@
+pub mod binlog_checkpoint_event;
 pub mod gtid_event;
 pub mod gtid_list_event;
+pub mod start_encryption_event;
@@ -0,0 +1,39 @@
+use crate::errors::Error;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+/// Length of the nonce used to build initialization vectors of encrypted events.
+pub const NONCE_LENGTH: usize = 12;
+
+/// Marks that all following events in the binlog file are encrypted.
+/// <a href="https://mariadb.com/kb/en/start_encryption_event/">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StartEncryptionEvent {
+    /// Gets encryption scheme. Only scheme 1 is supported by MariaDB.
+    pub scheme: u8,
+
+    /// Gets version of the encryption key.
+    pub key_version: u32,
+
+    /// Gets nonce that is combined with event position to get initialization vector.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub nonce: [u8; NONCE_LENGTH],
+}
+
+impl StartEncryptionEvent {
+    /// Parses events in MariaDB 10.1.7+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let scheme = cursor.read_u8()?;
+        let key_version = cursor.read_u32::<LittleEndian>()?;
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        cursor.read_exact(&mut nonce)?;
+
+        Ok(Self {
+            scheme,
+            key_version,
+            nonce,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use crate::errors::Error;
 use std::fmt;
 
 /// MariaDB 10.0.2+ representation of Gtid.
@@ -21,6 +22,19 @@ impl Gtid {
             sequence,
         }
     }
+
+    /// Parses Gtid from string representation.
+    pub fn parse(gtid: &str) -> Result<Self, Error> {
+        let components = gtid.trim().split('-').collect::<Vec<&str>>();
+        if components.len() != 3 {
+            return Err(Error::String(format!("Invalid gtid format {}", gtid)));
+        }
+        Ok(Self::new(
+            components[0].parse()?,
+            components[1].parse()?,
+            components[2].parse()?,
+        ))
+    }
 }
 
 impl fmt::Display for Gtid {
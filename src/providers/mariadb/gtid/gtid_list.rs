@@ -28,10 +28,8 @@ impl GtidList {
         let mut gtids = Vec::new();
 
         for gtid in gtid_list {
-            let components = gtid.split('-').collect::<Vec<&str>>();
-            let domain_id: u32 = components[0].parse()?;
-            let server_id: u32 = components[1].parse()?;
-            let sequence: u64 = components[2].parse()?;
+            let gtid = Gtid::parse(gtid)?;
+            let domain_id = gtid.domain_id;
 
             if domain_map.contains(&domain_id) {
                 return Err(Error::String(format!(
@@ -41,7 +39,7 @@ impl GtidList {
                 domain_map.insert(domain_id);
             }
 
-            gtids.push(Gtid::new(domain_id, server_id, sequence));
+            gtids.push(gtid);
         }
 
         Ok(Self { gtids })
@@ -58,6 +56,18 @@ impl GtidList {
         self.gtids.push(gtid);
         true
     }
+
+    /// Returns true if the gtid is at or before the position of its domain.
+    pub fn contains(&self, gtid: &Gtid) -> bool {
+        self.gtids
+            .iter()
+            .any(|x| x.domain_id == gtid.domain_id && gtid.sequence <= x.sequence)
+    }
+
+    /// Returns true if positions of all domains are reached in the other list.
+    pub fn is_subset_of(&self, other: &GtidList) -> bool {
+        self.gtids.iter().all(|x| other.contains(x))
+    }
 }
 
 impl fmt::Display for GtidList {
@@ -141,4 +151,26 @@ mod tests {
             gtid_list.to_string()
         );
     }
+
+    #[test]
+    fn contains_gtids_of_domains() {
+        let gtid_list = GtidList::parse("0-1-270,1-2-120").unwrap();
+
+        assert!(gtid_list.contains(&Gtid::new(0, 3, 270)));
+        assert!(gtid_list.contains(&Gtid::new(1, 2, 1)));
+        assert!(!gtid_list.contains(&Gtid::new(0, 1, 271)));
+        assert!(!gtid_list.contains(&Gtid::new(2, 1, 1)));
+    }
+
+    #[test]
+    fn subset_of_gtid_lists() {
+        let current = GtidList::parse("0-1-270,1-2-120").unwrap();
+
+        assert!(GtidList::parse("0-3-270").unwrap().is_subset_of(&current));
+        assert!(GtidList::new().is_subset_of(&current));
+        assert!(!GtidList::parse("0-1-271").unwrap().is_subset_of(&current));
+        assert!(!GtidList::parse("0-1-1,2-1-1")
+            .unwrap()
+            .is_subset_of(&current));
+    }
 }
@@ -1,3 +1,6 @@
+pub mod encryption;
 pub mod events;
 pub mod gtid;
 pub mod mariadb_provider;
+
+pub(crate) mod compression;
@@ -1,21 +1,51 @@
+use crate::extensions::read_len_enc_num;
 use crate::providers::mysql::gtid::uuid::Uuid;
 use crate::{errors::Error, providers::mysql::gtid::gtid::Gtid};
 use byteorder::{LittleEndian, ReadBytesExt};
 use std::io::{Cursor, Read};
 
+const LOGICAL_TIMESTAMP_TYPE_CODE: u8 = 2;
+const COMMIT_TIMESTAMP_LENGTH: usize = 7;
+const ORIGINAL_COMMIT_TIMESTAMP_FLAG: u64 = 1 << 55;
+const ORIGINAL_SERVER_VERSION_FLAG: u32 = 1 << 31;
+
 /// Marks start of a new event group(transaction).
-/// <a href="https://mariadb.com/kb/en/gtid_event/">See more</a>
+/// Fields following transaction id depend on the server version.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classmysql_1_1binlog_1_1event_1_1Gtid__event.html">See more</a>
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct GtidEvent {
     /// Gets Global Transaction ID of the event group.
     pub gtid: Gtid,
 
     /// Gets flags.
     pub flags: u8,
+
+    /// Gets sequence number of the transaction the current one depends on in MySQL 5.7+.
+    pub last_committed: Option<i64>,
+
+    /// Gets logical clock of the transaction in MySQL 5.7+.
+    pub sequence_number: Option<i64>,
+
+    /// Gets commit timestamp in microseconds on the immediate source in MySQL 8.0.1+.
+    pub immediate_commit_timestamp: Option<u64>,
+
+    /// Gets commit timestamp in microseconds on the original source in MySQL 8.0.1+.
+    pub original_commit_timestamp: Option<u64>,
+
+    /// Gets size of the transaction in bytes in MySQL 8.0.2+.
+    pub transaction_length: Option<u64>,
+
+    /// Gets version of the immediate source in MySQL 8.0.14+.
+    pub immediate_server_version: Option<u32>,
+
+    /// Gets version of the original source in MySQL 8.0.14+.
+    pub original_server_version: Option<u32>,
 }
 
 impl GtidEvent {
     /// Parses events in MySQL 5.6+.
+    /// Also parses AnonymousGtidEvent which has the same layout.
     pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
         let flags = cursor.read_u8()?;
 
@@ -26,6 +56,98 @@ impl GtidEvent {
         let transaction_id = cursor.read_u64::<LittleEndian>()?;
 
         let gtid = Gtid::new(source_id, transaction_id);
-        Ok(Self { gtid, flags })
+        let mut event = Self {
+            gtid,
+            flags,
+            last_committed: None,
+            sequence_number: None,
+            immediate_commit_timestamp: None,
+            original_commit_timestamp: None,
+            transaction_length: None,
+            immediate_server_version: None,
+            original_server_version: None,
+        };
+
+        // MySQL 5.6 events end with transaction id.
+        if !has_remaining(cursor) || cursor.read_u8()? != LOGICAL_TIMESTAMP_TYPE_CODE {
+            return Ok(event);
+        }
+        event.last_committed = Some(cursor.read_i64::<LittleEndian>()?);
+        event.sequence_number = Some(cursor.read_i64::<LittleEndian>()?);
+
+        if has_remaining(cursor) {
+            let timestamp = cursor.read_uint::<LittleEndian>(COMMIT_TIMESTAMP_LENGTH)?;
+            let immediate = timestamp & !ORIGINAL_COMMIT_TIMESTAMP_FLAG;
+            event.immediate_commit_timestamp = Some(immediate);
+            event.original_commit_timestamp = match timestamp & ORIGINAL_COMMIT_TIMESTAMP_FLAG {
+                0 => Some(immediate),
+                _ => Some(cursor.read_uint::<LittleEndian>(COMMIT_TIMESTAMP_LENGTH)?),
+            };
+        }
+
+        if has_remaining(cursor) {
+            event.transaction_length = Some(read_len_enc_num(cursor)? as u64);
+        }
+
+        if has_remaining(cursor) {
+            let version = cursor.read_u32::<LittleEndian>()?;
+            let immediate = version & !ORIGINAL_SERVER_VERSION_FLAG;
+            event.immediate_server_version = Some(immediate);
+            event.original_server_version = match version & ORIGINAL_SERVER_VERSION_FLAG {
+                0 => Some(immediate),
+                _ => Some(cursor.read_u32::<LittleEndian>()?),
+            };
+        }
+        Ok(event)
+    }
+}
+
+fn has_remaining(cursor: &Cursor<&[u8]>) -> bool {
+    (cursor.position() as usize) < cursor.get_ref().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GtidEvent;
+    use std::io::Cursor;
+
+    fn create_event() -> Vec<u8> {
+        let mut payload = vec![1];
+        payload.extend_from_slice(&[0x24; 16]);
+        payload.extend_from_slice(&15u64.to_le_bytes());
+        payload
+    }
+
+    #[test]
+    fn parse_mysql56_event() {
+        let payload = create_event();
+        let event = GtidEvent::parse(&mut Cursor::new(&payload)).unwrap();
+
+        assert_eq!(15, event.gtid.transaction_id);
+        assert_eq!(None, event.last_committed);
+        assert_eq!(None, event.immediate_commit_timestamp);
+    }
+
+    #[test]
+    fn parse_mysql80_event() {
+        let mut payload = create_event();
+        payload.push(2);
+        payload.extend_from_slice(&7i64.to_le_bytes());
+        payload.extend_from_slice(&8i64.to_le_bytes());
+        let immediate: u64 = 1_650_000_000_000_001;
+        payload.extend_from_slice(&(immediate | 1 << 55).to_le_bytes()[..7]);
+        payload.extend_from_slice(&1_650_000_000_000_000u64.to_le_bytes()[..7]);
+        payload.extend_from_slice(&[0xFC, 0x10, 0x27]);
+        payload.extend_from_slice(&80030u32.to_le_bytes());
+
+        let event = GtidEvent::parse(&mut Cursor::new(&payload)).unwrap();
+
+        assert_eq!(Some(7), event.last_committed);
+        assert_eq!(Some(8), event.sequence_number);
+        assert_eq!(Some(immediate), event.immediate_commit_timestamp);
+        assert_eq!(Some(1_650_000_000_000_000), event.original_commit_timestamp);
+        assert_eq!(Some(10000), event.transaction_length);
+        assert_eq!(Some(80030), event.immediate_server_version);
+        assert_eq!(Some(80030), event.original_server_version);
     }
 }
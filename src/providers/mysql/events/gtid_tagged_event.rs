@@ -0,0 +1,195 @@
+use crate::providers::mysql::gtid::uuid::Uuid;
+use crate::{errors::Error, providers::mysql::gtid::gtid::Gtid};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+/// Field ids of the serialized Gtid_event.
+const FLAGS_FIELD: u64 = 0;
+const UUID_FIELD: u64 = 1;
+const GNO_FIELD: u64 = 2;
+const TAG_FIELD: u64 = 3;
+const LAST_COMMITTED_FIELD: u64 = 4;
+const SEQUENCE_NUMBER_FIELD: u64 = 5;
+const IMMEDIATE_COMMIT_TIMESTAMP_FIELD: u64 = 6;
+const ORIGINAL_COMMIT_TIMESTAMP_FIELD: u64 = 7;
+const TRANSACTION_LENGTH_FIELD: u64 = 8;
+const IMMEDIATE_SERVER_VERSION_FIELD: u64 = 9;
+const ORIGINAL_SERVER_VERSION_FIELD: u64 = 10;
+const COMMIT_GROUP_TICKET_FIELD: u64 = 11;
+
+/// Marks start of a new event group(transaction) with a tagged GTID.
+/// Unlike GtidEvent the payload uses the mysql::serialization format.
+/// <a href="https://dev.mysql.com/doc/refman/8.4/en/replication-gtids-concepts.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct GtidTaggedEvent {
+    /// Gets Global Transaction ID of the event group without the tag.
+    pub gtid: Gtid,
+
+    /// Gets tag of the GTID.
+    pub tag: String,
+
+    /// Gets flags.
+    pub flags: u8,
+
+    /// Gets sequence number of the transaction the current one depends on.
+    pub last_committed: i64,
+
+    /// Gets logical clock of the transaction.
+    pub sequence_number: i64,
+
+    /// Gets commit timestamp in microseconds on the immediate source.
+    pub immediate_commit_timestamp: u64,
+
+    /// Gets commit timestamp in microseconds on the original source.
+    pub original_commit_timestamp: u64,
+
+    /// Gets size of the transaction in bytes.
+    pub transaction_length: u64,
+
+    /// Gets version of the immediate source.
+    pub immediate_server_version: u32,
+
+    /// Gets version of the original source.
+    pub original_server_version: u32,
+
+    /// Gets ticket of the binlog commit group.
+    pub commit_group_ticket: Option<u64>,
+}
+
+impl GtidTaggedEvent {
+    /// Parses events in MySQL 8.3+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let _format_version = read_unsigned(cursor)?;
+        let _message_size = read_unsigned(cursor)?;
+        let last_non_ignorable_field_id = read_unsigned(cursor)?;
+
+        let mut source_id = [0u8; 16];
+        let mut transaction_id = 0;
+        let mut original_commit_timestamp = None;
+        let mut original_server_version = None;
+        let mut event = Self {
+            gtid: Gtid::new(Uuid::new(source_id), 0),
+            tag: String::new(),
+            flags: 0,
+            last_committed: 0,
+            sequence_number: 0,
+            immediate_commit_timestamp: 0,
+            original_commit_timestamp: 0,
+            transaction_length: 0,
+            immediate_server_version: 0,
+            original_server_version: 0,
+            commit_group_ticket: None,
+        };
+
+        while (cursor.position() as usize) < cursor.get_ref().len() {
+            match read_unsigned(cursor)? {
+                FLAGS_FIELD => event.flags = cursor.read_u8()?,
+                UUID_FIELD => cursor.read_exact(&mut source_id)?,
+                GNO_FIELD => transaction_id = read_signed(cursor)? as u64,
+                TAG_FIELD => {
+                    let mut tag = vec![0; read_unsigned(cursor)? as usize];
+                    cursor.read_exact(&mut tag)?;
+                    event.tag = String::from_utf8(tag)?;
+                }
+                LAST_COMMITTED_FIELD => event.last_committed = read_signed(cursor)?,
+                SEQUENCE_NUMBER_FIELD => event.sequence_number = read_signed(cursor)?,
+                IMMEDIATE_COMMIT_TIMESTAMP_FIELD => {
+                    event.immediate_commit_timestamp = read_unsigned(cursor)?
+                }
+                ORIGINAL_COMMIT_TIMESTAMP_FIELD => {
+                    original_commit_timestamp = Some(read_unsigned(cursor)?)
+                }
+                TRANSACTION_LENGTH_FIELD => event.transaction_length = read_unsigned(cursor)?,
+                IMMEDIATE_SERVER_VERSION_FIELD => {
+                    event.immediate_server_version = read_unsigned(cursor)? as u32
+                }
+                ORIGINAL_SERVER_VERSION_FIELD => {
+                    original_server_version = Some(read_unsigned(cursor)? as u32)
+                }
+                COMMIT_GROUP_TICKET_FIELD => {
+                    event.commit_group_ticket = Some(read_unsigned(cursor)?)
+                }
+                field_id if field_id <= last_non_ignorable_field_id => {
+                    return Err(Error::String(format!(
+                        "Unknown non-ignorable GTID event field {}",
+                        field_id
+                    )))
+                }
+                // Fields added in newer versions can be skipped
+                _ => break,
+            }
+        }
+
+        // Original values are omitted when they are equal to immediate ones.
+        event.gtid = Gtid::new(Uuid::new(source_id), transaction_id);
+        event.original_commit_timestamp =
+            original_commit_timestamp.unwrap_or(event.immediate_commit_timestamp);
+        event.original_server_version =
+            original_server_version.unwrap_or(event.immediate_server_version);
+        Ok(event)
+    }
+}
+
+/// Reads variable-length integer where the number of trailing ones
+/// in the first byte gives the number of following bytes.
+pub(crate) fn read_unsigned(cursor: &mut Cursor<&[u8]>) -> Result<u64, Error> {
+    let first_byte = cursor.read_u8()?;
+    let length = first_byte.trailing_ones() as usize + 1;
+    if length > 8 {
+        return Ok(cursor.read_u64::<LittleEndian>()?);
+    }
+
+    let mut bytes = [0u8; 8];
+    bytes[0] = first_byte;
+    cursor.read_exact(&mut bytes[1..length])?;
+    Ok(u64::from_le_bytes(bytes) >> length)
+}
+
+/// Reads variable-length integer with the sign stored in the lowest bit.
+fn read_signed(cursor: &mut Cursor<&[u8]>) -> Result<i64, Error> {
+    let value = read_unsigned(cursor)?;
+    Ok(((value >> 1) as i64) ^ -((value & 1) as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_signed, read_unsigned, GtidTaggedEvent};
+    use std::io::Cursor;
+
+    #[test]
+    fn read_variable_length_integers() {
+        let read = |bytes: &[u8]| read_unsigned(&mut Cursor::new(bytes)).unwrap();
+        assert_eq!(5, read(&[5 << 1]));
+        assert_eq!(300, read(&[(300 << 2 | 1) as u8, (300 >> 6) as u8]));
+        assert_eq!(u64::MAX, read(&[0xff; 9]));
+
+        assert_eq!(-3, read_signed(&mut Cursor::new(&[5 << 1][..])).unwrap());
+        assert_eq!(3, read_signed(&mut Cursor::new(&[6 << 1][..])).unwrap());
+    }
+
+    #[test]
+    fn parse_tagged_event() {
+        let mut payload = vec![2, 100, 20];
+        payload.extend_from_slice(&[0, 1]);
+        payload.push(1 << 1);
+        payload.extend_from_slice(&[0x24; 16]);
+        payload.extend_from_slice(&[2 << 1, 14 << 1]);
+        payload.extend_from_slice(&[3 << 1, 3 << 1]);
+        payload.extend_from_slice(b"abc");
+        payload.extend_from_slice(&[4 << 1, 2 << 1, 5 << 1, 4 << 1, 6 << 1, 9 << 1]);
+        payload.extend_from_slice(&[8 << 1, 100 << 1, 9 << 1, 0x83, 0xd0, 0x09]);
+        let event = GtidTaggedEvent::parse(&mut Cursor::new(&payload)).unwrap();
+
+        assert_eq!(7, event.gtid.transaction_id);
+        assert_eq!("abc", event.tag);
+        assert_eq!(1, event.flags);
+        assert_eq!(1, event.last_committed);
+        assert_eq!(2, event.sequence_number);
+        assert_eq!(9, event.original_commit_timestamp);
+        assert_eq!(100, event.transaction_length);
+        assert_eq!(80400, event.immediate_server_version);
+        assert_eq!(80400, event.original_server_version);
+        assert_eq!(None, event.commit_group_ticket);
+    }
+}
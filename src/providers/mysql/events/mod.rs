@@ -1,2 +1,6 @@
 pub mod gtid_event;
+pub mod gtid_tagged_event;
 pub mod prev_gtids_event;
+pub mod transaction_context_event;
+pub mod view_change_event;
+pub mod xa_prepare_event;
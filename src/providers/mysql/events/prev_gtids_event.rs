@@ -1,3 +1,5 @@
+use crate::providers::mysql::events::gtid_tagged_event::read_unsigned;
+use crate::providers::mysql::gtid::gtid_set::TAGGED_FORMAT;
 use crate::providers::mysql::gtid::interval::Interval;
 use crate::providers::mysql::gtid::uuid::Uuid;
 use crate::providers::mysql::gtid::uuid_set::UuidSet;
@@ -7,6 +9,7 @@ use std::io::{Cursor, Read};
 
 /// Used to record the gtid_executed of previous binlog files.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PreviousGtidsEvent {
     /// Gets GtidSet of previous files.
     pub gtid_set: GtidSet,
@@ -16,6 +19,12 @@ impl PreviousGtidsEvent {
     /// Parses events in MySQL 5.6+.
     pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
         let uuid_set_number = cursor.read_u64::<LittleEndian>()?;
+        let tagged =
+            uuid_set_number & 0xFF == TAGGED_FORMAT && uuid_set_number >> 56 == TAGGED_FORMAT;
+        let uuid_set_number = match tagged {
+            true => (uuid_set_number >> 8) & 0xFFFF_FFFF_FFFF,
+            false => uuid_set_number,
+        };
         let mut gtid_set = GtidSet::new();
 
         for _i in 0..uuid_set_number {
@@ -23,16 +32,28 @@ impl PreviousGtidsEvent {
             cursor.read_exact(&mut source_id)?;
             let source_id = Uuid::new(source_id);
 
-            let mut uuid_set = UuidSet::new(source_id, Vec::new());
+            // Each tag of the server uuid is stored as a separate set in MySQL 8.3+
+            let mut tag = Vec::new();
+            if tagged {
+                tag.resize(read_unsigned(cursor)? as usize, 0);
+                cursor.read_exact(&mut tag)?;
+            }
+            let tag = String::from_utf8(tag)?;
+
+            let uuid_set = gtid_set
+                .uuid_sets
+                .entry(source_id.uuid.clone())
+                .or_insert(UuidSet::new(source_id, Vec::new()));
+            let intervals = match tag.is_empty() {
+                true => &mut uuid_set.intervals,
+                false => uuid_set.tagged_intervals.entry(tag).or_default(),
+            };
             let interval_number = cursor.read_u64::<LittleEndian>()?;
             for _y in 0..interval_number {
                 let start = cursor.read_u64::<LittleEndian>()?;
                 let end = cursor.read_u64::<LittleEndian>()?;
-                uuid_set.intervals.push(Interval::new(start, end - 1));
+                intervals.push(Interval::new(start, end - 1));
             }
-            gtid_set
-                .uuid_sets
-                .insert(uuid_set.source_id.uuid.clone(), uuid_set);
         }
 
         Ok(Self { gtid_set })
@@ -58,4 +79,21 @@ mod tests {
         let event = PreviousGtidsEvent::parse(&mut cursor).unwrap();
         assert_eq!(expected, event.gtid_set.to_string());
     }
+
+    #[test]
+    fn parse_tagged_gtid_set() {
+        let mut payload: Vec<u8> = vec![1, 2, 0, 0, 0, 0, 0, 1];
+        for tag in ["", "abc"] {
+            payload.extend_from_slice(&[0x24; 16]);
+            payload.push((tag.len() << 1) as u8);
+            payload.extend_from_slice(tag.as_bytes());
+            payload.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+            payload.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0, 6, 0, 0, 0, 0, 0, 0, 0]);
+        }
+        let mut cursor = Cursor::new(payload.as_slice());
+
+        let expected = String::from("24242424-2424-2424-2424-242424242424:1-5:abc:1-5");
+        let event = PreviousGtidsEvent::parse(&mut cursor).unwrap();
+        assert_eq!(expected, event.gtid_set.to_string());
+    }
 }
@@ -0,0 +1,99 @@
+use crate::{errors::Error, extensions::read_string};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+/// Carries transaction context used by Group Replication certification.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1Transaction__context__event.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TransactionContextEvent {
+    /// Gets UUID of the server where the transaction was executed.
+    pub server_uuid: String,
+
+    /// Gets id of the thread that executed the transaction.
+    pub thread_id: u32,
+
+    /// Gets whether GTID was specified by the user.
+    pub gtid_specified: bool,
+
+    /// Gets encoded database snapshot version.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64"))]
+    pub snapshot_version: Vec<u8>,
+
+    /// Gets hashes of rows written by the transaction.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64_vec"))]
+    pub write_set: Vec<Vec<u8>>,
+
+    /// Gets hashes of rows read by the transaction.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serialization::base64_vec"))]
+    pub read_set: Vec<Vec<u8>>,
+}
+
+impl TransactionContextEvent {
+    /// Parses events in MySQL 5.7.6+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let server_uuid_length = cursor.read_u8()? as usize;
+        let thread_id = cursor.read_u32::<LittleEndian>()?;
+        let gtid_specified = cursor.read_u8()? != 0;
+        let snapshot_version_length = cursor.read_u32::<LittleEndian>()? as usize;
+        let write_set_length = cursor.read_u32::<LittleEndian>()?;
+        let read_set_length = cursor.read_u32::<LittleEndian>()?;
+
+        let server_uuid = read_string(cursor, server_uuid_length)?;
+
+        let mut snapshot_version = vec![0; snapshot_version_length];
+        cursor.read_exact(&mut snapshot_version)?;
+
+        let write_set = read_set_items(cursor, write_set_length)?;
+        let read_set = read_set_items(cursor, read_set_length)?;
+
+        Ok(Self {
+            server_uuid,
+            thread_id,
+            gtid_specified,
+            snapshot_version,
+            write_set,
+            read_set,
+        })
+    }
+}
+
+fn read_set_items(cursor: &mut Cursor<&[u8]>, count: u32) -> Result<Vec<Vec<u8>>, Error> {
+    let mut items = Vec::new();
+    for _ in 0..count {
+        let length = cursor.read_u16::<LittleEndian>()? as usize;
+        let mut item = vec![0; length];
+        cursor.read_exact(&mut item)?;
+        items.push(item);
+    }
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransactionContextEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_transaction_context_event() {
+        let server_uuid = "24bc7850-2c16-11e6-a073-0242ac110002";
+        let mut payload = vec![server_uuid.len() as u8];
+        payload.extend_from_slice(&12u32.to_le_bytes());
+        payload.push(1);
+        payload.extend_from_slice(&3u32.to_le_bytes());
+        payload.extend_from_slice(&2u32.to_le_bytes());
+        payload.extend_from_slice(&1u32.to_le_bytes());
+        payload.extend_from_slice(server_uuid.as_bytes());
+        payload.extend_from_slice(&[7, 8, 9]);
+        payload.extend_from_slice(&[2, 0, 0xaa, 0xbb, 1, 0, 0xcc]);
+        payload.extend_from_slice(&[3, 0, 1, 2, 3]);
+
+        let event = TransactionContextEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!(server_uuid, event.server_uuid);
+        assert_eq!(12, event.thread_id);
+        assert!(event.gtid_specified);
+        assert_eq!(vec![7, 8, 9], event.snapshot_version);
+        assert_eq!(vec![vec![0xaa, 0xbb], vec![0xcc]], event.write_set);
+        assert_eq!(vec![vec![1, 2, 3]], event.read_set);
+    }
+}
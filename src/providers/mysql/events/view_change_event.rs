@@ -0,0 +1,80 @@
+use crate::{errors::Error, extensions::read_string};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+
+const VIEW_ID_LENGTH: usize = 40;
+
+/// Written by Group Replication when group membership changes.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1View__change__event.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ViewChangeEvent {
+    /// Gets id of the new view.
+    pub view_id: String,
+
+    /// Gets sequence number of the view change.
+    pub sequence_number: u64,
+
+    /// Gets certification information of the group.
+    pub certification_info: HashMap<String, Vec<u8>>,
+}
+
+impl ViewChangeEvent {
+    /// Parses events in MySQL 5.7.6+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        // View id is padded with zeros
+        let view_id = read_string(cursor, VIEW_ID_LENGTH)?
+            .trim_end_matches('\0')
+            .to_string();
+        let sequence_number = cursor.read_u64::<LittleEndian>()?;
+        let certification_info_size = cursor.read_u32::<LittleEndian>()?;
+
+        let mut certification_info = HashMap::new();
+        for _ in 0..certification_info_size {
+            let key_length = cursor.read_u16::<LittleEndian>()? as usize;
+            let key = read_string(cursor, key_length)?;
+
+            let value_length = cursor.read_u32::<LittleEndian>()? as usize;
+            let mut value = vec![0; value_length];
+            cursor.read_exact(&mut value)?;
+            certification_info.insert(key, value);
+        }
+
+        Ok(Self {
+            view_id,
+            sequence_number,
+            certification_info,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ViewChangeEvent, VIEW_ID_LENGTH};
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_view_change_event() {
+        let mut payload = b"16925301745537254:7".to_vec();
+        payload.resize(VIEW_ID_LENGTH, 0);
+        payload.extend_from_slice(&5u64.to_le_bytes());
+        payload.extend_from_slice(&2u32.to_le_bytes());
+        for (key, value) in [("group_gtid_executed", &b"uuid:1-5"[..]), ("key", &[1, 2])] {
+            payload.extend_from_slice(&(key.len() as u16).to_le_bytes());
+            payload.extend_from_slice(key.as_bytes());
+            payload.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            payload.extend_from_slice(value);
+        }
+
+        let event = ViewChangeEvent::parse(&mut Cursor::new(payload.as_slice())).unwrap();
+        assert_eq!("16925301745537254:7", event.view_id);
+        assert_eq!(5, event.sequence_number);
+        assert_eq!(2, event.certification_info.len());
+        assert_eq!(
+            b"uuid:1-5",
+            &event.certification_info["group_gtid_executed"][..]
+        );
+        assert_eq!(vec![1, 2], event.certification_info["key"]);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::{errors::Error, events::xid::Xid};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::Cursor;
+
+/// Ends the event group of XA PREPARE or XA COMMIT ... ONE PHASE statement.
+/// <a href="https://dev.mysql.com/doc/dev/mysql-server/latest/classbinary__log_1_1XA__prepare__event.html">See more</a>
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct XaPrepareEvent {
+    /// Gets whether the transaction was committed with XA COMMIT ... ONE PHASE.
+    pub one_phase: bool,
+
+    /// Gets id of the XA transaction.
+    pub xid: Xid,
+}
+
+impl XaPrepareEvent {
+    /// Parses events in MySQL 5.7.7+.
+    pub fn parse(cursor: &mut Cursor<&[u8]>) -> Result<Self, Error> {
+        let one_phase = cursor.read_u8()? != 0;
+        let format_id = cursor.read_i32::<LittleEndian>()?;
+        let gtrid_length = cursor.read_u32::<LittleEndian>()? as usize;
+        let bqual_length = cursor.read_u32::<LittleEndian>()? as usize;
+        let xid = Xid::parse(cursor, format_id, gtrid_length, bqual_length)?;
+
+        Ok(Self { one_phase, xid })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XaPrepareEvent;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_xa_prepare_event() {
+        let mut payload = vec![1];
+        payload.extend_from_slice(&1i32.to_le_bytes());
+        payload.extend_from_slice(&3u32.to_le_bytes());
+        payload.extend_from_slice(&1u32.to_le_bytes());
+        payload.extend_from_slice(b"trxb");
+
+        let event = XaPrepareEvent::parse(&mut Cursor::new(&payload)).unwrap();
+        assert!(event.one_phase);
+        assert_eq!(b"trx", event.xid.gtrid.as_slice());
+        assert_eq!(b"b", event.xid.bqual.as_slice());
+        assert_eq!("X'747278',X'62',1", event.xid.to_string());
+    }
+}
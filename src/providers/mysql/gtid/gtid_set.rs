@@ -2,11 +2,16 @@ use crate::errors::Error;
 use crate::providers::mysql::gtid::gtid::Gtid;
 use crate::providers::mysql::gtid::interval::Interval;
 use crate::providers::mysql::gtid::uuid::Uuid;
-use crate::providers::mysql::gtid::uuid_set::UuidSet;
-use std::collections::HashMap;
+use crate::providers::mysql::gtid::uuid_set::{collapse_intervals, UuidSet};
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 const UUID_LENGTH: usize = 36;
+const TAG_MAX_LENGTH: usize = 32;
+
+/// Format of binary GtidSet with tags in MySQL 8.3+.
+/// Stored in the first and the last byte of the number of sets.
+pub(crate) const TAGGED_FORMAT: u64 = 1;
 
 /// Represents GtidSet from MySQL 5.6 and above.
 /// <a href="https://dev.mysql.com/doc/refman/8.0/en/replication-gtids-concepts.html">See more</a>
@@ -38,19 +43,40 @@ impl GtidSet {
             let source_id = Uuid::parse(source_id)?;
 
             let mut intervals = Vec::new();
+            let mut tagged_intervals = BTreeMap::<String, Vec<Interval>>::new();
+            let mut tag = None;
             let ranges: String = uuid_set.chars().skip(UUID_LENGTH + 1).collect();
             let ranges = ranges.split(':').collect::<Vec<&str>>();
 
             for token in ranges {
+                // Intervals following the tag belong to it in MySQL 8.3+
+                if token.starts_with(|x: char| x.is_ascii_alphabetic() || x == '_') {
+                    tag = Some(parse_tag(token)?);
+                    continue;
+                }
                 let range = token.split('-').collect::<Vec<&str>>();
                 let interval = match range.len() {
                     1 => Interval::new(range[0].parse()?, range[0].parse()?),
                     2 => Interval::new(range[0].parse()?, range[1].parse()?),
                     _ => return Err(Error::String(format!("Invalid interval format {}", token))),
                 };
-                intervals.push(interval);
+                match &tag {
+                    Some(tag) => tagged_intervals
+                        .entry(tag.clone())
+                        .or_default()
+                        .push(interval),
+                    None => intervals.push(interval),
+                }
+            }
+
+            let mut uuid_set = UuidSet::new(source_id, intervals);
+            for (tag, mut intervals) in tagged_intervals {
+                if intervals.len() > 1 {
+                    collapse_intervals(&mut intervals);
+                }
+                uuid_set.tagged_intervals.insert(tag, intervals);
             }
-            result.insert(source_id.uuid.clone(), UuidSet::new(source_id, intervals));
+            result.insert(uuid_set.source_id.uuid.clone(), uuid_set);
         }
 
         Ok(Self { uuid_sets: result })
@@ -65,6 +91,62 @@ impl GtidSet {
 
         Ok(uuid_set.add_gtid(gtid)?)
     }
+
+    /// Adds a gtid value with the tag to the GtidSet.
+    /// Empty tag stands for untagged GTID.
+    pub fn add_tagged_gtid(&mut self, gtid: Gtid, tag: &str) -> Result<bool, Error> {
+        let uuid_set = self
+            .uuid_sets
+            .entry(gtid.source_id.uuid.clone())
+            .or_insert(UuidSet::new(gtid.source_id.clone(), Vec::new()));
+
+        uuid_set.add_tagged_gtid(gtid, tag)
+    }
+
+    /// Returns true if the gtid belongs to the GtidSet.
+    pub fn contains(&self, gtid: &Gtid) -> bool {
+        self.contains_tagged(gtid, "")
+    }
+
+    /// Returns true if the gtid with the tag belongs to the GtidSet.
+    pub fn contains_tagged(&self, gtid: &Gtid, tag: &str) -> bool {
+        match self.uuid_sets.get(&gtid.source_id.uuid) {
+            Some(uuid_set) => uuid_set.contains_tagged(gtid.transaction_id, tag),
+            None => false,
+        }
+    }
+
+    /// Returns true if every transaction of the set is contained in the other set.
+    pub fn is_subset_of(&self, other: &GtidSet) -> bool {
+        self.uuid_sets.iter().all(|(uuid, uuid_set)| {
+            let other = other.uuid_sets.get(uuid);
+            uuid_set.intervals_by_tag().all(|(tag, intervals)| {
+                let other = other.map_or(&[][..], |x| x.get_intervals(tag));
+                intervals.iter().all(|interval| {
+                    other
+                        .iter()
+                        .any(|x| x.start <= interval.start && interval.end <= x.end)
+                })
+            })
+        })
+    }
+
+    /// Returns true if any UuidSet has tagged intervals.
+    pub fn has_tags(&self) -> bool {
+        self.uuid_sets
+            .values()
+            .any(|x| !x.tagged_intervals.is_empty())
+    }
+}
+
+/// Tags are case-insensitive and stored in lowercase like in MySQL Server.
+fn parse_tag(tag: &str) -> Result<String, Error> {
+    let valid =
+        tag.len() <= TAG_MAX_LENGTH && tag.chars().all(|x| x.is_ascii_alphanumeric() || x == '_');
+    match valid {
+        true => Ok(tag.to_ascii_lowercase()),
+        false => Err(Error::String(format!("Invalid tag format {}", tag))),
+    }
 }
 
 impl fmt::Display for GtidSet {
@@ -289,4 +371,93 @@ mod tests {
             gtid_set.to_string()
         );
     }
+
+    #[test]
+    fn contains_gtids_of_intervals() {
+        let gtid_set = GtidSet::parse(&format!("{}:1-3:11", SERVER_UUID1)).unwrap();
+
+        assert!(gtid_set.contains(&Gtid::new(create_uuid1(), 2)));
+        assert!(gtid_set.contains(&Gtid::new(create_uuid1(), 11)));
+        assert!(!gtid_set.contains(&Gtid::new(create_uuid1(), 4)));
+        assert!(!gtid_set.contains(&Gtid::new(create_uuid2(), 1)));
+    }
+
+    #[test]
+    fn subset_of_gtid_sets() {
+        let executed =
+            GtidSet::parse(&format!("{}:1-20:30-40,{}:1-5", SERVER_UUID1, SERVER_UUID2)).unwrap();
+
+        let committed = GtidSet::parse(&format!("{}:1-15:32", SERVER_UUID1)).unwrap();
+        assert!(committed.is_subset_of(&executed));
+        assert!(GtidSet::new().is_subset_of(&executed));
+
+        let committed = GtidSet::parse(&format!("{}:1-21", SERVER_UUID1)).unwrap();
+        assert!(!committed.is_subset_of(&executed));
+        let committed = GtidSet::parse(&format!("{}:1-6", SERVER_UUID2)).unwrap();
+        assert!(!committed.is_subset_of(&executed));
+        assert!(!executed.is_subset_of(&GtidSet::new()));
+    }
+
+    #[test]
+    fn parse_tagged_intervals() {
+        let gtid_set = GtidSet::parse(&format!(
+            "{}:1-5:Beta:3:7-9:alpha:1-2:3,{}:_x:4",
+            SERVER_UUID1, SERVER_UUID2
+        ))
+        .unwrap();
+
+        let uuid_set = gtid_set.uuid_sets.get(SERVER_UUID1).unwrap();
+        assert_eq!(1, uuid_set.intervals.len());
+        assert_eq!(1, uuid_set.tagged_intervals["alpha"].len());
+        assert_eq!(2, uuid_set.tagged_intervals["beta"].len());
+        assert_eq!(
+            format!(
+                "{}:1-5:alpha:1-3:beta:3:7-9,{}:_x:4",
+                SERVER_UUID1, SERVER_UUID2
+            ),
+            gtid_set.to_string()
+        );
+
+        assert!(gtid_set.contains_tagged(&Gtid::new(create_uuid1(), 8), "beta"));
+        assert!(!gtid_set.contains_tagged(&Gtid::new(create_uuid1(), 5), "beta"));
+        assert!(!gtid_set.contains(&Gtid::new(create_uuid2(), 4)));
+        assert!(GtidSet::parse(&format!("{}:1:a-b:2", SERVER_UUID1)).is_err());
+    }
+
+    #[test]
+    fn add_tagged_gtids() {
+        let mut gtid_set = GtidSet::parse(&format!("{}:1-3", SERVER_UUID1)).unwrap();
+
+        gtid_set
+            .add_tagged_gtid(Gtid::new(create_uuid1(), 1), "tag")
+            .unwrap();
+        gtid_set
+            .add_tagged_gtid(Gtid::new(create_uuid1(), 2), "tag")
+            .unwrap();
+        gtid_set
+            .add_tagged_gtid(Gtid::new(create_uuid1(), 4), "")
+            .unwrap();
+        gtid_set
+            .add_tagged_gtid(Gtid::new(create_uuid2(), 7), "tag")
+            .unwrap();
+
+        assert_eq!(
+            format!("{}:1-4:tag:1-2,{}:tag:7", SERVER_UUID1, SERVER_UUID2),
+            gtid_set.to_string()
+        );
+        assert!(gtid_set.has_tags());
+    }
+
+    #[test]
+    fn subset_of_tagged_gtid_sets() {
+        let executed = GtidSet::parse(&format!("{}:1-20:tag:1-5", SERVER_UUID1)).unwrap();
+
+        let committed = GtidSet::parse(&format!("{}:1-10:tag:2-5", SERVER_UUID1)).unwrap();
+        assert!(committed.is_subset_of(&executed));
+
+        let committed = GtidSet::parse(&format!("{}:tag:6", SERVER_UUID1)).unwrap();
+        assert!(!committed.is_subset_of(&executed));
+        let committed = GtidSet::parse(&format!("{}:other:1", SERVER_UUID1)).unwrap();
+        assert!(!committed.is_subset_of(&executed));
+    }
 }
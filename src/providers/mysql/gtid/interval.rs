@@ -2,6 +2,7 @@ use std::fmt;
 
 /// Represents contiguous transaction interval in GtidSet.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Interval {
     /// Gets first transaction id in the interval.
     pub start: u64,
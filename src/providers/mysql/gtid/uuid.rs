@@ -23,6 +23,9 @@ impl Uuid {
     pub fn parse(uuid: String) -> Result<Self, Error> {
         let hex = uuid.replace("-", "");
         let vec = hex::decode(hex)?;
+        if vec.len() != 16 {
+            return Err(Error::String(format!("Invalid uuid format {}", uuid)));
+        }
 
         let mut data = [0u8; 16];
         (0..16).for_each(|i| data[i] = vec[i]);
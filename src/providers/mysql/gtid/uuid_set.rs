@@ -2,16 +2,22 @@ use crate::errors::Error;
 use crate::providers::mysql::gtid::gtid::Gtid;
 use crate::providers::mysql::gtid::interval::Interval;
 use crate::providers::mysql::gtid::uuid::Uuid;
+use std::collections::BTreeMap;
 use std::fmt;
 
 /// Represents replication state for a specific server.
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct UuidSet {
     /// Gets server uuid of the UuidSet.
     pub source_id: Uuid,
 
     /// Gets a list of intervals of the UuidSet.
     pub intervals: Vec<Interval>,
+
+    /// Gets intervals of tagged GTIDs by tag in MySQL 8.3+.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub tagged_intervals: BTreeMap<String, Vec<Interval>>,
 }
 
 impl UuidSet {
@@ -22,21 +28,32 @@ impl UuidSet {
         Self {
             source_id,
             intervals,
+            tagged_intervals: BTreeMap::new(),
         }
     }
 
     /// Adds a gtid value to the UuidSet.
     pub fn add_gtid(&mut self, gtid: Gtid) -> Result<bool, Error> {
+        self.add_tagged_gtid(gtid, "")
+    }
+
+    /// Adds a gtid value with the tag to the UuidSet.
+    /// Empty tag stands for untagged GTID.
+    pub fn add_tagged_gtid(&mut self, gtid: Gtid, tag: &str) -> Result<bool, Error> {
         if self.source_id.data != gtid.source_id.data {
             return Err(Error::String(
                 "SourceId of the passed gtid doesn't belong to the UuidSet".to_string(),
             ));
         }
 
-        let index = find_interval_index(&self.intervals, gtid.transaction_id);
+        let intervals = match tag {
+            "" => &mut self.intervals,
+            tag => self.tagged_intervals.entry(tag.to_string()).or_default(),
+        };
+        let index = find_interval_index(intervals, gtid.transaction_id);
         let mut added = false;
-        if index < self.intervals.len() {
-            let interval = &mut self.intervals[index];
+        if index < intervals.len() {
+            let interval = &mut intervals[index];
             if interval.start == gtid.transaction_id + 1 {
                 interval.start = gtid.transaction_id;
                 added = true;
@@ -49,13 +66,42 @@ impl UuidSet {
         }
         if !added {
             let interval = Interval::new(gtid.transaction_id, gtid.transaction_id);
-            self.intervals.insert(index, interval);
+            intervals.insert(index, interval);
         }
-        if self.intervals.len() > 1 {
-            collapse_intervals(&mut self.intervals);
+        if intervals.len() > 1 {
+            collapse_intervals(intervals);
         }
         Ok(true)
     }
+
+    /// Returns true if the transaction id belongs to one of the intervals.
+    pub fn contains(&self, transaction_id: u64) -> bool {
+        self.contains_tagged(transaction_id, "")
+    }
+
+    /// Returns true if the transaction id belongs to one of the intervals of the tag.
+    pub fn contains_tagged(&self, transaction_id: u64, tag: &str) -> bool {
+        self.get_intervals(tag)
+            .iter()
+            .any(|x| x.start <= transaction_id && transaction_id <= x.end)
+    }
+
+    /// Gets intervals of the tag. Empty tag returns intervals of untagged GTIDs.
+    pub fn get_intervals(&self, tag: &str) -> &[Interval] {
+        match tag {
+            "" => &self.intervals,
+            tag => self.tagged_intervals.get(tag).map_or(&[], |x| x),
+        }
+    }
+
+    /// Returns intervals by tag starting with untagged ones.
+    pub fn intervals_by_tag(&self) -> impl Iterator<Item = (&str, &[Interval])> {
+        let tagged = self
+            .tagged_intervals
+            .iter()
+            .map(|(tag, intervals)| (tag.as_str(), intervals.as_slice()));
+        std::iter::once(("", self.intervals.as_slice())).chain(tagged)
+    }
 }
 
 pub fn find_interval_index(intervals: &Vec<Interval>, transaction_id: u64) -> usize {
@@ -100,13 +146,16 @@ pub fn collapse_intervals(intervals: &mut Vec<Interval>) {
 impl fmt::Display for UuidSet {
     /// Returns string representation of an UuidSet part of a GtidSet.
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let intervals = self
-            .intervals
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<String>>()
-            .join(":");
+        let mut parts = Vec::new();
+        for (tag, intervals) in self.intervals_by_tag() {
+            if !tag.is_empty() {
+                parts.push(tag.to_string());
+            } else if intervals.is_empty() && !self.tagged_intervals.is_empty() {
+                continue;
+            }
+            parts.extend(intervals.iter().map(|x| x.to_string()));
+        }
 
-        write!(f, "{}:{}", self.source_id, intervals)
+        write!(f, "{}:{}", self.source_id, parts.join(":"))
     }
 }
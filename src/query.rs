@@ -0,0 +1,189 @@
+use crate::binlog_client::BinlogClient;
+use crate::commands::query_command::QueryCommand;
+use crate::constants::MAX_BODY_LENGTH;
+use crate::errors::Error;
+use crate::extensions::{check_error_packet, read_len_enc_num};
+use crate::packet_channel::PacketChannel;
+use crate::responses::end_of_file_packet::EndOfFilePacket;
+use crate::responses::ok_packet::OkPacket;
+use crate::responses::response_type::ResponseType;
+use crate::result_set::{Column, ResultRow, ResultSet};
+use std::io::Cursor;
+use std::sync::Arc;
+
+/// Response of the server to COM_QUERY.
+pub enum QueryResponse {
+    /// Statement without result set.
+    Ok(OkPacket),
+
+    /// Columns of the result set. Rows are read with `read_row`.
+    ResultSet(Arc<Vec<Column>>),
+}
+
+impl BinlogClient {
+    /// Runs the query on a new connection and returns its result set.
+    /// Each call opens and authenticates a connection, so session state is not kept between calls.
+    /// Use `session` to run several queries on one connection.
+    pub fn query(&self, sql: &str) -> Result<ResultSet, Error> {
+        let (mut channel, _provider) = self.connect()?;
+        channel.query(sql)
+    }
+}
+
+impl PacketChannel {
+    /// Runs the query and reads all rows of its result set.
+    pub fn query(&mut self, sql: &str) -> Result<ResultSet, Error> {
+        let columns = match self.send_query(sql)? {
+            QueryResponse::Ok(packet) => {
+                return Ok(ResultSet {
+                    affected_rows: packet.affected_rows,
+                    last_insert_id: packet.last_insert_id,
+                    warnings: packet.warnings,
+                    ..Default::default()
+                })
+            }
+            QueryResponse::ResultSet(columns) => columns,
+        };
+
+        let mut rows = Vec::new();
+        loop {
+            match self.read_row(&columns)? {
+                Ok(row) => rows.push(row),
+                Err(packet) => {
+                    return Ok(ResultSet {
+                        columns,
+                        rows,
+                        affected_rows: packet.affected_rows,
+                        last_insert_id: packet.last_insert_id,
+                        warnings: packet.warnings,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Sends COM_QUERY and reads OK packet or column definitions of the result set.
+    pub fn send_query(&mut self, sql: &str) -> Result<QueryResponse, Error> {
+        let command = QueryCommand::new(sql.to_string());
+        self.write_packet(&command.serialize()?, 0)?;
+
+        let (packet, _) = self.read_packet()?;
+        check_error_packet(&packet, &format!("{} error.", sql))?;
+        if packet[0] == ResponseType::OK {
+            return Ok(QueryResponse::Ok(OkPacket::parse(&packet[1..])?));
+        }
+
+        let column_count = read_len_enc_num(&mut Cursor::new(&packet))?;
+        let mut columns = Vec::with_capacity(column_count);
+        for _ in 0..column_count {
+            let (packet, _) = self.read_packet()?;
+            columns.push(Column::parse(&packet)?);
+        }
+
+        // EOF packet after column definitions is omitted with CLIENT_DEPRECATE_EOF
+        if !self.deprecate_eof {
+            let (packet, _) = self.read_packet()?;
+            check_error_packet(&packet, &format!("{} error.", sql))?;
+        }
+        Ok(QueryResponse::ResultSet(Arc::new(columns)))
+    }
+
+    /// Reads next row of the result set.
+    /// Returns the packet that ends the result set after the last row.
+    pub fn read_row(
+        &mut self,
+        columns: &Arc<Vec<Column>>,
+    ) -> Result<Result<ResultRow, OkPacket>, Error> {
+        let (packet, _) = self.read_packet()?;
+        check_error_packet(&packet, "Reading result set error.")?;
+
+        // Rows can start with 0xFE length prefix of values longer than the packet limit
+        if packet[0] == ResponseType::END_OF_FILE && packet.len() < MAX_BODY_LENGTH {
+            if self.deprecate_eof {
+                return Ok(Err(OkPacket::parse(&packet[1..])?));
+            }
+            let eof = EndOfFilePacket::parse(&packet[1..])?;
+            return Ok(Err(OkPacket {
+                warnings: eof.warning_count,
+                status_flags: eof.server_status,
+                ..Default::default()
+            }));
+        }
+        Ok(Ok(ResultRow::parse(&packet, columns.clone())?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::errors::Error;
+    use crate::replica_options::ReplicaOptions;
+    use crate::result_set::ResultSet;
+    use crate::test_server::{Response, ServerConfig, TestServer};
+
+    fn config_with(deprecate_eof: bool) -> ServerConfig {
+        ServerConfig {
+            deprecate_eof,
+            ..Default::default()
+        }
+    }
+
+    fn query(config: ServerConfig, sql: &str) -> Result<ResultSet, Error> {
+        let server = TestServer::start_with(config, |query| match query {
+            "SHOW BINARY LOGS" => Response::rows(
+                &["Log_name", "File_size", "Encrypted"],
+                &[
+                    &[Some("mysql-bin.000001"), Some("177"), None],
+                    &[Some("mysql-bin.000002"), Some("1250"), Some("No")],
+                ],
+            ),
+            "SHOW TABLES" => Response::rows(&["Tables_in_shop"], &[]),
+            "DROP TABLE shop.orders" => Response::Error(1051, "Unknown table 'shop.orders'"),
+            _ => Response::Ok,
+        });
+        let options = ReplicaOptions {
+            hostname: "127.0.0.1".to_string(),
+            port: server.port,
+            ..Default::default()
+        };
+        let result = BinlogClient::new(options).query(sql);
+        server.join();
+        result
+    }
+
+    #[test]
+    fn query_result_sets() {
+        for deprecate_eof in [false, true] {
+            let result = query(config_with(deprecate_eof), "SHOW BINARY LOGS").unwrap();
+
+            assert_eq!(3, result.columns.len());
+            assert_eq!("File_size", result.columns[1].name);
+            assert_eq!(2, result.rows.len());
+
+            let row = &result.rows[1];
+            assert_eq!(Some(1250), row.get::<u64>("File_size").unwrap());
+            assert_eq!(Some("No".to_string()), row.get_at::<String>(2).unwrap());
+            assert_eq!(None, result.rows[0].get::<String>("Encrypted").unwrap());
+            match row.get::<u64>("Log_name") {
+                Err(Error::ConversionError(x)) => assert_eq!("Log_name", x.column),
+                _ => panic!("Conversion error expected"),
+            }
+
+            let result = query(config_with(deprecate_eof), "SHOW TABLES").unwrap();
+            assert_eq!(1, result.columns.len());
+            assert!(result.rows.is_empty());
+        }
+    }
+
+    #[test]
+    fn query_ok_and_error_packets() {
+        let result = query(ServerConfig::default(), "SET @a = 1").unwrap();
+        assert!(result.columns.is_empty());
+        assert_eq!(0, result.affected_rows);
+
+        match query(ServerConfig::default(), "DROP TABLE shop.orders") {
+            Err(Error::String(x)) => assert!(x.contains("Unknown table")),
+            _ => panic!("Error packet expected"),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::binlog_options::BinlogOptions;
+use crate::compression_mode::CompressionMode;
+use crate::server_address::ServerAddress;
 use crate::ssl_mode::SslMode;
+use std::path::PathBuf;
 use std::time::Duration;
 
 /// Settings used to connect to MySQL/MariaDB.
@@ -10,9 +13,26 @@ pub struct ReplicaOptions {
     /// Hostname to connect. Defaults to "localhost".
     pub hostname: String,
 
+    /// Servers tried in order when the server of hostname and port is unavailable.
+    /// Requires GTID positioning as binlog file positions differ between servers.
+    /// Defaults to empty.
+    pub failover_servers: Vec<ServerAddress>,
+
+    /// Path to the Unix socket file of the server. Hostname and port are ignored when specified.
+    /// Available on Unix platforms only.
+    pub socket_path: Option<PathBuf>,
+
     /// Defines whether SSL/TLS must be used. Defaults to SslMode.DISABLED.
     pub ssl_mode: SslMode,
 
+    /// Defines whether packets are compressed. Defaults to CompressionMode::Disabled.
+    /// Reduces traffic of row images at the cost of CPU time.
+    pub compression: CompressionMode,
+
+    /// Level of the compression algorithm.
+    /// Defaults to 6 for zlib and 3 for zstd. zstd accepts levels from 1 to 22.
+    pub compression_level: Option<u32>,
+
     /// A database user which is used to register as a database slave.
     /// The user needs to have <c>REPLICATION SLAVE</c>, <c>REPLICATION CLIENT</c> privileges.
     pub username: String,
@@ -20,6 +40,14 @@ pub struct ReplicaOptions {
     /// The password of the user which is used to connect.
     pub password: String,
 
+    /// PEM file with RSA public key of the server used by sha256_password and caching_sha2_password.
+    /// The key is requested from the server when the file is not specified.
+    pub server_public_key_path: Option<PathBuf>,
+
+    /// Allows mysql_clear_password plugin used by LDAP and PAM authentication. Defaults to false.
+    /// The plugin sends the password unencrypted, so enable it only for trusted networks.
+    pub enable_cleartext_plugin: bool,
+
     /// Default database name specified in Handshake connection.
     /// Has nothing to do with filtering events by database name.
     pub database: Option<String>,
@@ -36,6 +64,21 @@ pub struct ReplicaOptions {
     /// Defaults to 30 seconds.
     pub heartbeat_interval: Duration,
 
+    /// Time limit of establishing TCP connection to each address the hostname resolves to.
+    /// Defaults to 10 seconds.
+    pub connect_timeout: Duration,
+
+    /// Time limit of waiting for a packet from the server.
+    /// Must exceed heartbeat interval in blocking mode.
+    /// Defaults to heartbeat interval plus 10 seconds of network latency.
+    pub read_timeout: Option<Duration>,
+
+    /// Time limit of sending a packet to the server. Defaults to None (no limit).
+    pub write_timeout: Option<Duration>,
+
+    /// Idle time before TCP keepalive probes are sent. Defaults to None (OS settings).
+    pub keepalive: Option<Duration>,
+
     /// Defines the binlog coordinates that replication should start from.
     /// Defaults to BinlogOptions.FromEnd()
     pub binlog: BinlogOptions,
@@ -46,14 +89,34 @@ impl Default for ReplicaOptions {
         ReplicaOptions {
             port: 3306,
             hostname: String::from("localhost"),
+            failover_servers: Vec::new(),
+            socket_path: None,
             ssl_mode: SslMode::Disabled,
+            compression: CompressionMode::Disabled,
+            compression_level: None,
             username: String::new(),
             password: String::new(),
+            server_public_key_path: None,
+            enable_cleartext_plugin: false,
             database: None,
             server_id: 65535,
             blocking: true,
             heartbeat_interval: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            read_timeout: None,
+            write_timeout: None,
+            keepalive: None,
             binlog: BinlogOptions::from_end(),
         }
     }
 }
+
+impl ReplicaOptions {
+    /// Gets addresses of the server and its failover servers in the order they are tried.
+    pub fn servers(&self) -> Vec<ServerAddress> {
+        let server = ServerAddress::new(self.hostname.clone(), self.port);
+        std::iter::once(server)
+            .chain(self.failover_servers.iter().cloned())
+            .collect()
+    }
+}
@@ -1,12 +1,14 @@
 use crate::{errors::Error, extensions::read_null_term_string};
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
 /// Authentication Switch Request.
 /// <a href="https://mariadb.com/kb/en/library/connection/#authentication-switch-request">See more</a>
 #[derive(Debug)]
 pub struct AuthPluginSwitchPacket {
     pub auth_plugin_name: String,
-    pub auth_plugin_data: String,
+
+    /// Scramble of the plugin. Binary for ed25519 and parsec plugins.
+    pub auth_plugin_data: Vec<u8>,
 }
 
 impl AuthPluginSwitchPacket {
@@ -14,7 +16,8 @@ impl AuthPluginSwitchPacket {
         let mut cursor = Cursor::new(packet);
 
         let auth_plugin_name = read_null_term_string(&mut cursor)?;
-        let auth_plugin_data = read_null_term_string(&mut cursor)?;
+        let mut auth_plugin_data = Vec::new();
+        cursor.read_to_end(&mut auth_plugin_data)?;
 
         Ok(Self {
             auth_plugin_name,
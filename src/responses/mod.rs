@@ -2,5 +2,5 @@ pub mod auth_switch_packet;
 pub mod end_of_file_packet;
 pub mod error_packet;
 pub mod handshake_packet;
+pub mod ok_packet;
 pub mod response_type;
-pub mod result_set_row_packet;
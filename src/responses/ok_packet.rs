@@ -0,0 +1,39 @@
+use crate::errors::Error;
+use crate::extensions::read_len_enc_num;
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+
+/// OK_Packet signals successful completion of a command.
+/// Also ends result sets when CLIENT_DEPRECATE_EOF is negotiated.
+/// <a href="https://mariadb.com/kb/en/library/ok_packet/">See more</a>
+#[derive(Debug, Default)]
+pub struct OkPacket {
+    pub affected_rows: u64,
+    pub last_insert_id: u64,
+    pub status_flags: u16,
+    pub warnings: u16,
+    pub info: String,
+}
+
+impl OkPacket {
+    pub fn parse(packet: &[u8]) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(packet);
+
+        let affected_rows = read_len_enc_num(&mut cursor)? as u64;
+        let last_insert_id = read_len_enc_num(&mut cursor)? as u64;
+        let status_flags = cursor.read_u16::<LittleEndian>()?;
+        let warnings = cursor.read_u16::<LittleEndian>()?;
+
+        // Info is followed by session state changes when SESSION_TRACK is negotiated
+        let mut info = Vec::new();
+        cursor.read_to_end(&mut info)?;
+
+        Ok(Self {
+            affected_rows,
+            last_insert_id,
+            status_flags,
+            warnings,
+            info: String::from_utf8_lossy(&info).to_string(),
+        })
+    }
+}
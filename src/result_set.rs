@@ -0,0 +1,370 @@
+use crate::constants::column_type::ColumnType;
+use crate::errors::{ConversionError, Error};
+use crate::events::row_events::mysql_value::{Date, DateTime, Decimal, MySqlValue, Time};
+use crate::extensions::{read_bitmap_big_endian, read_len_enc_num, read_len_enc_str};
+use byteorder::{LittleEndian, ReadBytesExt};
+use std::io::{Cursor, Read};
+use std::str::FromStr;
+use std::sync::Arc;
+
+const NULL_CELL: u8 = 0xFB;
+const NOT_NULL_FLAG: u16 = 0x01;
+const PRIMARY_KEY_FLAG: u16 = 0x02;
+const UNSIGNED_FLAG: u16 = 0x20;
+const BINARY_CHARSET: u16 = 63;
+
+/// Column of a query result set.
+/// <a href="https://mariadb.com/kb/en/library/resultset/#column-definition-packet">See more</a>
+#[derive(Clone, Debug)]
+pub struct Column {
+    /// Gets database name of the column table.
+    pub schema: String,
+
+    /// Gets table name or its alias in the query.
+    pub table: String,
+
+    /// Gets physical table name.
+    pub org_table: String,
+
+    /// Gets column name or its alias in the query.
+    pub name: String,
+
+    /// Gets physical column name.
+    pub org_name: String,
+
+    /// Gets collation id of the column. 63 for binary columns.
+    pub character_set: u16,
+
+    /// Gets maximum length of the column values.
+    pub column_length: u32,
+
+    /// Gets MySQL column type code like in TableMapEvent.
+    pub column_type: u8,
+
+    /// Gets column flags like NOT NULL, PRIMARY KEY and UNSIGNED.
+    pub flags: u16,
+
+    /// Gets number of decimals of numeric and temporal columns.
+    pub decimals: u8,
+}
+
+impl Column {
+    /// Parses ColumnDefinition41 packet.
+    pub(crate) fn parse(packet: &[u8]) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(packet);
+
+        let _catalog = read_len_enc_str(&mut cursor)?;
+        let schema = read_len_enc_str(&mut cursor)?;
+        let table = read_len_enc_str(&mut cursor)?;
+        let org_table = read_len_enc_str(&mut cursor)?;
+        let name = read_len_enc_str(&mut cursor)?;
+        let org_name = read_len_enc_str(&mut cursor)?;
+
+        // Length of the fixed fields is always 0x0C
+        let _fixed_length = read_len_enc_num(&mut cursor)?;
+        let character_set = cursor.read_u16::<LittleEndian>()?;
+        let column_length = cursor.read_u32::<LittleEndian>()?;
+        let column_type = cursor.read_u8()?;
+        let flags = cursor.read_u16::<LittleEndian>()?;
+        let decimals = cursor.read_u8()?;
+
+        Ok(Self {
+            schema,
+            table,
+            org_table,
+            name,
+            org_name,
+            character_set,
+            column_length,
+            column_type,
+            flags,
+            decimals,
+        })
+    }
+
+    /// Returns true if the column cannot contain NULL.
+    pub fn is_not_null(&self) -> bool {
+        self.flags & NOT_NULL_FLAG != 0
+    }
+
+    /// Returns true if the column is part of the primary key.
+    pub fn is_primary_key(&self) -> bool {
+        self.flags & PRIMARY_KEY_FLAG != 0
+    }
+
+    /// Returns true if the numeric column is unsigned.
+    pub fn is_unsigned(&self) -> bool {
+        self.flags & UNSIGNED_FLAG != 0
+    }
+
+    /// Returns true if the column values are bytes rather than text.
+    pub fn is_binary(&self) -> bool {
+        self.character_set == BINARY_CHARSET
+    }
+
+    /// Converts text protocol value to the type used in row events.
+    /// TIMESTAMP values must be returned in UTC session time zone.
+    /// ENUM and SET values are returned as strings because their ordinals are not known.
+    pub fn parse_value(&self, value: &[u8]) -> Result<MySqlValue, Error> {
+        let column_type = ColumnType::from_code(self.column_type)?;
+        match column_type {
+            ColumnType::TinyBlob
+            | ColumnType::MediumBlob
+            | ColumnType::LongBlob
+            | ColumnType::Blob
+            | ColumnType::Json
+            | ColumnType::Geometry => return Ok(MySqlValue::Blob(value.to_vec())),
+            ColumnType::VarChar | ColumnType::VarString | ColumnType::String
+                if self.is_binary() =>
+            {
+                return Ok(MySqlValue::Blob(value.to_vec()))
+            }
+            ColumnType::Bit => {
+                let mut cursor = Cursor::new(value);
+                let mut bitmap = read_bitmap_big_endian(&mut cursor, self.column_length as usize)?;
+                bitmap.reverse();
+                return Ok(MySqlValue::Bit(bitmap));
+            }
+            _ => {}
+        }
+
+        let text = std::str::from_utf8(value)
+            .map_err(|_| conversion_error(&self.name, "Value is not valid UTF-8".to_string()))?;
+        let value = match column_type {
+            ColumnType::Tiny => MySqlValue::TinyInt(self.parse_integer(text)? as u8),
+            ColumnType::Short => MySqlValue::SmallInt(self.parse_integer(text)? as u16),
+            ColumnType::Int24 => MySqlValue::MediumInt(self.parse_integer(text)? as u32 & 0xFFFFFF),
+            ColumnType::Long => MySqlValue::Int(self.parse_integer(text)? as u32),
+            ColumnType::LongLong => MySqlValue::BigInt(self.parse_integer(text)?),
+            ColumnType::Float => MySqlValue::Float(self.parse_text(text)?),
+            ColumnType::Double => MySqlValue::Double(self.parse_text(text)?),
+            ColumnType::Decimal | ColumnType::NewDecimal => {
+                MySqlValue::Decimal(self.parse_decimal(text)?)
+            }
+            ColumnType::Year => MySqlValue::Year(self.parse_text(text)?),
+            ColumnType::Date | ColumnType::NewDate => {
+                MySqlValue::Date(parse_date(text).ok_or_else(|| self.format_error(text, "DATE"))?)
+            }
+            ColumnType::Time | ColumnType::Time2 => {
+                if text.starts_with('-') {
+                    return Err(Error::String(
+                        "Parsing negative TIME values is not supported in this version".to_string(),
+                    ));
+                }
+                let (hour, minute, second, millis) =
+                    parse_clock(text).ok_or_else(|| self.format_error(text, "TIME"))?;
+                MySqlValue::Time(Time {
+                    hour: hour as i16,
+                    minute,
+                    second,
+                    millis,
+                })
+            }
+            ColumnType::DateTime | ColumnType::DateTime2 => MySqlValue::DateTime(
+                parse_date_time(text).ok_or_else(|| self.format_error(text, "DATETIME"))?,
+            ),
+            ColumnType::TimeStamp | ColumnType::TimeStamp2 => {
+                let value =
+                    parse_date_time(text).ok_or_else(|| self.format_error(text, "TIMESTAMP"))?;
+                match value.date().is_zero() {
+                    true => MySqlValue::Timestamp(0),
+                    false => MySqlValue::Timestamp((value.to_epoch_micros() / 1000) as u64),
+                }
+            }
+            ColumnType::VarChar
+            | ColumnType::VarString
+            | ColumnType::String
+            | ColumnType::Enum
+            | ColumnType::Set => MySqlValue::String(text.to_string()),
+            ColumnType::Null => {
+                let message = "NULL column cannot have values".to_string();
+                return Err(conversion_error(&self.name, message));
+            }
+            _ => unreachable!("Binary values are converted above"),
+        };
+        Ok(value)
+    }
+
+    /// Returns signed values as two's complement like row events.
+    fn parse_integer(&self, text: &str) -> Result<u64, Error> {
+        match self.is_unsigned() {
+            true => self.parse_text(text),
+            false => Ok(self.parse_text::<i64>(text)? as u64),
+        }
+    }
+
+    fn parse_decimal(&self, text: &str) -> Result<Decimal, Error> {
+        let mut decimal = Decimal::parse(text)?;
+        // Column length includes the decimal point and the sign
+        let precision =
+            self.column_length as i64 - (self.decimals > 0) as i64 - (!self.is_unsigned()) as i64;
+        if precision >= decimal.precision as i64 && precision <= 65 {
+            decimal.precision = precision as u8;
+        }
+        Ok(decimal)
+    }
+
+    fn parse_text<T: FromStr>(&self, text: &str) -> Result<T, Error> {
+        text.parse()
+            .map_err(|_| self.format_error(text, std::any::type_name::<T>()))
+    }
+
+    fn format_error(&self, text: &str, type_name: &str) -> Error {
+        let message = format!("Cannot convert {} to {}", text, type_name);
+        conversion_error(&self.name, message)
+    }
+}
+
+/// Row of a query result set.
+#[derive(Clone, Debug)]
+pub struct ResultRow {
+    /// Gets columns of the result set.
+    pub columns: Arc<Vec<Column>>,
+
+    /// Gets values in text protocol representation. None for NULL.
+    pub cells: Vec<Option<Vec<u8>>>,
+}
+
+impl ResultRow {
+    /// Parses text protocol row packet.
+    pub(crate) fn parse(packet: &[u8], columns: Arc<Vec<Column>>) -> Result<Self, Error> {
+        let mut cursor = Cursor::new(packet);
+        let mut cells = Vec::with_capacity(columns.len());
+        while (cursor.position() as usize) < packet.len() {
+            if packet[cursor.position() as usize] == NULL_CELL {
+                cursor.set_position(cursor.position() + 1);
+                cells.push(None);
+                continue;
+            }
+            let length = read_len_enc_num(&mut cursor)?;
+            let mut cell = vec![0; length];
+            cursor.read_exact(&mut cell)?;
+            cells.push(Some(cell));
+        }
+        Ok(Self { columns, cells })
+    }
+
+    /// Gets index of the column with the name.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|x| x.name == name)
+    }
+
+    /// Gets raw value of the column. Returns None for NULL.
+    pub fn get_bytes(&self, index: usize) -> Option<&[u8]> {
+        self.cells.get(index).and_then(|x| x.as_deref())
+    }
+
+    /// Gets values of the row converted to the types used in row events.
+    pub fn values(&self) -> Result<Vec<Option<MySqlValue>>, Error> {
+        self.cells
+            .iter()
+            .zip(self.columns.iter())
+            .map(|(cell, column)| cell.as_ref().map(|x| column.parse_value(x)).transpose())
+            .collect()
+    }
+
+    /// Gets value of the column found by name and converts it to the Rust type.
+    /// Returns None for NULL.
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, Error> {
+        match self.column_index(name) {
+            Some(index) => self.convert(index, name),
+            None => Err(conversion_error(name, "Column not found".to_string())),
+        }
+    }
+
+    /// Gets value of the column by ordinal and converts it to the Rust type.
+    /// Returns None for NULL.
+    pub fn get_at<T: FromStr>(&self, index: usize) -> Result<Option<T>, Error> {
+        if index >= self.cells.len() {
+            let message = "Column not found".to_string();
+            return Err(conversion_error(&index.to_string(), message));
+        }
+        self.convert(index, &index.to_string())
+    }
+
+    fn convert<T: FromStr>(&self, index: usize, column: &str) -> Result<Option<T>, Error> {
+        let value = match self.get_bytes(index) {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let value = std::str::from_utf8(value)
+            .map_err(|_| conversion_error(column, "Value is not valid UTF-8".to_string()))?;
+        match value.parse() {
+            Ok(x) => Ok(Some(x)),
+            Err(_) => {
+                let message = format!("Cannot convert {} to {}", value, std::any::type_name::<T>());
+                Err(conversion_error(column, message))
+            }
+        }
+    }
+}
+
+/// Result of a text protocol query.
+/// Statements that don't return rows have empty columns and rows.
+#[derive(Clone, Debug, Default)]
+pub struct ResultSet {
+    /// Gets columns of the result set.
+    pub columns: Arc<Vec<Column>>,
+
+    /// Gets rows of the result set.
+    pub rows: Vec<ResultRow>,
+
+    /// Gets number of rows changed by the statement.
+    pub affected_rows: u64,
+
+    /// Gets AUTO_INCREMENT value generated by the statement.
+    pub last_insert_id: u64,
+
+    /// Gets number of warnings of the statement.
+    pub warnings: u16,
+}
+
+/// Parses date like 2024-01-02.
+fn parse_date(text: &str) -> Option<Date> {
+    let mut parts = text.splitn(3, '-');
+    Some(Date {
+        year: parts.next()?.parse().ok()?,
+        month: parts.next()?.parse().ok()?,
+        day: parts.next()?.parse().ok()?,
+    })
+}
+
+/// Parses time like 838:59:59.123456. Fraction is truncated to milliseconds.
+fn parse_clock(text: &str) -> Option<(u16, u8, u8, u32)> {
+    let (time, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if !fraction.chars().all(|x| x.is_ascii_digit()) {
+        return None;
+    }
+    let millis = format!("{:0<3}", &fraction[..fraction.len().min(3)]);
+
+    let mut parts = time.splitn(3, ':');
+    Some((
+        parts.next()?.parse().ok()?,
+        parts.next()?.parse().ok()?,
+        parts.next()?.parse().ok()?,
+        millis.parse().ok()?,
+    ))
+}
+
+/// Parses date and time like 2024-01-02 03:04:05.123456.
+fn parse_date_time(text: &str) -> Option<DateTime> {
+    let (date, time) = text.split_once(' ')?;
+    let date = parse_date(date)?;
+    let (hour, minute, second, millis) = parse_clock(time)?;
+    Some(DateTime {
+        year: date.year,
+        month: date.month,
+        day: date.day,
+        hour: u8::try_from(hour).ok()?,
+        minute,
+        second,
+        millis,
+    })
+}
+
+fn conversion_error(column: &str, message: String) -> Error {
+    Error::ConversionError(ConversionError {
+        column: column.to_string(),
+        message,
+    })
+}
@@ -0,0 +1,151 @@
+//! Serde representations of public types.
+//! - Binary data (blobs, payloads, XA identifiers) is written as standard base64 string.
+//! - Decimals are written as strings to keep exact value, e.g. "-123.45".
+//! - GTIDs, GTID sets, GTID lists and UUIDs are written in canonical text form of the server.
+//! - Events of custom parsers are written as null and cannot be deserialized.
+//! - Other types use field names and enum variant names of the structs.
+
+use crate::errors::Error;
+use crate::events::row_events::mysql_value::Decimal;
+use crate::providers::mariadb::gtid::gtid::Gtid as MariaGtid;
+use crate::providers::mariadb::gtid::gtid_list::GtidList;
+use crate::providers::mysql::gtid::gtid::Gtid as MySqlGtid;
+use crate::providers::mysql::gtid::gtid_set::GtidSet;
+use crate::providers::mysql::gtid::uuid::Uuid;
+use serde::de::Error as _;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// Serializes byte arrays as base64 strings.
+pub(crate) mod base64 {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer, T: AsRef<[u8]>>(
+        value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&STANDARD.encode(value))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, T: TryFrom<Vec<u8>>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        let bytes = STANDARD.decode(value).map_err(D::Error::custom)?;
+        T::try_from(bytes).map_err(|_| D::Error::custom("Invalid length of byte array"))
+    }
+}
+
+/// Serializes lists of byte arrays as lists of base64 strings.
+pub(crate) mod base64_vec {
+    use base64::engine::general_purpose::STANDARD;
+    use base64::Engine;
+    use serde::de::Error as _;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(value.iter().map(|x| STANDARD.encode(x)))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<Vec<u8>>, D::Error> {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        values
+            .iter()
+            .map(|x| STANDARD.decode(x).map_err(D::Error::custom))
+            .collect()
+    }
+}
+
+/// Serializes values of unknown types as unit.
+pub(crate) mod opaque {
+    use serde::Serializer;
+
+    pub fn serialize<S: Serializer, T: ?Sized>(
+        _value: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_unit()
+    }
+}
+
+/// Implements serialization using Display and parse function of the type.
+macro_rules! impl_text_serde {
+    ($type:ty, $parse:expr) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                let parse: fn(&str) -> Result<$type, Error> = $parse;
+                parse(&value).map_err(|e| D::Error::custom(format!("{:?}", e)))
+            }
+        }
+    };
+}
+
+impl_text_serde!(Decimal, Decimal::parse);
+impl_text_serde!(Uuid, |x| Uuid::parse(x.to_string()));
+impl_text_serde!(MySqlGtid, MySqlGtid::parse);
+impl_text_serde!(GtidSet, GtidSet::parse);
+impl_text_serde!(MariaGtid, MariaGtid::parse);
+impl_text_serde!(GtidList, GtidList::parse);
+
+#[cfg(test)]
+mod tests {
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::row_events::mysql_value::{Decimal, MySqlValue};
+    use crate::events::xid::Xid;
+    use crate::providers::mariadb::gtid::gtid_list::GtidList;
+    use crate::providers::mysql::gtid::gtid_set::GtidSet;
+
+    #[test]
+    fn values_use_text_representations() {
+        let value = MySqlValue::Decimal(Decimal::parse("-00123.4500").unwrap());
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"Decimal":"-123.4500"}"#, json);
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+
+        let value = MySqlValue::Blob(vec![0, 1, 2, 255]);
+        let json = serde_json::to_string(&value).unwrap();
+        assert_eq!(r#"{"Blob":"AAEC/w=="}"#, json);
+        assert_eq!(value, serde_json::from_str(&json).unwrap());
+
+        let xid = Xid {
+            format_id: 1,
+            gtrid: b"gtrid".to_vec(),
+            bqual: vec![],
+        };
+        let json = serde_json::to_string(&xid).unwrap();
+        assert_eq!(r#"{"format_id":1,"gtrid":"Z3RyaWQ=","bqual":""}"#, json);
+        assert_eq!(xid, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn gtids_use_text_representations() {
+        let text = "24bc7850-2c16-11e6-a073-0242ac110002:1-5";
+        let json = serde_json::to_string(&GtidSet::parse(text).unwrap()).unwrap();
+        assert_eq!(format!("\"{}\"", text), json);
+        let gtid_set: GtidSet = serde_json::from_str(&json).unwrap();
+        assert_eq!(text, gtid_set.to_string());
+
+        let json = serde_json::to_string(&GtidList::parse("0-1-270,1-2-120").unwrap()).unwrap();
+        assert_eq!(r#""0-1-270,1-2-120""#, json);
+        assert!(serde_json::from_str::<GtidList>(r#""0-1""#).is_err());
+    }
+
+    #[test]
+    fn custom_events_are_opaque() {
+        let event = BinlogEvent::CustomEvent(Box::new(3usize));
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(r#"{"CustomEvent":null}"#, json);
+        assert!(serde_json::from_str::<BinlogEvent>(&json).is_err());
+    }
+}
@@ -0,0 +1,23 @@
+use std::fmt;
+
+/// Hostname and port of a MySQL/MariaDB server.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ServerAddress {
+    /// Gets hostname or IP address of the server.
+    pub hostname: String,
+
+    /// Gets port number of the server.
+    pub port: u16,
+}
+
+impl ServerAddress {
+    pub fn new(hostname: String, port: u16) -> Self {
+        Self { hostname, port }
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.hostname, self.port)
+    }
+}
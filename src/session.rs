@@ -0,0 +1,61 @@
+use crate::binlog_client::BinlogClient;
+use crate::errors::Error;
+use crate::packet_channel::PacketChannel;
+use crate::result_set::ResultSet;
+
+/// Connection for running queries.
+/// Session state like variables and transactions is kept between queries.
+pub struct Session {
+    channel: PacketChannel,
+}
+
+impl Session {
+    /// Runs the query and reads all rows of its result set.
+    pub fn query(&mut self, sql: &str) -> Result<ResultSet, Error> {
+        self.channel.query(sql)
+    }
+}
+
+impl BinlogClient {
+    /// Opens a session on a new connection with the settings of the client.
+    /// The replication connection cannot run queries once binlog dump is started.
+    pub fn session(&self) -> Result<Session, Error> {
+        let (channel, _provider) = self.connect()?;
+        Ok(Session { channel })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::binlog_client::BinlogClient;
+    use crate::replica_options::ReplicaOptions;
+    use crate::test_server::{Response, TestServer};
+
+    #[test]
+    fn run_queries_in_one_session() {
+        let server = TestServer::start("8.0.36", |query| match query {
+            "SELECT @position" => Response::rows(&["@position"], &[&[Some("1250")]]),
+            _ => Response::Ok,
+        });
+        let options = ReplicaOptions {
+            hostname: "127.0.0.1".to_string(),
+            port: server.port,
+            ..Default::default()
+        };
+
+        // The test server accepts one connection, so both queries share it
+        let mut session = BinlogClient::new(options).session().unwrap();
+        session.query("SET @position = 1250").unwrap();
+        let result_set = session.query("SELECT @position").unwrap();
+        assert_eq!(
+            Some(1250),
+            result_set.rows[0].get::<u32>("@position").unwrap()
+        );
+        drop(session);
+
+        assert_eq!(
+            vec!["SET @position = 1250", "SELECT @position"],
+            server.join()
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod snapshot_options;
+pub mod snapshot_reader;
@@ -0,0 +1,35 @@
+/// Defines how the snapshot is synchronized with the binlog position.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum SnapshotLocking {
+    /// FLUSH TABLES WITH READ LOCK is held while the transaction starts and binlog position is captured.
+    /// Requires RELOAD privilege. Works with MySQL and MariaDB.
+    GlobalReadLock,
+
+    /// START TRANSACTION WITH CONSISTENT SNAPSHOT without locks.
+    /// Requires MariaDB that reports binlog position of the snapshot.
+    ConsistentSnapshot,
+}
+
+/// Settings of initial snapshot.
+#[derive(Clone, Debug)]
+pub struct SnapshotOptions {
+    /// Tables to read given as database.table.
+    pub tables: Vec<String>,
+
+    /// Defaults to SnapshotLocking::GlobalReadLock.
+    pub locking: SnapshotLocking,
+
+    /// Defines whether streaming continues from GTID position when GTIDs are enabled on the server.
+    /// Otherwise binlog file position is used. Defaults to true.
+    pub use_gtid: bool,
+}
+
+impl SnapshotOptions {
+    pub fn new(tables: Vec<String>) -> Self {
+        Self {
+            tables,
+            locking: SnapshotLocking::GlobalReadLock,
+            use_gtid: true,
+        }
+    }
+}
@@ -0,0 +1,485 @@
+use crate::binlog_client::BinlogClient;
+use crate::binlog_options::BinlogOptions;
+use crate::constants::database_provider::DatabaseProvider;
+use crate::errors::Error;
+use crate::events::row_events::row_data::RowData;
+use crate::packet_channel::PacketChannel;
+use crate::providers::mariadb::gtid::gtid_list::GtidList;
+use crate::providers::mysql::gtid::gtid_set::GtidSet;
+use crate::query::QueryResponse;
+use crate::replica_options::ReplicaOptions;
+use crate::result_set::Column;
+use crate::snapshot::snapshot_options::{SnapshotLocking, SnapshotOptions};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Row of a table read in the snapshot. Represents read-type change.
+#[derive(Debug)]
+pub struct SnapshotRow {
+    pub database: String,
+    pub table: String,
+
+    /// Columns of the table.
+    pub columns: Arc<Vec<Column>>,
+
+    /// Values converted to the types used in row events.
+    /// Encode it as the row after change with Operation::Read.
+    pub data: RowData,
+}
+
+/// Reads existing rows of tables in a consistent snapshot
+/// and captures binlog position to stream following changes from.
+pub struct SnapshotReader {
+    pub options: SnapshotOptions,
+    client: BinlogClient,
+}
+
+impl SnapshotReader {
+    /// Connection settings are taken from replica options.
+    /// Binlog position of the replica options is replaced by the snapshot position.
+    pub fn new(replica_options: ReplicaOptions, options: SnapshotOptions) -> Self {
+        Self {
+            options,
+            client: BinlogClient::new(replica_options),
+        }
+    }
+
+    /// Starts the snapshot transaction and captures binlog position.
+    /// Returns rows of the tables that are read in the transaction.
+    pub fn read(&mut self) -> Result<SnapshotRows, Error> {
+        let tables = self
+            .options
+            .tables
+            .iter()
+            .map(|x| match x.split_once('.') {
+                Some((database, table)) => Ok((database.to_string(), table.to_string())),
+                None => Err(Error::String(format!(
+                    "Table {} must be given as database.table",
+                    x
+                ))),
+            })
+            .collect::<Result<VecDeque<(String, String)>, Error>>()?;
+
+        let (mut channel, provider) = self.client.connect()?;
+        channel.query("SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ")?;
+        // TIMESTAMP values are converted to Unix time like in row events
+        channel.query("SET time_zone = '+00:00'")?;
+
+        let binlog = match self.options.locking {
+            SnapshotLocking::GlobalReadLock => {
+                channel.query("FLUSH TABLES WITH READ LOCK")?;
+                channel.query("START TRANSACTION WITH CONSISTENT SNAPSHOT")?;
+                let binlog = self.capture_position(&mut channel, &provider, false);
+                // Release the lock before reporting errors
+                channel.query("UNLOCK TABLES")?;
+                binlog?
+            }
+            SnapshotLocking::ConsistentSnapshot => {
+                if let DatabaseProvider::MySQL = provider {
+                    return Err(Error::String(
+                        "Snapshot without lock requires MariaDB".to_string(),
+                    ));
+                }
+                channel.query("START TRANSACTION WITH CONSISTENT SNAPSHOT")?;
+                self.capture_position(&mut channel, &provider, true)?
+            }
+        };
+        self.client.options.binlog = binlog;
+
+        Ok(SnapshotRows {
+            channel,
+            tables,
+            current: None,
+            finished: false,
+        })
+    }
+
+    /// Returns client that streams changes after the snapshot.
+    /// Call it after the snapshot rows are read.
+    pub fn into_client(self) -> BinlogClient {
+        self.client
+    }
+
+    fn capture_position(
+        &self,
+        channel: &mut PacketChannel,
+        provider: &DatabaseProvider,
+        snapshot_status: bool,
+    ) -> Result<BinlogOptions, Error> {
+        let (filename, position, gtid_set) = match snapshot_status {
+            // MariaDB reports binlog position of the consistent snapshot
+            true => {
+                let rows = channel.query("SHOW STATUS LIKE 'binlog_snapshot_%'")?.rows;
+                let status = |name: &str| {
+                    rows.iter()
+                        .find(|x| x.get_bytes(0) == Some(name.as_bytes()))
+                        .map(|x| x.get_at::<String>(1))
+                        .transpose()?
+                        .flatten()
+                        .ok_or(Error::String(format!("{} status is not available", name)))
+                };
+                (
+                    status("binlog_snapshot_file")?,
+                    status("binlog_snapshot_position")?,
+                    None,
+                )
+            }
+            false => {
+                let result_set = match binary_log_status_supported(&channel.server_version) {
+                    true => channel.query("SHOW BINARY LOG STATUS")?,
+                    false => channel.query("SHOW MASTER STATUS")?,
+                };
+                let row = result_set.rows.into_iter().next().ok_or(Error::String(
+                    "Binary logging is disabled on the server".to_string(),
+                ))?;
+                (
+                    row.get_at::<String>(0)?.unwrap_or_default(),
+                    row.get_at::<String>(1)?.unwrap_or_default(),
+                    row.get::<String>("Executed_Gtid_Set").unwrap_or_default(),
+                )
+            }
+        };
+        let position: u32 = position.parse()?;
+
+        if self.options.use_gtid {
+            match provider {
+                DatabaseProvider::MySQL => {
+                    if let Some(gtid_set) = gtid_set.filter(|x| !x.trim().is_empty()) {
+                        return Ok(BinlogOptions::from_mysql_gtid(GtidSet::parse(&gtid_set)?));
+                    }
+                }
+                DatabaseProvider::MariaDB => {
+                    let sql = format!("SELECT BINLOG_GTID_POS('{}', {})", filename, position);
+                    let rows = channel.query(&sql)?.rows;
+                    let gtid_list: Option<String> = match rows.first() {
+                        Some(row) => row.get_at(0)?,
+                        None => None,
+                    };
+                    if let Some(gtid_list) = gtid_list.filter(|x| !x.is_empty()) {
+                        return Ok(BinlogOptions::from_mariadb_gtid(GtidList::parse(
+                            &gtid_list,
+                        )?));
+                    }
+                }
+            }
+        }
+        Ok(BinlogOptions::from_position(filename, position))
+    }
+}
+
+/// MySQL 8.2 added SHOW BINARY LOG STATUS and 8.4 removed SHOW MASTER STATUS.
+fn binary_log_status_supported(server_version: &str) -> bool {
+    if server_version.contains("MariaDB") {
+        return false;
+    }
+    let mut parts = server_version
+        .split(|x: char| !x.is_ascii_digit())
+        .map(|x| x.parse::<u32>().unwrap_or(0));
+    let major = parts.next().unwrap_or(0);
+    let minor = parts.next().unwrap_or(0);
+    (major, minor) >= (8, 2)
+}
+
+/// Streams rows of snapshot tables one by one.
+/// The snapshot transaction is committed after the last row.
+pub struct SnapshotRows {
+    channel: PacketChannel,
+    tables: VecDeque<(String, String)>,
+    current: Option<(String, String, Arc<Vec<Column>>)>,
+    finished: bool,
+}
+
+impl SnapshotRows {
+    fn start_table(&mut self, database: String, table: String) -> Result<(), Error> {
+        let sql = format!(
+            "SELECT * FROM `{}`.`{}`",
+            database.replace('`', "``"),
+            table.replace('`', "``")
+        );
+        match self.channel.send_query(&sql)? {
+            QueryResponse::ResultSet(columns) => {
+                self.current = Some((database, table, columns));
+                Ok(())
+            }
+            QueryResponse::Ok(_) => Err(Error::String(format!("{} returned no rows", sql))),
+        }
+    }
+
+    fn read_row(&mut self) -> Result<Option<SnapshotRow>, Error> {
+        loop {
+            let (database, table, columns) = match &self.current {
+                Some(x) => x.clone(),
+                None => match self.tables.pop_front() {
+                    Some((database, table)) => {
+                        self.start_table(database, table)?;
+                        continue;
+                    }
+                    None => {
+                        self.channel.query("COMMIT")?;
+                        return Ok(None);
+                    }
+                },
+            };
+
+            match self.channel.read_row(&columns)? {
+                Ok(row) => {
+                    return Ok(Some(SnapshotRow {
+                        data: RowData::new(row.values()?),
+                        database,
+                        table,
+                        columns,
+                    }))
+                }
+                Err(_) => self.current = None,
+            }
+        }
+    }
+}
+
+impl Iterator for SnapshotRows {
+    type Item = Result<SnapshotRow, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        match self.read_row() {
+            Ok(Some(row)) => Some(Ok(row)),
+            Ok(None) => {
+                self.finished = true;
+                None
+            }
+            Err(e) => {
+                self.finished = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SnapshotReader;
+    use crate::events::row_events::mysql_value::{DateTime, Decimal, MySqlValue};
+    use crate::replica_options::ReplicaOptions;
+    use crate::result_set::Column;
+    use crate::snapshot::snapshot_options::{SnapshotLocking, SnapshotOptions};
+    use crate::starting_strategy::StartingStrategy;
+    use crate::test_server::{text_column, Response, TestServer};
+
+    fn create_options(port: u16) -> ReplicaOptions {
+        ReplicaOptions {
+            hostname: "127.0.0.1".to_string(),
+            port,
+            username: "root".to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn column(name: &str, column_type: u8, flags: u16, column_length: u32) -> Column {
+        Column {
+            character_set: 63,
+            column_length,
+            column_type,
+            flags,
+            decimals: 0,
+            ..text_column(name)
+        }
+    }
+
+    #[test]
+    fn read_snapshot_and_capture_mysql_gtid() {
+        let server = TestServer::start("8.0.36", |query| match query {
+            "SHOW MASTER STATUS" => Response::rows(
+                &[
+                    "File",
+                    "Position",
+                    "Binlog_Do_DB",
+                    "Binlog_Ignore_DB",
+                    "Executed_Gtid_Set",
+                ],
+                &[&[
+                    Some("mysql-bin.000003"),
+                    Some("1250"),
+                    Some(""),
+                    Some(""),
+                    Some("24bc7850-2c16-11e6-a073-0242ac110002:1-15"),
+                ]],
+            ),
+            "SELECT * FROM `shop`.`orders`" => Response::typed_rows(
+                &[column("id", 3, 0, 0), text_column("note")],
+                &[&[Some("1"), Some("first")], &[Some("2"), None]],
+            ),
+            "SELECT * FROM `shop`.`items`" => Response::rows(&["id"], &[]),
+            _ => Response::Ok,
+        });
+
+        let options =
+            SnapshotOptions::new(vec!["shop.orders".to_string(), "shop.items".to_string()]);
+        let mut reader = SnapshotReader::new(create_options(server.port), options);
+        let rows: Vec<_> = reader.read().unwrap().map(|x| x.unwrap()).collect();
+
+        assert_eq!(2, rows.len());
+        assert_eq!("orders", rows[0].table);
+        let columns: Vec<_> = rows[0].columns.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(vec!["id", "note"], columns);
+        assert_eq!(vec![Some(MySqlValue::Int(2)), None], rows[1].data.cells);
+
+        let client = reader.into_client();
+        let binlog = &client.options.binlog;
+        assert_eq!(StartingStrategy::FromGtid, binlog.starting_strategy);
+        assert_eq!(
+            "24bc7850-2c16-11e6-a073-0242ac110002:1-15",
+            binlog.gtid_set.as_ref().unwrap().to_string()
+        );
+        drop(client);
+
+        assert_eq!(
+            vec![
+                "SET SESSION TRANSACTION ISOLATION LEVEL REPEATABLE READ",
+                "SET time_zone = '+00:00'",
+                "FLUSH TABLES WITH READ LOCK",
+                "START TRANSACTION WITH CONSISTENT SNAPSHOT",
+                "SHOW MASTER STATUS",
+                "UNLOCK TABLES",
+                "SELECT * FROM `shop`.`orders`",
+                "SELECT * FROM `shop`.`items`",
+                "COMMIT",
+            ],
+            server.join()
+        );
+    }
+
+    #[test]
+    fn convert_snapshot_values_to_row_event_types() {
+        let server = TestServer::start("8.0.36", |query| match query {
+            "SHOW MASTER STATUS" => Response::rows(
+                &["File", "Position"],
+                &[&[Some("mysql-bin.000003"), Some("1250")]],
+            ),
+            "SELECT * FROM `shop`.`orders`" => Response::typed_rows(
+                &[
+                    column("id", 3, 0, 11),
+                    column("quantity", 2, 0x20, 5),
+                    Column {
+                        decimals: 2,
+                        ..column("price", 246, 0, 12)
+                    },
+                    Column {
+                        decimals: 3,
+                        ..column("created", 12, 0, 23)
+                    },
+                    column("updated", 7, 0, 19),
+                    column("flags", 16, 0x20, 3),
+                    column("data", 252, 0x10, 65535),
+                    text_column("note"),
+                ],
+                &[&[
+                    Some("-5"),
+                    Some("65535"),
+                    Some("-10.50"),
+                    Some("2024-01-02 03:04:05.678"),
+                    Some("1970-01-02 00:00:01"),
+                    Some("\x05"),
+                    Some("ab"),
+                    Some("first"),
+                ]],
+            ),
+            _ => Response::Ok,
+        });
+
+        let options = SnapshotOptions::new(vec!["shop.orders".to_string()]);
+        let mut reader = SnapshotReader::new(create_options(server.port), options);
+        let rows: Vec<_> = reader.read().unwrap().map(|x| x.unwrap()).collect();
+        drop(reader);
+        server.join();
+
+        let price = Decimal {
+            negative: true,
+            integral: "10".to_string(),
+            fractional: "50".to_string(),
+            precision: 10,
+            scale: 2,
+        };
+        let created = DateTime {
+            year: 2024,
+            month: 1,
+            day: 2,
+            hour: 3,
+            minute: 4,
+            second: 5,
+            millis: 678,
+        };
+        let expected = vec![
+            Some(MySqlValue::Int(-5i32 as u32)),
+            Some(MySqlValue::SmallInt(65535)),
+            Some(MySqlValue::Decimal(price)),
+            Some(MySqlValue::DateTime(created)),
+            Some(MySqlValue::Timestamp(86_401_000)),
+            Some(MySqlValue::Bit(vec![true, false, true])),
+            Some(MySqlValue::Blob(b"ab".to_vec())),
+            Some(MySqlValue::String("first".to_string())),
+        ];
+        assert_eq!(expected, rows[0].data.cells);
+    }
+
+    #[test]
+    fn capture_position_with_binary_log_status() {
+        let server = TestServer::start("8.4.0", |query| match query {
+            "SHOW BINARY LOG STATUS" => Response::rows(
+                &["File", "Position"],
+                &[&[Some("mysql-bin.000007"), Some("157")]],
+            ),
+            "SHOW MASTER STATUS" => Response::Error(1064, "You have an error in your SQL syntax"),
+            _ => Response::Ok,
+        });
+
+        let mut options = SnapshotOptions::new(Vec::new());
+        options.use_gtid = false;
+        let mut reader = SnapshotReader::new(create_options(server.port), options);
+        assert!(reader.read().unwrap().next().is_none());
+
+        let client = reader.into_client();
+        let binlog = &client.options.binlog;
+        assert_eq!("mysql-bin.000007", binlog.filename);
+        assert_eq!(157, binlog.position);
+        drop(client);
+        assert!(server
+            .join()
+            .contains(&"SHOW BINARY LOG STATUS".to_string()));
+    }
+
+    #[test]
+    fn capture_mariadb_snapshot_position() {
+        let server = TestServer::start("10.11.6-MariaDB", |query| match query {
+            "SHOW STATUS LIKE 'binlog_snapshot_%'" => Response::rows(
+                &["Variable_name", "Value"],
+                &[
+                    &[Some("binlog_snapshot_file"), Some("mariadb-bin.000002")],
+                    &[Some("binlog_snapshot_position"), Some("3480")],
+                ],
+            ),
+            "SELECT BINLOG_GTID_POS('mariadb-bin.000002', 3480)" => {
+                Response::rows(&["pos"], &[&[Some("0-1-270")]])
+            }
+            "SELECT * FROM `shop`.`orders`" => Response::Error(1146, "Table doesn't exist"),
+            _ => Response::Ok,
+        });
+
+        let mut options = SnapshotOptions::new(vec!["shop.orders".to_string()]);
+        options.locking = SnapshotLocking::ConsistentSnapshot;
+        let mut reader = SnapshotReader::new(create_options(server.port), options);
+        let mut rows = reader.read().unwrap();
+        assert!(rows.next().unwrap().is_err());
+        assert!(rows.next().is_none());
+        drop(rows);
+
+        let client = reader.into_client();
+        let gtid_list = client.options.binlog.gtid_list.as_ref().unwrap();
+        assert_eq!("0-1-270", gtid_list.to_string());
+        drop(client);
+        assert!(!server
+            .join()
+            .contains(&"FLUSH TABLES WITH READ LOCK".to_string()));
+    }
+}
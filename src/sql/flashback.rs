@@ -0,0 +1,376 @@
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::event_header::EventHeader;
+use crate::events::table_map_event::TableMapEvent;
+use crate::sql::flashback_options::FlashbackOptions;
+use crate::sql::sql_generator::SqlGenerator;
+use std::collections::HashMap;
+
+/// Generates statements that undo row events like mysqlbinlog --flashback.
+/// Inserts become deletes, deletes become inserts and updates swap before and after images.
+/// Transactions and their statements are returned in reverse order.
+pub struct FlashbackGenerator {
+    pub options: FlashbackOptions,
+    generator: SqlGenerator,
+    tables: HashMap<u64, TableMapEvent>,
+    binlog_file: String,
+    gtid_included: bool,
+
+    /// Undo statements of each transaction in original order.
+    transactions: Vec<Vec<String>>,
+    in_transaction: bool,
+}
+
+impl FlashbackGenerator {
+    pub fn new(options: FlashbackOptions) -> Self {
+        let generator = SqlGenerator::new(options.sql.clone());
+        let gtid_included = !has_gtid_bounds(&options);
+        Self {
+            options,
+            generator,
+            tables: HashMap::new(),
+            binlog_file: String::new(),
+            gtid_included,
+            transactions: Vec::new(),
+            in_transaction: false,
+        }
+    }
+
+    /// Sets binlog file name of the following events.
+    /// The name is also updated by RotateEvent.
+    pub fn set_binlog_file(&mut self, binlog_file: &str) {
+        self.binlog_file = binlog_file.to_string();
+    }
+
+    /// Handles the event. Returns false when the event is after the range and reading can stop.
+    pub fn add(&mut self, header: &EventHeader, event: &BinlogEvent) -> Result<bool, Error> {
+        match event {
+            BinlogEvent::TableMapEvent(x) => {
+                self.tables.insert(x.table_id, x.clone());
+            }
+            BinlogEvent::RotateEvent(x) => self.binlog_file = x.binlog_filename.clone(),
+            BinlogEvent::MySqlGtidEvent(x) => {
+                let options = &self.options;
+                self.gtid_included = options
+                    .start_gtid_set
+                    .as_ref()
+                    .is_none_or(|set| !set.contains(&x.gtid))
+                    && options
+                        .stop_gtid_set
+                        .as_ref()
+                        .is_none_or(|set| set.contains(&x.gtid));
+            }
+            BinlogEvent::MariaDbGtidEvent(x) => {
+                let options = &self.options;
+                self.gtid_included = options
+                    .start_gtid_list
+                    .as_ref()
+                    .is_none_or(|list| !list.contains(&x.gtid))
+                    && options
+                        .stop_gtid_list
+                        .as_ref()
+                        .is_none_or(|list| list.contains(&x.gtid));
+            }
+            BinlogEvent::MySqlAnonymousGtidEvent(_) => {
+                self.gtid_included = !has_gtid_bounds(&self.options)
+            }
+            _ => {}
+        }
+
+        // Artificial events like fake RotateEvent have zero timestamp
+        let position = header
+            .next_event_position
+            .saturating_sub(header.event_length);
+        if header.timestamp > 0
+            && self
+                .options
+                .stop_time
+                .is_some_and(|x| header.timestamp >= x)
+        {
+            return Ok(false);
+        }
+        if let Some((file, stop)) = &self.options.stop_position {
+            if !self.binlog_file.is_empty()
+                && (self.binlog_file.as_str(), position) >= (file, *stop)
+            {
+                return Ok(false);
+            }
+        }
+
+        match event {
+            BinlogEvent::MySqlGtidEvent(_)
+            | BinlogEvent::MySqlAnonymousGtidEvent(_)
+            | BinlogEvent::MariaDbGtidEvent(_) => self.begin_transaction(),
+            BinlogEvent::QueryEvent(x) if x.sql_statement == "BEGIN" => self.begin_transaction(),
+            BinlogEvent::QueryEvent(x) if x.sql_statement == "COMMIT" => {
+                self.in_transaction = false
+            }
+            BinlogEvent::XidEvent(_) => self.in_transaction = false,
+            BinlogEvent::WriteRowsEvent(_)
+            | BinlogEvent::UpdateRowsEvent(_)
+            | BinlogEvent::DeleteRowsEvent(_)
+                if self.is_included(header, position) =>
+            {
+                let statements = self.undo(event)?;
+                if !self.in_transaction {
+                    self.begin_transaction();
+                }
+                self.transactions.last_mut().unwrap().extend(statements);
+            }
+            _ => {}
+        }
+        Ok(true)
+    }
+
+    /// Returns undo statements in reverse order.
+    /// Statements of each transaction are wrapped in BEGIN and COMMIT.
+    pub fn finish(self) -> Vec<String> {
+        let mut result = Vec::new();
+        for transaction in self.transactions.into_iter().rev() {
+            if transaction.is_empty() {
+                continue;
+            }
+            result.push("BEGIN".to_string());
+            result.extend(transaction.into_iter().rev());
+            result.push("COMMIT".to_string());
+        }
+        result
+    }
+
+    fn begin_transaction(&mut self) {
+        match self.transactions.last() {
+            Some(x) if x.is_empty() => {}
+            _ => self.transactions.push(Vec::new()),
+        }
+        self.in_transaction = true;
+    }
+
+    fn is_included(&self, header: &EventHeader, position: u32) -> bool {
+        if !self.gtid_included {
+            return false;
+        }
+        if header.timestamp > 0
+            && self
+                .options
+                .start_time
+                .is_some_and(|x| header.timestamp < x)
+        {
+            return false;
+        }
+        if let Some((file, start)) = &self.options.start_position {
+            if (self.binlog_file.as_str(), position) < (file, *start) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Returns undo statements of the event rows in original order.
+    fn undo(&self, event: &BinlogEvent) -> Result<Vec<String>, Error> {
+        let table_id = match event {
+            BinlogEvent::WriteRowsEvent(x) => x.table_id,
+            BinlogEvent::UpdateRowsEvent(x) => x.table_id,
+            BinlogEvent::DeleteRowsEvent(x) => x.table_id,
+            _ => return Ok(Vec::new()),
+        };
+        let table = match self.tables.get(&table_id) {
+            Some(x) => x,
+            None => return Err(Error::String(format!("Table map {} not found", table_id))),
+        };
+        if !self.includes_table(table) {
+            return Ok(Vec::new());
+        }
+
+        match event {
+            BinlogEvent::WriteRowsEvent(x) => {
+                self.generator.delete(table, &x.columns_present, &x.rows)
+            }
+            BinlogEvent::DeleteRowsEvent(x) => {
+                self.generator.insert(table, &x.columns_present, &x.rows)
+            }
+            BinlogEvent::UpdateRowsEvent(x) => x
+                .rows
+                .iter()
+                .map(|row| {
+                    self.generator.update(
+                        table,
+                        &x.columns_after_update,
+                        &row.after_update,
+                        &x.columns_before_update,
+                        &row.before_update,
+                    )
+                })
+                .collect(),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    fn includes_table(&self, table: &TableMapEvent) -> bool {
+        self.options.tables.is_empty()
+            || self.options.tables.iter().any(|x| match x.split_once('.') {
+                Some((database, name)) => {
+                    database == table.database_name && name == table.table_name
+                }
+                None => *x == table.table_name,
+            })
+    }
+}
+
+fn has_gtid_bounds(options: &FlashbackOptions) -> bool {
+    options.start_gtid_set.is_some()
+        || options.stop_gtid_set.is_some()
+        || options.start_gtid_list.is_some()
+        || options.stop_gtid_list.is_some()
+}
+
+/// Generates undo statements for events of BinlogReader or BinlogClient.
+/// Reading stops after the range. Use stop bounds or non-blocking mode with BinlogClient.
+pub fn flashback(
+    events: impl Iterator<Item = Result<(EventHeader, BinlogEvent), Error>>,
+    binlog_file: &str,
+    options: FlashbackOptions,
+) -> Result<Vec<String>, Error> {
+    let mut generator = FlashbackGenerator::new(options);
+    generator.set_binlog_file(binlog_file);
+    for result in events {
+        let (header, event) = result?;
+        if !generator.add(&header, &event)? {
+            break;
+        }
+    }
+    Ok(generator.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::flashback;
+    use crate::events::binlog_event::BinlogEvent;
+    use crate::events::event_header::EventHeader;
+    use crate::events::row_events::delete_rows_event::DeleteRowsEvent;
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::events::row_events::row_data::{RowData, UpdateRowData};
+    use crate::events::row_events::update_rows_event::UpdateRowsEvent;
+    use crate::events::row_events::write_rows_event::WriteRowsEvent;
+    use crate::events::xid_event::XidEvent;
+    use crate::sql::flashback_options::FlashbackOptions;
+    use crate::sql::sql_options::SqlOptions;
+    use crate::test_fixtures::{create_header, create_table, ID, TABLE_ID};
+
+    fn create_row(id: u32, note: &str) -> RowData {
+        RowData::new(vec![
+            Some(MySqlValue::Int(id)),
+            Some(MySqlValue::String(note.to_string())),
+        ])
+    }
+
+    fn create_events() -> Vec<(EventHeader, BinlogEvent)> {
+        let mut table = create_table(&[ID, ("note", 15, 80)]);
+        table.table_metadata.as_mut().unwrap().simple_primary_keys = Some(vec![0]);
+        let write = WriteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 2,
+            columns_present: vec![true, true],
+            rows: vec![create_row(1, "a")],
+        };
+        let update = UpdateRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 2,
+            columns_before_update: vec![true, true],
+            columns_after_update: vec![true, true],
+            rows: vec![UpdateRowData::new(create_row(1, "a"), create_row(1, "b"))],
+        };
+        let delete = DeleteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 2,
+            columns_present: vec![true, true],
+            rows: vec![create_row(2, "c")],
+        };
+
+        let events = vec![
+            BinlogEvent::TableMapEvent(table),
+            BinlogEvent::WriteRowsEvent(write),
+            BinlogEvent::XidEvent(XidEvent { xid: 1 }),
+            BinlogEvent::UpdateRowsEvent(update),
+            BinlogEvent::DeleteRowsEvent(delete),
+            BinlogEvent::XidEvent(XidEvent { xid: 2 }),
+        ];
+        events
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| (create_header(100 + i as u32 * 100), event))
+            .collect()
+    }
+
+    #[test]
+    fn undo_transactions_in_reverse_order() {
+        let events = create_events().into_iter().map(Ok);
+        let statements = flashback(events, "bin.000001", FlashbackOptions::default()).unwrap();
+
+        assert_eq!(
+            vec![
+                "BEGIN",
+                "INSERT INTO `shop`.`orders` (`id`, `note`) VALUES (2, 'c')",
+                "UPDATE `shop`.`orders` SET `id`=1, `note`='a' WHERE `id`=1",
+                "COMMIT",
+                "BEGIN",
+                "DELETE FROM `shop`.`orders` WHERE `id`=1",
+                "COMMIT",
+            ],
+            statements
+        );
+    }
+
+    #[test]
+    fn undo_events_of_position_range() {
+        let events = create_events().into_iter().map(Ok);
+        let options = FlashbackOptions {
+            start_position: Some(("bin.000001".to_string(), 300)),
+            stop_position: Some(("bin.000001".to_string(), 500)),
+            ..Default::default()
+        };
+        let statements = flashback(events, "bin.000001", options).unwrap();
+
+        assert_eq!(
+            vec![
+                "BEGIN",
+                "UPDATE `shop`.`orders` SET `id`=1, `note`='a' WHERE `id`=1",
+                "COMMIT",
+            ],
+            statements
+        );
+    }
+
+    #[test]
+    fn undo_duplicate_inserts_without_key_per_row() {
+        let write = WriteRowsEvent {
+            table_id: TABLE_ID,
+            flags: 0,
+            columns_number: 2,
+            columns_present: vec![true, true],
+            rows: vec![create_row(1, "a"), create_row(1, "a")],
+        };
+        let events = vec![
+            BinlogEvent::TableMapEvent(create_table(&[ID, ("note", 15, 80)])),
+            BinlogEvent::WriteRowsEvent(write),
+            BinlogEvent::XidEvent(XidEvent { xid: 1 }),
+        ];
+        let events = events
+            .into_iter()
+            .enumerate()
+            .map(|(i, event)| Ok((create_header(100 + i as u32 * 100), event)));
+        let options = FlashbackOptions {
+            sql: SqlOptions {
+                multi_row: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let statements = flashback(events, "bin.000001", options).unwrap();
+
+        let delete = "DELETE FROM `shop`.`orders` WHERE `id`=1 AND `note`='a' LIMIT 1";
+        assert_eq!(vec!["BEGIN", delete, delete, "COMMIT"], statements);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::providers::mariadb::gtid::gtid_list::GtidList;
+use crate::providers::mysql::gtid::gtid_set::GtidSet;
+use crate::sql::sql_options::SqlOptions;
+
+/// Range of events and tables to generate undo statements for.
+/// Unset bounds don't limit the range.
+#[derive(Debug, Default)]
+pub struct FlashbackOptions {
+    /// Settings of generated statements.
+    pub sql: SqlOptions,
+
+    /// Binlog file name and position of the first event in the range.
+    pub start_position: Option<(String, u32)>,
+
+    /// Binlog file name and position where the range ends. The event at the position is excluded.
+    pub stop_position: Option<(String, u32)>,
+
+    /// Events with timestamp before the value in seconds are excluded.
+    pub start_time: Option<u32>,
+
+    /// The range ends at the first event with timestamp equal or later than the value in seconds.
+    pub stop_time: Option<u32>,
+
+    /// MySQL transactions contained in the set are excluded.
+    pub start_gtid_set: Option<GtidSet>,
+
+    /// MySQL transactions not contained in the set are excluded.
+    pub stop_gtid_set: Option<GtidSet>,
+
+    /// MariaDB transactions at or before the list are excluded.
+    pub start_gtid_list: Option<GtidList>,
+
+    /// MariaDB transactions after the list are excluded.
+    pub stop_gtid_list: Option<GtidList>,
+
+    /// Tables given as name or database.name. Empty list includes all tables.
+    pub tables: Vec<String>,
+}
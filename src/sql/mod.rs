@@ -0,0 +1,5 @@
+pub mod flashback;
+pub mod flashback_options;
+pub mod sql_generator;
+pub mod sql_options;
+pub mod value_formatter;
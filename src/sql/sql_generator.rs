@@ -0,0 +1,341 @@
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::binlog_event::BinlogEvent;
+use crate::events::row_events::row_data::RowData;
+use crate::events::table_map_event::TableMapEvent;
+use crate::sql::sql_options::SqlOptions;
+use crate::sql::value_formatter::{format_value, quote_identifier};
+
+const COLUMN_NAMES_NOT_FOUND: &str =
+    "Column names are not available. Set binlog_row_metadata=FULL on the server";
+
+/// Reconstructs INSERT, UPDATE and DELETE statements from row events.
+/// Statements are returned without trailing semicolon.
+pub struct SqlGenerator {
+    pub options: SqlOptions,
+}
+
+impl SqlGenerator {
+    pub fn new(options: SqlOptions) -> Self {
+        Self { options }
+    }
+
+    /// Generates statements of the row event. Returns empty list for other events.
+    pub fn generate(
+        &self,
+        event: &BinlogEvent,
+        table: &TableMapEvent,
+    ) -> Result<Vec<String>, Error> {
+        match event {
+            BinlogEvent::WriteRowsEvent(x) => self.insert(table, &x.columns_present, &x.rows),
+            BinlogEvent::UpdateRowsEvent(x) => x
+                .rows
+                .iter()
+                .map(|row| {
+                    self.update(
+                        table,
+                        &x.columns_before_update,
+                        &row.before_update,
+                        &x.columns_after_update,
+                        &row.after_update,
+                    )
+                })
+                .collect(),
+            BinlogEvent::DeleteRowsEvent(x) => self.delete(table, &x.columns_present, &x.rows),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    /// Generates INSERT statements of the rows.
+    /// Columns are listed by name unless the row image is full and names are unknown.
+    pub fn insert(
+        &self,
+        table: &TableMapEvent,
+        columns: &[bool],
+        rows: &[RowData],
+    ) -> Result<Vec<String>, Error> {
+        let indexes = present_columns(columns);
+        let column_list = match column_names(table) {
+            Some(names) => {
+                let names: Vec<String> = indexes
+                    .iter()
+                    .map(|x| quote_identifier(&names[*x]))
+                    .collect();
+                format!(" ({})", names.join(", "))
+            }
+            None if indexes.len() == table.column_types.len() => String::new(),
+            None => return Err(Error::String(COLUMN_NAMES_NOT_FOUND.to_string())),
+        };
+
+        let prefix = format!(
+            "INSERT INTO {}{} VALUES ",
+            self.table_name(table),
+            column_list
+        );
+        let values = rows
+            .iter()
+            .map(|row| {
+                let values = indexes
+                    .iter()
+                    .map(|x| format_value(table, *x, row.cells[*x].as_ref()))
+                    .collect::<Result<Vec<String>, Error>>()?;
+                Ok(format!("({})", values.join(", ")))
+            })
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        match self.options.multi_row {
+            true if !values.is_empty() => Ok(vec![format!("{}{}", prefix, values.join(", "))]),
+            true => Ok(Vec::new()),
+            false => Ok(values.iter().map(|x| format!("{}{}", prefix, x)).collect()),
+        }
+    }
+
+    /// Generates UPDATE statement changing the row from before to after image.
+    pub fn update(
+        &self,
+        table: &TableMapEvent,
+        before_columns: &[bool],
+        before: &RowData,
+        after_columns: &[bool],
+        after: &RowData,
+    ) -> Result<String, Error> {
+        let names = column_names(table).ok_or(Error::String(COLUMN_NAMES_NOT_FOUND.to_string()))?;
+        let assignments = present_columns(after_columns)
+            .into_iter()
+            .map(|x| {
+                let value = format_value(table, x, after.cells[x].as_ref())?;
+                Ok(format!("{}={}", quote_identifier(&names[x]), value))
+            })
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        let (keys, is_key) = key_columns(table, before_columns);
+        let mut sql = format!(
+            "UPDATE {} SET {} WHERE {}",
+            self.table_name(table),
+            assignments.join(", "),
+            condition(table, names, &keys, before)?
+        );
+        if !is_key {
+            sql.push_str(" LIMIT 1");
+        }
+        Ok(sql)
+    }
+
+    /// Generates DELETE statements of the rows.
+    pub fn delete(
+        &self,
+        table: &TableMapEvent,
+        columns: &[bool],
+        rows: &[RowData],
+    ) -> Result<Vec<String>, Error> {
+        let names = column_names(table).ok_or(Error::String(COLUMN_NAMES_NOT_FOUND.to_string()))?;
+        let (keys, is_key) = key_columns(table, columns);
+        let conditions = rows
+            .iter()
+            .map(|row| condition(table, names, &keys, row))
+            .collect::<Result<Vec<String>, Error>>()?;
+
+        let prefix = format!("DELETE FROM {} WHERE ", self.table_name(table));
+        // Rows without key are deleted one by one so that duplicate rows are deleted once per row
+        if self.options.multi_row && is_key {
+            if conditions.is_empty() {
+                return Ok(Vec::new());
+            }
+            let conditions: Vec<String> = conditions.iter().map(|x| format!("({})", x)).collect();
+            return Ok(vec![format!("{}{}", prefix, conditions.join(" OR "))]);
+        }
+
+        let suffix = if is_key { "" } else { " LIMIT 1" };
+        Ok(conditions
+            .iter()
+            .map(|x| format!("{}{}{}", prefix, x, suffix))
+            .collect())
+    }
+
+    fn table_name(&self, table: &TableMapEvent) -> String {
+        match self.options.qualify_table_names {
+            true => format!(
+                "{}.{}",
+                quote_identifier(&table.database_name),
+                quote_identifier(&table.table_name)
+            ),
+            false => quote_identifier(&table.table_name),
+        }
+    }
+}
+
+fn column_names(table: &TableMapEvent) -> Option<&Vec<String>> {
+    let names = table.table_metadata.as_ref()?.column_names.as_ref()?;
+    match names.len() == table.column_types.len() {
+        true => Some(names),
+        false => None,
+    }
+}
+
+fn present_columns(columns: &[bool]) -> Vec<usize> {
+    (0..columns.len()).filter(|x| columns[*x]).collect()
+}
+
+/// Returns primary key columns if they are present in the row image.
+/// Otherwise returns all present columns except FLOAT that cannot be compared exactly.
+fn key_columns(table: &TableMapEvent, columns: &[bool]) -> (Vec<usize>, bool) {
+    let mut keys: Vec<usize> = match &table.table_metadata {
+        Some(metadata) => match (
+            &metadata.simple_primary_keys,
+            &metadata.primary_keys_with_prefix,
+        ) {
+            (Some(keys), _) => keys.iter().map(|x| *x as usize).collect(),
+            (None, Some(keys)) => keys.iter().map(|(x, _)| *x as usize).collect(),
+            (None, None) => Vec::new(),
+        },
+        None => Vec::new(),
+    };
+    keys.retain(|x| *x < columns.len());
+    if !keys.is_empty() && keys.iter().all(|x| columns[*x]) {
+        return (keys, true);
+    }
+
+    let present = present_columns(columns);
+    let exact: Vec<usize> = present
+        .iter()
+        .copied()
+        .filter(|x| table.actual_column_type(*x).0 != ColumnType::Float as u8)
+        .collect();
+    match exact.is_empty() {
+        true => (present, false),
+        false => (exact, false),
+    }
+}
+
+fn condition(
+    table: &TableMapEvent,
+    names: &[String],
+    columns: &[usize],
+    row: &RowData,
+) -> Result<String, Error> {
+    let conditions = columns
+        .iter()
+        .map(|x| match &row.cells[*x] {
+            Some(value) => Ok(format!(
+                "{}={}",
+                quote_identifier(&names[*x]),
+                format_value(table, *x, Some(value))?
+            )),
+            None => Ok(format!("{} IS NULL", quote_identifier(&names[*x]))),
+        })
+        .collect::<Result<Vec<String>, Error>>()?;
+    Ok(conditions.join(" AND "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SqlGenerator;
+    use crate::events::row_events::mysql_value::{Date, Decimal, MySqlValue};
+    use crate::events::row_events::row_data::RowData;
+    use crate::events::table_map_event::TableMapEvent;
+    use crate::sql::sql_options::SqlOptions;
+    use crate::test_fixtures::{self, ID};
+
+    fn create_table(primary_keys: Option<Vec<u32>>) -> TableMapEvent {
+        let columns = [
+            ID,
+            ("note", 15, 80),
+            ("total", 246, 0x0205),
+            ("created", 10, 0),
+        ];
+        let mut table = test_fixtures::create_table(&columns);
+        table.table_metadata.as_mut().unwrap().simple_primary_keys = primary_keys;
+        table
+    }
+
+    fn create_row(id: u32, note: Option<&str>) -> RowData {
+        RowData::new(vec![
+            Some(MySqlValue::Int(id)),
+            note.map(|x| MySqlValue::String(x.to_string())),
+            Some(MySqlValue::Decimal(Decimal::parse("-12.50").unwrap())),
+            Some(MySqlValue::Date(Date {
+                year: 2024,
+                month: 1,
+                day: 2,
+            })),
+        ])
+    }
+
+    #[test]
+    fn generate_statements_by_primary_key() {
+        let table = create_table(Some(vec![0]));
+        let generator = SqlGenerator::new(SqlOptions::default());
+        let columns = vec![true; 4];
+
+        let rows = vec![create_row(1, Some("it's")), create_row(2, None)];
+        assert_eq!(
+            vec![
+                "INSERT INTO `shop`.`orders` (`id`, `note`, `total`, `created`) VALUES (1, 'it\\'s', -12.50, '2024-01-02')",
+                "INSERT INTO `shop`.`orders` (`id`, `note`, `total`, `created`) VALUES (2, NULL, -12.50, '2024-01-02')",
+            ],
+            generator.insert(&table, &columns, &rows).unwrap()
+        );
+
+        let before = create_row(1, Some("a"));
+        let after = create_row(1, Some("b"));
+        assert_eq!(
+            "UPDATE `shop`.`orders` SET `id`=1, `note`='b', `total`=-12.50, `created`='2024-01-02' WHERE `id`=1",
+            generator.update(&table, &columns, &before, &columns, &after).unwrap()
+        );
+        assert_eq!(
+            vec!["DELETE FROM `shop`.`orders` WHERE `id`=2"],
+            generator
+                .delete(&table, &columns, &[create_row(2, None)])
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn generate_statements_by_before_image() {
+        let table = create_table(None);
+        let mut options = SqlOptions::default();
+        options.multi_row = true;
+        options.qualify_table_names = false;
+        let generator = SqlGenerator::new(options);
+        let columns = vec![true, true, false, false];
+
+        let rows = vec![create_row(1, Some("a")), create_row(2, None)];
+        assert_eq!(
+            vec!["INSERT INTO `orders` (`id`, `note`) VALUES (1, 'a'), (2, NULL)"],
+            generator.insert(&table, &columns, &rows).unwrap()
+        );
+        assert_eq!(
+            vec![
+                "DELETE FROM `orders` WHERE `id`=1 AND `note`='a' LIMIT 1",
+                "DELETE FROM `orders` WHERE `id`=2 AND `note` IS NULL LIMIT 1",
+            ],
+            generator.delete(&table, &columns, &rows).unwrap()
+        );
+    }
+
+    #[test]
+    fn delete_duplicate_rows_without_key_once_per_row() {
+        let mut options = SqlOptions::default();
+        options.multi_row = true;
+        options.qualify_table_names = false;
+        let generator = SqlGenerator::new(options);
+        let columns = vec![true, true, false, false];
+
+        let rows = vec![create_row(1, Some("a")), create_row(1, Some("a"))];
+        let statement = "DELETE FROM `orders` WHERE `id`=1 AND `note`='a' LIMIT 1";
+        assert_eq!(
+            vec![statement, statement],
+            generator
+                .delete(&create_table(None), &columns, &rows)
+                .unwrap()
+        );
+
+        let rows = vec![create_row(1, Some("a")), create_row(2, Some("b"))];
+        assert_eq!(
+            vec!["DELETE FROM `orders` WHERE (`id`=1) OR (`id`=2)"],
+            generator
+                .delete(&create_table(Some(vec![0])), &columns, &rows)
+                .unwrap()
+        );
+    }
+}
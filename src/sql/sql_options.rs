@@ -0,0 +1,19 @@
+/// Settings of SQL generator.
+#[derive(Clone, Debug)]
+pub struct SqlOptions {
+    /// Defines whether rows of an event are rendered as one statement. Defaults to false.
+    /// Applies to INSERT and DELETE by key. Updates and deletes of rows without key are rendered per row.
+    pub multi_row: bool,
+
+    /// Defines whether table names are qualified with database name. Defaults to true.
+    pub qualify_table_names: bool,
+}
+
+impl Default for SqlOptions {
+    fn default() -> Self {
+        Self {
+            multi_row: false,
+            qualify_table_names: true,
+        }
+    }
+}
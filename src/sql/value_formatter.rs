@@ -0,0 +1,148 @@
+use crate::constants::column_type::ColumnType;
+use crate::errors::Error;
+use crate::events::row_events::from_value::FromMySqlValue;
+use crate::events::row_events::json_binary::decode_json;
+use crate::events::row_events::mysql_value::MySqlValue;
+use crate::events::table_map_event::TableMapEvent;
+use std::fmt::Write;
+
+/// Quotes identifier with backticks.
+pub fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Quotes string literal escaping special characters like mysql_real_escape_string.
+pub fn quote_string(value: &str) -> String {
+    let mut result = String::with_capacity(value.len() + 2);
+    result.push('\'');
+    for c in value.chars() {
+        match c {
+            '\0' => result.push_str("\\0"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\\' => result.push_str("\\\\"),
+            '\'' => result.push_str("\\'"),
+            '"' => result.push_str("\\\""),
+            '\x1a' => result.push_str("\\Z"),
+            c => result.push(c),
+        }
+    }
+    result.push('\'');
+    result
+}
+
+/// Formats bytes as hexadecimal literal.
+pub fn quote_bytes(value: &[u8]) -> String {
+    if value.is_empty() {
+        return "''".to_string();
+    }
+    let mut result = String::with_capacity(value.len() * 2 + 3);
+    result.push_str("X'");
+    for byte in value {
+        write!(result, "{:02X}", byte).unwrap();
+    }
+    result.push('\'');
+    result
+}
+
+/// Formats the cell of the table column as SQL literal.
+pub fn format_value(
+    table: &TableMapEvent,
+    column_index: usize,
+    value: Option<&MySqlValue>,
+) -> Result<String, Error> {
+    let value = match value {
+        Some(x) => x,
+        None => return Ok("NULL".to_string()),
+    };
+
+    let unsigned = table.is_unsigned(column_index).unwrap_or(false);
+    let convert = |value: &MySqlValue| -> Result<Vec<u8>, Error> {
+        Vec::<u8>::from_mysql_value(Some(value), unsigned).map_err(Error::String)
+    };
+    let column_type = ColumnType::from_code(table.actual_column_type(column_index).0)?;
+
+    let result = match (column_type, value) {
+        (_, MySqlValue::TinyInt(_))
+        | (_, MySqlValue::SmallInt(_))
+        | (_, MySqlValue::MediumInt(_))
+        | (_, MySqlValue::Int(_))
+        | (_, MySqlValue::BigInt(_)) => match unsigned {
+            true => u64::from_mysql_value(Some(value), true).map(|x| x.to_string()),
+            false => i64::from_mysql_value(Some(value), false).map(|x| x.to_string()),
+        }
+        .map_err(Error::String)?,
+        (_, MySqlValue::Float(x)) => x.to_string(),
+        (_, MySqlValue::Double(x)) => x.to_string(),
+        (_, MySqlValue::Decimal(x)) => x.to_string(),
+        (_, MySqlValue::Year(x)) => x.to_string(),
+        (ColumnType::Json, _) => format!(
+            "CAST({} AS JSON)",
+            quote_string(&decode_json(&convert(value)?)?)
+        ),
+        (ColumnType::Geometry, _) => quote_bytes(&convert(value)?),
+        (_, MySqlValue::String(_)) | (_, MySqlValue::Blob(_)) => {
+            let bytes = convert(value)?;
+            match table.is_binary_column(column_index) {
+                true => quote_bytes(&bytes),
+                // Values of other charsets are kept as bytes
+                false => match std::str::from_utf8(&bytes) {
+                    Ok(x) => quote_string(x),
+                    Err(_) => quote_bytes(&bytes),
+                },
+            }
+        }
+        (_, MySqlValue::Bit(bits)) => {
+            // Bits are stored from the most significant
+            let bits: String = bits.iter().map(|x| if *x { '1' } else { '0' }).collect();
+            format!("b'{}'", bits)
+        }
+        (_, MySqlValue::Enum(x)) => match table.enum_values(column_index) {
+            Some(_) => quote_string(&table.enum_string(column_index, *x)),
+            None => x.to_string(),
+        },
+        (_, MySqlValue::Set(x)) => match table.set_values(column_index) {
+            Some(_) => quote_string(&table.set_string(column_index, *x)),
+            None => x.to_string(),
+        },
+        (_, MySqlValue::Date(x)) => format!("'{}'", x),
+        (_, MySqlValue::Time(x)) => format!("'{}'", x),
+        (_, MySqlValue::DateTime(x)) => format!("'{}'", x),
+        // Zero value doesn't depend on time zone of the session
+        (_, MySqlValue::Timestamp(0)) => "'0000-00-00 00:00:00'".to_string(),
+        (_, MySqlValue::Timestamp(x)) => {
+            format!("FROM_UNIXTIME({}.{:03})", x / 1000, x % 1000)
+        }
+    };
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_value, quote_bytes, quote_identifier, quote_string};
+    use crate::events::row_events::mysql_value::MySqlValue;
+    use crate::test_fixtures::{create_table, ID};
+
+    #[test]
+    fn quote_literals() {
+        assert_eq!("`or``ders`", quote_identifier("or`ders"));
+        assert_eq!(
+            "'It\\'s \\\"a\\\"\\n\\\\path\\0'",
+            quote_string("It's \"a\"\n\\path\0")
+        );
+        assert_eq!("X'00FF10'", quote_bytes(&[0x00, 0xFF, 0x10]));
+        assert_eq!("''", quote_bytes(&[]));
+    }
+
+    #[test]
+    fn format_bit_from_most_significant() {
+        // BIT(10) column
+        let table = create_table(&[ID, ("flags", 16, 0x0102)]);
+        let bits = "1000000011".chars().map(|x| x == '1').collect();
+        let value = MySqlValue::Bit(bits);
+        assert_eq!(
+            "b'1000000011'",
+            format_value(&table, 1, Some(&value)).unwrap()
+        );
+    }
+}
@@ -1,4 +1,5 @@
 #[derive(Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum StartingStrategy {
     FromStart,
     FromEnd,
@@ -0,0 +1,57 @@
+use crate::events::event_header::EventHeader;
+use crate::events::row_events::mysql_value::{Decimal, MySqlValue};
+use crate::events::row_events::row_data::RowData;
+use crate::events::table_map_event::TableMapEvent;
+use crate::metadata::default_charset::DefaultCharset;
+use crate::metadata::table_metadata::TableMetadata;
+
+/// Table id of the test table in row events.
+pub const TABLE_ID: u64 = 7;
+
+/// INT column.
+pub const ID: (&str, u8, u16) = ("id", 3, 0);
+
+/// VARCHAR(100) column.
+pub const NAME: (&str, u8, u16) = ("name", 15, 400);
+
+/// DECIMAL(10,2) column.
+pub const PRICE: (&str, u8, u16) = ("price", 246, 0x0200 | 10);
+
+/// Creates TableMapEvent of shop.orders table with utf8mb4 default charset.
+/// Columns are given as name, type and metadata. The first column is NOT NULL.
+pub fn create_table(columns: &[(&str, u8, u16)]) -> TableMapEvent {
+    TableMapEvent {
+        table_id: TABLE_ID,
+        database_name: "shop".to_string(),
+        table_name: "orders".to_string(),
+        column_types: columns.iter().map(|x| x.1).collect(),
+        column_metadata: columns.iter().map(|x| x.2).collect(),
+        null_bitmap: (0..columns.len()).map(|x| x > 0).collect(),
+        table_metadata: Some(TableMetadata {
+            default_charset: Some(DefaultCharset::new(255, vec![])),
+            column_names: Some(columns.iter().map(|x| x.0.to_string()).collect()),
+            ..Default::default()
+        }),
+    }
+}
+
+/// Creates row of ID, NAME and PRICE columns.
+pub fn create_row(id: u32, name: &str) -> RowData {
+    RowData::new(vec![
+        Some(MySqlValue::Int(id)),
+        Some(MySqlValue::String(name.to_string())),
+        Some(MySqlValue::Decimal(Decimal::parse("-123.45").unwrap())),
+    ])
+}
+
+/// Creates header of 100 bytes long event at the position.
+pub fn create_header(position: u32) -> EventHeader {
+    EventHeader {
+        timestamp: 1,
+        event_type: 30,
+        server_id: 1,
+        event_length: 100,
+        next_event_position: position + 100,
+        event_flags: 0,
+    }
+}
@@ -0,0 +1,458 @@
+//! Scripted stand-in for MySQL/MariaDB server used in tests.
+//! Accepts one connection, authenticates any user and answers queries using a handler.
+
+use crate::compression_mode::CompressionMode;
+use crate::constants::capability_flags;
+use crate::extensions::xor;
+use crate::packet_compression::PacketCompression;
+use crate::result_set::Column;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use ed25519_dalek::hazmat::ExpandedSecretKey;
+use ed25519_dalek::{Signature, SigningKey, VerifyingKey};
+use openssl::hash::MessageDigest;
+use openssl::pkcs5::pbkdf2_hmac;
+use openssl::pkey::Private;
+use openssl::rsa::{Padding, Rsa};
+use sha2::{Digest, Sha512};
+use std::io::{self, Read, Write};
+use std::net::TcpListener;
+use std::os::unix::net::UnixListener;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::thread::{self, JoinHandle};
+
+const COM_QUIT: u8 = 0x01;
+const COM_QUERY: u8 = 0x03;
+
+/// Response of the server to a query.
+pub enum Response {
+    Ok,
+    Error(u16, &'static str),
+    ResultSet(Vec<Column>, Vec<Vec<Option<String>>>),
+}
+
+impl Response {
+    /// Result set of VARCHAR columns.
+    pub fn rows(columns: &[&str], rows: &[&[Option<&str>]]) -> Self {
+        let columns: Vec<Column> = columns.iter().map(|x| text_column(x)).collect();
+        Self::typed_rows(&columns, rows)
+    }
+
+    /// Result set of the columns with types.
+    pub fn typed_rows(columns: &[Column], rows: &[&[Option<&str>]]) -> Self {
+        let rows = rows
+            .iter()
+            .map(|row| row.iter().map(|x| x.map(|x| x.to_string())).collect())
+            .collect();
+        Response::ResultSet(columns.to_vec(), rows)
+    }
+}
+
+/// Creates utf8mb4 VARCHAR column.
+pub fn text_column(name: &str) -> Column {
+    Column {
+        schema: String::new(),
+        table: String::new(),
+        org_table: String::new(),
+        name: name.to_string(),
+        org_name: name.to_string(),
+        character_set: 45,
+        column_length: 255,
+        column_type: 253,
+        flags: 0,
+        decimals: 0,
+    }
+}
+
+/// Settings of the server handshake.
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub server_version: &'static str,
+
+    /// Ends result sets with OK packet if the client requests CLIENT_DEPRECATE_EOF.
+    pub deprecate_eof: bool,
+
+    /// Switches authentication to the plugin that checks the password.
+    /// Otherwise any user is accepted.
+    pub auth_plugin: Option<&'static str>,
+    pub password: &'static str,
+
+    /// Listens on the Unix socket file instead of TCP port.
+    pub socket_path: Option<PathBuf>,
+
+    /// Supports zlib and zstd compression.
+    pub compression: bool,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            server_version: "8.0.36",
+            deprecate_eof: false,
+            auth_plugin: None,
+            password: "",
+            socket_path: None,
+            compression: false,
+        }
+    }
+}
+
+pub struct TestServer {
+    pub port: u16,
+    /// Queries received by the server.
+    pub queries: Arc<Mutex<Vec<String>>>,
+
+    /// Capabilities requested by the client.
+    pub client_capabilities: Arc<AtomicU64>,
+    handle: JoinHandle<()>,
+}
+
+impl TestServer {
+    pub fn start(
+        server_version: &'static str,
+        handler: impl Fn(&str) -> Response + Send + 'static,
+    ) -> Self {
+        let config = ServerConfig {
+            server_version,
+            ..Default::default()
+        };
+        Self::start_with(config, handler)
+    }
+
+    pub fn start_with(
+        config: ServerConfig,
+        handler: impl Fn(&str) -> Response + Send + 'static,
+    ) -> Self {
+        let queries = Arc::new(Mutex::new(Vec::new()));
+        let log = queries.clone();
+        let client_capabilities = Arc::new(AtomicU64::new(0));
+        let capabilities = client_capabilities.clone();
+
+        // Client may disconnect without COM_QUIT
+        let (port, handle) = match &config.socket_path {
+            Some(path) => {
+                let listener = UnixListener::bind(path).unwrap();
+                let handle = thread::spawn(move || {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let _ = serve(&mut stream, config, &handler, &log, &capabilities);
+                });
+                (0, handle)
+            }
+            None => {
+                let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+                let port = listener.local_addr().unwrap().port();
+                let handle = thread::spawn(move || {
+                    let (mut stream, _) = listener.accept().unwrap();
+                    let _ = serve(&mut stream, config, &handler, &log, &capabilities);
+                });
+                (port, handle)
+            }
+        };
+        Self {
+            port,
+            queries,
+            client_capabilities,
+            handle,
+        }
+    }
+
+    /// Waits until the client disconnects and returns received queries.
+    pub fn join(self) -> Vec<String> {
+        self.handle.join().unwrap();
+        let queries = self.queries.lock().unwrap();
+        queries.clone()
+    }
+}
+
+fn serve(
+    stream: &mut (impl Read + Write),
+    config: ServerConfig,
+    handler: &impl Fn(&str) -> Response,
+    log: &Mutex<Vec<String>>,
+    capabilities: &AtomicU64,
+) -> io::Result<()> {
+    write_packet(stream, &handshake(&config), 0)?;
+    let response = read_packet(stream)?;
+    let client_capabilities = (&response[..]).read_u32::<LittleEndian>()? as u64;
+    capabilities.store(client_capabilities, Ordering::SeqCst);
+    let deprecate_eof = client_capabilities & capability_flags::DEPRECATE_EOF != 0;
+    let authenticated = match config.auth_plugin {
+        Some(plugin) => authenticate(stream, plugin, config.password)?,
+        None => true,
+    };
+    if !authenticated {
+        return write_response(stream, Response::Error(1045, "Access denied"), false);
+    }
+    write_packet(stream, &ok_packet(), 2)?;
+
+    let compression = match client_capabilities {
+        x if x & capability_flags::COMPRESS != 0 => CompressionMode::Zlib,
+        x if x & capability_flags::ZSTD_COMPRESSION_ALGORITHM != 0 => CompressionMode::Zstd,
+        _ => return serve_commands(stream, handler, log, deprecate_eof),
+    };
+    let mut stream = CompressedStream {
+        stream,
+        compression: PacketCompression::new(compression, compression.default_level()),
+    };
+    serve_commands(&mut stream, handler, log, deprecate_eof)
+}
+
+fn serve_commands(
+    stream: &mut (impl Read + Write),
+    handler: &impl Fn(&str) -> Response,
+    log: &Mutex<Vec<String>>,
+    deprecate_eof: bool,
+) -> io::Result<()> {
+    loop {
+        let packet = read_packet(stream)?;
+        match packet[0] {
+            COM_QUERY => {
+                let query = String::from_utf8_lossy(&packet[1..]).to_string();
+                log.lock().unwrap().push(query.clone());
+                write_response(stream, handler(&query), deprecate_eof)?;
+            }
+            COM_QUIT => return Ok(()),
+            _ => write_response(
+                stream,
+                Response::Error(1047, "Unknown command"),
+                deprecate_eof,
+            )?,
+        }
+    }
+}
+
+/// Frames packets of the server in compressed packets.
+/// Every write is sent in a separate frame, so packets are split between frames.
+struct CompressedStream<'a, S> {
+    stream: &'a mut S,
+    compression: PacketCompression,
+}
+
+impl<S: Read> Read for CompressedStream<'_, S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.compression.read_exact(self.stream, buf)?;
+        Ok(buf.len())
+    }
+}
+
+impl<S: Write> Write for CompressedStream<'_, S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.compression.write(self.stream, buf, false)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stream.flush()
+    }
+}
+
+fn write_response(
+    stream: &mut (impl Read + Write),
+    response: Response,
+    deprecate_eof: bool,
+) -> io::Result<()> {
+    match response {
+        Response::Ok => write_packet(stream, &ok_packet(), 1),
+        Response::Error(code, message) => {
+            let mut packet = vec![0xFF];
+            packet.write_u16::<LittleEndian>(code)?;
+            packet.extend_from_slice(b"#HY000");
+            packet.extend_from_slice(message.as_bytes());
+            write_packet(stream, &packet, 1)
+        }
+        Response::ResultSet(columns, rows) => {
+            let mut seq_num = 1;
+            let mut write = |stream: &mut dyn Write, packet: &[u8]| {
+                let result = write_packet(stream, packet, seq_num);
+                seq_num = seq_num.wrapping_add(1);
+                result
+            };
+
+            write(stream, &[columns.len() as u8])?;
+            for column in &columns {
+                write(stream, &column_definition(column))?;
+            }
+            if !deprecate_eof {
+                write(stream, &eof_packet())?;
+            }
+            for row in rows {
+                let mut packet = Vec::new();
+                for cell in row {
+                    match cell {
+                        Some(value) => write_len_enc_str(&mut packet, value.as_bytes()),
+                        None => packet.push(0xFB),
+                    }
+                }
+                write(stream, &packet)?;
+            }
+            match deprecate_eof {
+                true => write(stream, &[&[0xFE][..], &ok_packet()[1..]].concat()),
+                false => write(stream, &eof_packet()),
+            }
+        }
+    }
+}
+
+/// RSA key pair of the server used by sha256_password.
+pub fn rsa_key() -> &'static Rsa<Private> {
+    static KEY: OnceLock<Rsa<Private>> = OnceLock::new();
+    KEY.get_or_init(|| Rsa::generate(2048).unwrap())
+}
+
+/// Switches to the auth plugin and returns whether the client knows the password.
+fn authenticate(
+    stream: &mut (impl Read + Write),
+    plugin: &str,
+    password: &str,
+) -> io::Result<bool> {
+    let scramble = b"abcdefghijklmnopqrst";
+    // Binary scramble of MariaDB plugins can contain zero bytes
+    let nonce: Vec<u8> = (0..32u8).map(|x| x.wrapping_mul(67)).collect();
+
+    let mut packet = vec![0xFE];
+    packet.extend_from_slice(plugin.as_bytes());
+    packet.push(0);
+    match plugin {
+        "client_ed25519" | "parsec" => packet.extend_from_slice(&nonce),
+        _ => {
+            packet.extend_from_slice(scramble);
+            packet.push(0);
+        }
+    }
+    write_packet(stream, &packet, 2)?;
+    let response = read_packet(stream)?;
+
+    let expected_password = [password.as_bytes(), &[0]].concat();
+    let authenticated = match plugin {
+        "mysql_clear_password" => response == expected_password,
+        "sha256_password" => {
+            let encrypted = match response.as_slice() {
+                [0x01] => {
+                    let public_key = rsa_key().public_key_to_pem().unwrap();
+                    write_packet(stream, &[&[0x01][..], &public_key].concat(), 4)?;
+                    read_packet(stream)?
+                }
+                _ => response,
+            };
+            let mut decrypted = vec![0; rsa_key().size() as usize];
+            let length = rsa_key()
+                .private_decrypt(&encrypted, &mut decrypted, Padding::PKCS1_OAEP)
+                .unwrap();
+            xor(&decrypted[..length], scramble) == expected_password
+        }
+        "client_ed25519" => {
+            let secret_key = ExpandedSecretKey::from_bytes(&Sha512::digest(password).into());
+            let signature = Signature::from_slice(&response).unwrap();
+            VerifyingKey::from(&secret_key)
+                .verify_strict(&nonce, &signature)
+                .is_ok()
+        }
+        "parsec" => {
+            let salt = b"0123456789abcdefgh";
+            write_packet(stream, &[&b"P\x00"[..], salt].concat(), 4)?;
+            let response = read_packet(stream)?;
+
+            let mut key = [0; 32];
+            pbkdf2_hmac(
+                password.as_bytes(),
+                salt,
+                1024,
+                MessageDigest::sha512(),
+                &mut key,
+            )
+            .unwrap();
+            let message = [&nonce, &response[..32]].concat();
+            let signature = Signature::from_slice(&response[32..]).unwrap();
+            SigningKey::from_bytes(&key)
+                .verifying_key()
+                .verify_strict(&message, &signature)
+                .is_ok()
+        }
+        _ => false,
+    };
+    Ok(authenticated)
+}
+
+fn handshake(config: &ServerConfig) -> Vec<u8> {
+    let mut capabilities = capability_flags::PROTOCOL_41
+        | capability_flags::SECURE_CONNECTION
+        | capability_flags::PLUGIN_AUTH;
+    if config.deprecate_eof {
+        capabilities |= capability_flags::DEPRECATE_EOF;
+    }
+    if config.compression {
+        capabilities |= capability_flags::COMPRESS | capability_flags::ZSTD_COMPRESSION_ALGORITHM;
+    }
+
+    let mut packet = vec![10];
+    packet.extend_from_slice(config.server_version.as_bytes());
+    packet.push(0);
+    packet.extend_from_slice(&1u32.to_le_bytes());
+    packet.extend_from_slice(b"abcdefgh\0");
+    packet.extend_from_slice(&(capabilities as u16).to_le_bytes());
+    packet.push(45);
+    packet.extend_from_slice(&2u16.to_le_bytes());
+    packet.extend_from_slice(&((capabilities >> 16) as u16).to_le_bytes());
+    packet.push(21);
+    packet.extend_from_slice(&[0; 10]);
+    packet.extend_from_slice(b"ijklmnopqrst\0");
+    packet.extend_from_slice(b"mysql_native_password\0");
+    packet
+}
+
+fn column_definition(column: &Column) -> Vec<u8> {
+    let mut packet = Vec::new();
+    let values = [
+        "def",
+        &column.schema,
+        &column.table,
+        &column.org_table,
+        &column.name,
+        &column.org_name,
+    ];
+    for value in values {
+        write_len_enc_str(&mut packet, value.as_bytes());
+    }
+    packet.push(0x0C);
+    packet.extend_from_slice(&column.character_set.to_le_bytes());
+    packet.extend_from_slice(&column.column_length.to_le_bytes());
+    packet.push(column.column_type);
+    packet.extend_from_slice(&column.flags.to_le_bytes());
+    packet.push(column.decimals);
+    packet.extend_from_slice(&[0; 2]);
+    packet
+}
+
+fn ok_packet() -> Vec<u8> {
+    vec![0x00, 0, 0, 2, 0, 0, 0]
+}
+
+fn eof_packet() -> Vec<u8> {
+    vec![0xFE, 0, 0, 2, 0]
+}
+
+fn write_len_enc_str(packet: &mut Vec<u8>, value: &[u8]) {
+    match value.len() {
+        x if x < 0xFB => packet.push(x as u8),
+        x => {
+            packet.push(0xFC);
+            packet.extend_from_slice(&(x as u16).to_le_bytes());
+        }
+    }
+    packet.extend_from_slice(value);
+}
+
+fn read_packet(stream: &mut impl Read) -> io::Result<Vec<u8>> {
+    let length = stream.read_u24::<LittleEndian>()?;
+    let _seq_num = stream.read_u8()?;
+    let mut packet = vec![0; length as usize];
+    stream.read_exact(&mut packet)?;
+    Ok(packet)
+}
+
+fn write_packet(stream: &mut (impl Write + ?Sized), packet: &[u8], seq_num: u8) -> io::Result<()> {
+    stream.write_u24::<LittleEndian>(packet.len() as u32)?;
+    stream.write_u8(seq_num)?;
+    stream.write_all(packet)
+}
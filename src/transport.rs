@@ -0,0 +1,186 @@
+use socket2::{SockRef, TcpKeepalive};
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpStream, ToSocketAddrs};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::time::Duration;
+
+use crate::replica_options::ReplicaOptions;
+
+/// Stream that carries protocol packets.
+pub enum Transport {
+    Tcp(TcpStream),
+    #[cfg(unix)]
+    Unix(UnixStream),
+}
+
+impl Transport {
+    /// Connects to the server with the index in `ReplicaOptions::servers`.
+    /// The first server is connected through the socket file if it's specified.
+    pub fn connect(options: &ReplicaOptions, server: usize) -> Result<Self, io::Error> {
+        match options.socket_path.as_ref().filter(|_| server == 0) {
+            #[cfg(unix)]
+            Some(path) => Ok(Transport::Unix(UnixStream::connect(path)?)),
+            #[cfg(not(unix))]
+            Some(_) => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "Unix sockets are not supported on this platform",
+            )),
+            None => {
+                let server = &options.servers()[server];
+                let addresses: Vec<_> = (server.hostname.as_str(), server.port)
+                    .to_socket_addrs()?
+                    .collect();
+                let stream = connect_any(&addresses, options.connect_timeout)?;
+                if let Some(time) = options.keepalive {
+                    let keepalive = TcpKeepalive::new().with_time(time);
+                    SockRef::from(&stream).set_tcp_keepalive(&keepalive)?;
+                }
+                Ok(Transport::Tcp(stream))
+            }
+        }
+    }
+
+    pub fn set_read_timeout(&self, timeout: Option<Duration>) -> Result<(), io::Error> {
+        match self {
+            Transport::Tcp(x) => x.set_read_timeout(timeout),
+            #[cfg(unix)]
+            Transport::Unix(x) => x.set_read_timeout(timeout),
+        }
+    }
+
+    pub fn set_write_timeout(&self, timeout: Option<Duration>) -> Result<(), io::Error> {
+        match self {
+            Transport::Tcp(x) => x.set_write_timeout(timeout),
+            #[cfg(unix)]
+            Transport::Unix(x) => x.set_write_timeout(timeout),
+        }
+    }
+}
+
+/// Tries the addresses in order and returns the first established connection.
+/// Returns the error of the last address if none is reachable.
+fn connect_any(addresses: &[SocketAddr], timeout: Duration) -> Result<TcpStream, io::Error> {
+    let mut last_error = None;
+    for address in addresses {
+        match TcpStream::connect_timeout(address, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(e) if e.kind() == io::ErrorKind::TimedOut => {
+                let message = format!("Could not connect to {} within {:?}", address, timeout);
+                last_error = Some(io::Error::new(io::ErrorKind::TimedOut, message));
+            }
+            Err(e) => last_error = Some(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "Hostname resolved to no addresses")
+    }))
+}
+
+impl Read for Transport {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self {
+            Transport::Tcp(x) => x.read(buf),
+            #[cfg(unix)]
+            Transport::Unix(x) => x.read(buf),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Transport::Tcp(x) => x.write(buf),
+            #[cfg(unix)]
+            Transport::Unix(x) => x.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Transport::Tcp(x) => x.flush(),
+            #[cfg(unix)]
+            Transport::Unix(x) => x.flush(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::connect_any;
+    use crate::binlog_client::BinlogClient;
+    use crate::constants::TIMEOUT_MESSAGE;
+    use crate::errors::Error;
+    use crate::replica_options::ReplicaOptions;
+    use crate::test_server::{Response, ServerConfig, TestServer};
+    use std::io;
+    use std::net::{SocketAddr, TcpListener};
+    use std::time::Duration;
+
+    #[test]
+    fn query_over_unix_socket() {
+        let path = std::env::temp_dir().join(format!("mysql_cdc_{}.sock", std::process::id()));
+        let config = ServerConfig {
+            socket_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let server = TestServer::start_with(config, |_| {
+            Response::rows(&["@@socket"], &[&[Some("/run/mysqld/mysqld.sock")]])
+        });
+
+        let options = ReplicaOptions {
+            hostname: "unreachable.invalid".to_string(),
+            socket_path: Some(path.clone()),
+            ..Default::default()
+        };
+        let result_set = BinlogClient::new(options).query("SELECT @@socket").unwrap();
+        assert_eq!(
+            Some("/run/mysqld/mysqld.sock".to_string()),
+            result_set.rows[0].get("@@socket").unwrap()
+        );
+
+        assert_eq!(vec!["SELECT @@socket"], server.join());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn connect_to_next_address() {
+        // Port of the dropped listener refuses connections
+        let closed = TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let open = listener.local_addr().unwrap();
+
+        let stream = connect_any(&[closed, open], Duration::from_secs(1)).unwrap();
+        assert_eq!(open, stream.peer_addr().unwrap());
+
+        let error = connect_any(&[closed], Duration::from_secs(1)).unwrap_err();
+        assert_eq!(io::ErrorKind::ConnectionRefused, error.kind());
+        let error = connect_any(&Vec::<SocketAddr>::new(), Duration::from_secs(1)).unwrap_err();
+        assert_eq!(io::ErrorKind::NotFound, error.kind());
+    }
+
+    #[test]
+    fn query_exceeding_read_timeout() {
+        let server = TestServer::start("8.0.36", |_| {
+            std::thread::sleep(Duration::from_millis(500));
+            Response::Ok
+        });
+
+        let options = ReplicaOptions {
+            hostname: "localhost".to_string(),
+            port: server.port,
+            read_timeout: Some(Duration::from_millis(100)),
+            write_timeout: Some(Duration::from_secs(1)),
+            keepalive: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+        match BinlogClient::new(options).query("SELECT SLEEP(1)") {
+            Err(Error::Timeout(x)) => assert_eq!(TIMEOUT_MESSAGE, x),
+            _ => panic!("Timeout error expected"),
+        }
+        server.join();
+    }
+}
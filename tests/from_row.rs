@@ -0,0 +1,93 @@
+#![cfg(feature = "derive")]
+
+use mysql_cdc::errors::Error;
+use mysql_cdc::events::row_events::from_row::FromRow;
+use mysql_cdc::events::row_events::mysql_value::MySqlValue;
+use mysql_cdc::events::row_events::row_data::RowData;
+use mysql_cdc::events::table_map_event::TableMapEvent;
+use mysql_cdc::metadata::table_metadata::TableMetadata;
+
+#[derive(FromRow, Debug, PartialEq)]
+struct User {
+    id: u32,
+    #[mysql_cdc(rename = "name")]
+    user_name: Option<String>,
+    balance: i64,
+}
+
+#[derive(FromRow, Debug, PartialEq)]
+struct UserTuple(u32, Option<String>, i64);
+
+fn create_table(column_names: Option<Vec<String>>) -> TableMapEvent {
+    TableMapEvent {
+        table_id: 1,
+        database_name: "db".to_string(),
+        table_name: "users".to_string(),
+        column_types: vec![3, 15, 8],
+        column_metadata: vec![0, 100, 0],
+        null_bitmap: vec![false, true, false],
+        table_metadata: Some(TableMetadata {
+            signedness: Some(vec![true, false]),
+            column_names,
+            ..Default::default()
+        }),
+    }
+}
+
+fn create_row() -> RowData {
+    RowData::new(vec![
+        Some(MySqlValue::Int(u32::MAX)),
+        Some(MySqlValue::String("Alice".to_string())),
+        Some(MySqlValue::BigInt(-5i64 as u64)),
+    ])
+}
+
+#[test]
+fn derive_maps_columns_by_name() {
+    let names = vec!["balance", "id", "name"];
+    let mut table = create_table(Some(names.iter().map(|x| x.to_string()).collect()));
+    table.column_types = vec![8, 3, 15];
+    table.column_metadata = vec![0, 0, 100];
+    table.table_metadata.as_mut().unwrap().signedness = Some(vec![false, true]);
+
+    let row = RowData::new(vec![
+        Some(MySqlValue::BigInt(-5i64 as u64)),
+        Some(MySqlValue::Int(u32::MAX)),
+        None,
+    ]);
+
+    let user = User::from_row(&row, &table).unwrap();
+    assert_eq!(
+        User {
+            id: u32::MAX,
+            user_name: None,
+            balance: -5
+        },
+        user
+    );
+}
+
+#[test]
+fn derive_maps_columns_by_ordinal() {
+    let table = create_table(None);
+    let user = UserTuple::from_row(&create_row(), &table).unwrap();
+    assert_eq!(UserTuple(u32::MAX, Some("Alice".to_string()), -5), user);
+
+    let user = User::from_row(&create_row(), &table).unwrap();
+    assert_eq!(Some("Alice".to_string()), user.user_name);
+}
+
+#[test]
+fn derive_reports_column_of_failed_conversion() {
+    #[derive(FromRow, Debug)]
+    #[allow(dead_code)]
+    struct InvalidUser {
+        id: i32,
+    }
+
+    let table = create_table(Some(vec!["id".to_string()]));
+    match InvalidUser::from_row(&create_row(), &table) {
+        Err(Error::ConversionError(x)) => assert_eq!("id", x.column),
+        x => panic!("Unexpected result {:?}", x),
+    }
+}